@@ -8,6 +8,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap())
                 .join("file_descriptor_set.bin"),
         )
-        .compile(&["proto/epp.proto"], &["proto/", "~/.local/include"])?;
+        .compile(
+            &["proto/epp.proto", "proto/admin/admin.proto"],
+            &["proto/", "~/.local/include"],
+        )?;
     Ok(())
 }
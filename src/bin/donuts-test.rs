@@ -279,6 +279,7 @@ async fn main() {
         None,
         None,
         None,
+        None,
         &mut cmd_tx_1,
     )
     .await
@@ -330,6 +331,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_1,
     )
@@ -381,6 +383,7 @@ async fn main() {
                 personal_registration: None,
                 keysys: None,
                 nominet_ext: None,
+                allocation_token: None,
             },
             &mut cmd_tx_1,
         )
@@ -400,6 +403,7 @@ async fn main() {
             None,
             None,
             None,
+            None,
             &mut cmd_tx_2,
         )
         .await
@@ -488,6 +492,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_1,
     )
@@ -540,6 +545,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_1,
     )
@@ -592,6 +598,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_1,
     )
@@ -601,9 +608,10 @@ async fn main() {
     // Register an Early Access domain
     info!("Create early access domain");
     info!("EAP domain: {}", eap_domain);
-    let eap_check = epp_proxy::client::domain::check(&eap_domain, None, None, None, &mut cmd_tx_1)
-        .await
-        .unwrap();
+    let eap_check =
+        epp_proxy::client::domain::check(&eap_domain, None, None, None, None, &mut cmd_tx_1)
+            .await
+            .unwrap();
     let eap_fee = eap_check
         .response
         .donuts_fee_check
@@ -661,6 +669,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_1,
     )
@@ -670,7 +679,7 @@ async fn main() {
     // Register a Premium domain name for 3 separate premium price points
     info!("Create 3 premium domains");
     let premium_check_1 =
-        epp_proxy::client::domain::check(premium_domain_1, None, None, None, &mut cmd_tx_1)
+        epp_proxy::client::domain::check(premium_domain_1, None, None, None, None, &mut cmd_tx_1)
             .await
             .unwrap();
     let premium_fee_1 = premium_check_1
@@ -735,6 +744,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_1,
     )
@@ -742,7 +752,7 @@ async fn main() {
     .unwrap();
 
     let premium_check_2 =
-        epp_proxy::client::domain::check(premium_domain_2, None, None, None, &mut cmd_tx_1)
+        epp_proxy::client::domain::check(premium_domain_2, None, None, None, None, &mut cmd_tx_1)
             .await
             .unwrap();
     let premium_fee_2 = premium_check_2
@@ -807,6 +817,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_1,
     )
@@ -814,7 +825,7 @@ async fn main() {
     .unwrap();
 
     let premium_check_3 =
-        epp_proxy::client::domain::check(premium_domain_3, None, None, None, &mut cmd_tx_1)
+        epp_proxy::client::domain::check(premium_domain_3, None, None, None, None, &mut cmd_tx_1)
             .await
             .unwrap();
     let premium_fee_3 = premium_check_3
@@ -874,6 +885,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_1,
     )
@@ -900,6 +912,7 @@ async fn main() {
         }),
         None,
         None,
+        None,
         &mut cmd_tx_2,
     )
     .await
@@ -936,6 +949,7 @@ async fn main() {
         }),
         None,
         None,
+        None,
         &mut cmd_tx_1,
     )
     .await
@@ -944,7 +958,7 @@ async fn main() {
     // Delete and Restore a Premium name
     info!("Deleting and restoring premiun name");
     let premium_check_dr =
-        epp_proxy::client::domain::check(premium_domain_dr, None, None, None, &mut cmd_tx_1)
+        epp_proxy::client::domain::check(premium_domain_dr, None, None, None, None, &mut cmd_tx_1)
             .await
             .unwrap();
     let premium_fee_dr = premium_check_dr
@@ -970,6 +984,7 @@ async fn main() {
 
     epp_proxy::client::rgp::request(
         premium_domain_dr,
+        None,
         Some(epp_proxy::client::fee::DonutsFeeData {
             sets: vec![epp_proxy::client::fee::DonutsFeeSet {
                 category: premium_fee_dr.category,
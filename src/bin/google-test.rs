@@ -284,7 +284,7 @@ async fn main() {
 
     // 2.2.2 - Perform a check to see that an ASCII domain label is available.
     info!("Finding available domain");
-    epp_proxy::client::domain::check(&ga_domain, None, None, None, &mut cmd_tx_ga_1)
+    epp_proxy::client::domain::check(&ga_domain, None, None, None, None, &mut cmd_tx_ga_1)
         .await
         .unwrap();
 
@@ -338,6 +338,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_ga_1,
     )
@@ -346,13 +347,13 @@ async fn main() {
 
     // 2.2.4 - Perform a check and verify that the ASCII domain label is no longer available
     info!("Checking domain was registered");
-    epp_proxy::client::domain::check(&ga_domain, None, None, None, &mut cmd_tx_ga_1)
+    epp_proxy::client::domain::check(&ga_domain, None, None, None, None, &mut cmd_tx_ga_1)
         .await
         .unwrap();
 
     // 2.3.2 - Perform a check to see that a Japanese IDN domain label is available
     info!("Finding available IDN domain");
-    epp_proxy::client::domain::check(&ga_domain_idn, None, None, None, &mut cmd_tx_ga_1)
+    epp_proxy::client::domain::check(&ga_domain_idn, None, None, None, None, &mut cmd_tx_ga_1)
         .await
         .unwrap();
 
@@ -406,6 +407,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_ga_1,
     )
@@ -414,13 +416,13 @@ async fn main() {
 
     // 2.3.4 - Perform a check and verify that the Japanese IDN domain label is no longer available
     info!("Checking IDN domain was registered");
-    epp_proxy::client::domain::check(&ga_domain_idn, None, None, None, &mut cmd_tx_ga_1)
+    epp_proxy::client::domain::check(&ga_domain_idn, None, None, None, None, &mut cmd_tx_ga_1)
         .await
         .unwrap();
 
     // 2.4.2 - Perform a check to see that domain name “test‑and‑validate.<registrar name>‑ga” is available
     info!("Finding available claims domain");
-    epp_proxy::client::domain::check(&ga_domain_claims, None, None, None, &mut cmd_tx_ga_1)
+    epp_proxy::client::domain::check(&ga_domain_claims, None, None, None, None, &mut cmd_tx_ga_1)
         .await
         .unwrap();
 
@@ -520,6 +522,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_ga_1,
     )
@@ -528,7 +531,7 @@ async fn main() {
 
     // 2.4.6 - Perform a check and verify that the domain name “test‑and‑validate.<registrar name>‑ga” is no longer available
     info!("Checking claims domain was registered");
-    epp_proxy::client::domain::check(&ga_domain_claims, None, None, None, &mut cmd_tx_ga_1)
+    epp_proxy::client::domain::check(&ga_domain_claims, None, None, None, None, &mut cmd_tx_ga_1)
         .await
         .unwrap();
 
@@ -544,12 +547,14 @@ async fn main() {
                     unit: epp_proxy::client::PeriodUnit::Years,
                     value: 2,
                 }),
+                custom_name: None,
                 phase: None,
                 sub_phase: None,
             }],
         }),
         None,
         None,
+        None,
         &mut cmd_tx_ga_1,
     )
     .await
@@ -616,6 +621,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_ga_1,
     )
@@ -624,7 +630,7 @@ async fn main() {
 
     // 2.5.4 - Perform a check and verify that the domain name “rich.<registrar name>‑ga” is no longer available.
     info!("Checking premium domain was registered");
-    epp_proxy::client::domain::check(&ga_domain_premium, None, None, None, &mut cmd_tx_ga_1)
+    epp_proxy::client::domain::check(&ga_domain_premium, None, None, None, None, &mut cmd_tx_ga_1)
         .await
         .unwrap();
 
@@ -865,7 +871,7 @@ async fn main() {
 
     // 2.9.2 - Query the info of the domain created in Step 2.2 and verify that it is still in the add grace period
     info!("Checking domain in AGP");
-    epp_proxy::client::domain::info(&ga_domain, None, None, None, None, &mut cmd_tx_ga_1)
+    epp_proxy::client::domain::info(&ga_domain, None, None, None, None, false, &mut cmd_tx_ga_1)
         .await
         .unwrap();
 
@@ -877,7 +883,7 @@ async fn main() {
 
     // 2.9.4 - Perform a check and verify that the domain label is now available
     info!("Checking domain now available");
-    epp_proxy::client::domain::check(&ga_domain, None, None, None, &mut cmd_tx_ga_1)
+    epp_proxy::client::domain::check(&ga_domain, None, None, None, None, &mut cmd_tx_ga_1)
         .await
         .unwrap();
 
@@ -891,6 +897,7 @@ async fn main() {
             None,
             None,
             None,
+            false,
             &mut cmd_tx_ga_1,
         )
         .await
@@ -906,9 +913,17 @@ async fn main() {
 
     // 2.10.3 - Query the info of the domain created in Step 2.3 and verify that it is not in the add grace period
     info!("Checking IDN domain not in AGP");
-    epp_proxy::client::domain::info(&ga_domain_idn, None, None, None, None, &mut cmd_tx_ga_1)
-        .await
-        .unwrap();
+    epp_proxy::client::domain::info(
+        &ga_domain_idn,
+        None,
+        None,
+        None,
+        None,
+        false,
+        &mut cmd_tx_ga_1,
+    )
+    .await
+    .unwrap();
 
     // 2.10.4 - Delete domain created in Step 2.3
     info!("Deleting IDN domain");
@@ -918,28 +933,44 @@ async fn main() {
 
     // 2.10.5 - Perform a check and verify that the domain label is still not available
     info!("Checking domain still not available");
-    epp_proxy::client::domain::check(&ga_domain_idn, None, None, None, &mut cmd_tx_ga_1)
+    epp_proxy::client::domain::check(&ga_domain_idn, None, None, None, None, &mut cmd_tx_ga_1)
         .await
         .unwrap();
 
     // 2.11.2 - Query the info of the domain used in Step 2.10 and verify that it has pending delete
     // status and is in the redemption grace period
     info!("Checking IDN domain in RGP");
-    epp_proxy::client::domain::info(&ga_domain_idn, None, None, None, None, &mut cmd_tx_ga_1)
-        .await
-        .unwrap();
+    epp_proxy::client::domain::info(
+        &ga_domain_idn,
+        None,
+        None,
+        None,
+        None,
+        false,
+        &mut cmd_tx_ga_1,
+    )
+    .await
+    .unwrap();
 
     // 2.11.3 - Restore the domain. Note that no restore report is required
     info!("Restoring IDN domain");
-    epp_proxy::client::rgp::request(&ga_domain_idn, None, &mut cmd_tx_ga_1)
+    epp_proxy::client::rgp::request(&ga_domain_idn, None, None, &mut cmd_tx_ga_1)
         .await
         .unwrap();
 
     // 2.11.4 - Query the info of the domain again verify that it no longer has pending delete status
     info!("Checking IDN domain no longer in RGP");
-    epp_proxy::client::domain::info(&ga_domain_idn, None, None, None, None, &mut cmd_tx_ga_1)
-        .await
-        .unwrap();
+    epp_proxy::client::domain::info(
+        &ga_domain_idn,
+        None,
+        None,
+        None,
+        None,
+        false,
+        &mut cmd_tx_ga_1,
+    )
+    .await
+    .unwrap();
 
     // 2.12.2 - Delete the domain used in Step 2.11
     info!("Deleting IDN domain");
@@ -951,10 +982,16 @@ async fn main() {
     info!("Waiting for RGP to expire");
     loop {
         tokio::time::sleep(std::time::Duration::from_secs(60)).await;
-        let res =
-            epp_proxy::client::domain::check(&ga_domain_idn, None, None, None, &mut cmd_tx_ga_1)
-                .await
-                .unwrap();
+        let res = epp_proxy::client::domain::check(
+            &ga_domain_idn,
+            None,
+            None,
+            None,
+            None,
+            &mut cmd_tx_ga_1,
+        )
+        .await
+        .unwrap();
         if res.response.avail {
             break;
         }
@@ -974,10 +1011,16 @@ async fn main() {
     let mut trans_domain_i = 1;
     let trans_domain_1 = loop {
         let trans_domain = format!("staclar-{}.{}", trans_domain_i, ga_tld);
-        let res =
-            epp_proxy::client::domain::check(&trans_domain, None, None, None, &mut cmd_tx_ga_1)
-                .await
-                .unwrap();
+        let res = epp_proxy::client::domain::check(
+            &trans_domain,
+            None,
+            None,
+            None,
+            None,
+            &mut cmd_tx_ga_1,
+        )
+        .await
+        .unwrap();
         if res.response.avail {
             break trans_domain;
         } else {
@@ -1019,6 +1062,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_ga_1,
     )
@@ -1035,6 +1079,7 @@ async fn main() {
         None,
         None,
         None,
+        None,
         &mut cmd_tx_ga_2,
     )
     .await
@@ -1055,10 +1100,16 @@ async fn main() {
     trans_domain_i += 1;
     let trans_domain_2 = loop {
         let trans_domain = format!("staclar-{}.{}", trans_domain_i, ga_tld);
-        let res =
-            epp_proxy::client::domain::check(&trans_domain, None, None, None, &mut cmd_tx_ga_1)
-                .await
-                .unwrap();
+        let res = epp_proxy::client::domain::check(
+            &trans_domain,
+            None,
+            None,
+            None,
+            None,
+            &mut cmd_tx_ga_1,
+        )
+        .await
+        .unwrap();
         if res.response.avail {
             break trans_domain;
         } else {
@@ -1100,6 +1151,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_ga_1,
     )
@@ -1116,6 +1168,7 @@ async fn main() {
         None,
         None,
         None,
+        None,
         &mut cmd_tx_ga_2,
     )
     .await
@@ -1136,10 +1189,16 @@ async fn main() {
     trans_domain_i += 1;
     let trans_domain_3 = loop {
         let trans_domain = format!("staclar-{}.{}", trans_domain_i, ga_tld);
-        let res =
-            epp_proxy::client::domain::check(&trans_domain, None, None, None, &mut cmd_tx_ga_1)
-                .await
-                .unwrap();
+        let res = epp_proxy::client::domain::check(
+            &trans_domain,
+            None,
+            None,
+            None,
+            None,
+            &mut cmd_tx_ga_1,
+        )
+        .await
+        .unwrap();
         if res.response.avail {
             break trans_domain;
         } else {
@@ -1181,6 +1240,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_ga_1,
     )
@@ -1197,6 +1257,7 @@ async fn main() {
         None,
         None,
         None,
+        None,
         &mut cmd_tx_ga_2,
     )
     .await
@@ -1241,9 +1302,17 @@ async fn main() {
 
     // 2.16.2.b - Do an info on the domain and verify its sponsoring client is now set to <registrar name>‑4
     info!("Checking domain sponsorship changed");
-    epp_proxy::client::domain::info(&trans_domain_1, None, None, None, None, &mut cmd_tx_ga_2)
-        .await
-        .unwrap();
+    epp_proxy::client::domain::info(
+        &trans_domain_1,
+        None,
+        None,
+        None,
+        None,
+        false,
+        &mut cmd_tx_ga_2,
+    )
+    .await
+    .unwrap();
 
     // 2.17.1 - Using account <registrar name>‑3 deny a pending transfer away from this registrar on the domain created in Step 2.14
     info!("Denying transfer");
@@ -1279,6 +1348,7 @@ async fn main() {
         None,
         None,
         None,
+        false,
         &mut cmd_tx_ga_2,
     )
     .await
@@ -1375,6 +1445,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_sunrise,
     )
@@ -403,7 +403,7 @@ async fn main() {
 
     // 2.3.1.18 Check Domain (Domain Available for Registration)
     info!("Checking domain");
-    epp_proxy::client::domain::check("example.org", None, None, None, &mut cmd_tx)
+    epp_proxy::client::domain::check("example.org", None, None, None, None, &mut cmd_tx)
         .await
         .unwrap();
 
@@ -445,6 +445,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx,
     )
@@ -453,13 +454,13 @@ async fn main() {
 
     // 2.3.1.20 Check Domain (Domain Not Available for Registration)
     info!("Checking domain not available");
-    epp_proxy::client::domain::check("example.org", None, None, None, &mut cmd_tx)
+    epp_proxy::client::domain::check("example.org", None, None, None, None, &mut cmd_tx)
         .await
         .unwrap();
 
     // 2.3.1.21 Query Domain
     info!("Querying domain");
-    epp_proxy::client::domain::info("example.org", None, None, None, None, &mut cmd_tx)
+    epp_proxy::client::domain::info("example.org", None, None, None, None, false, &mut cmd_tx)
         .await
         .unwrap();
 
@@ -553,7 +554,7 @@ async fn main() {
 
     // 2.3.1.30 Check Domain (Domain Available for Registration)
     info!("Checking domain");
-    epp_proxy::client::domain::check("domain.org", None, None, None, &mut cmd_tx)
+    epp_proxy::client::domain::check("domain.org", None, None, None, None, &mut cmd_tx)
         .await
         .unwrap();
 
@@ -595,6 +596,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx,
     )
@@ -604,7 +606,7 @@ async fn main() {
     // 2.3.1.32 Query Domain
     info!("Querying domain");
     let domain_info =
-        epp_proxy::client::domain::info("domain.org", None, None, None, None, &mut cmd_tx)
+        epp_proxy::client::domain::info("domain.org", None, None, None, None, false, &mut cmd_tx)
             .await
             .unwrap();
 
@@ -621,6 +623,7 @@ async fn main() {
         None,
         None,
         None,
+        None,
         &mut cmd_tx,
     )
     .await
@@ -748,6 +751,7 @@ async fn main() {
         None,
         None,
         None,
+        None,
         &mut cmd_tx,
     )
     .await
@@ -806,6 +810,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx
     )
@@ -850,6 +855,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx
     )
@@ -894,6 +900,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx
     )
@@ -913,6 +920,7 @@ async fn main() {
         None,
         None,
         None,
+        None,
         &mut cmd_tx
     )
     .await
@@ -956,6 +964,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx
     )
@@ -983,7 +992,7 @@ async fn main() {
 
     // 2.4.1.1 Check Domain (Domain Available for Registration)
     info!("Checking DNSSEC domain");
-    epp_proxy::client::domain::check("dsdomain1.org", None, None, None, &mut cmd_tx)
+    epp_proxy::client::domain::check("dsdomain1.org", None, None, None, None, &mut cmd_tx)
         .await
         .unwrap();
 
@@ -1036,6 +1045,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx,
     )
@@ -1098,6 +1108,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx,
     )
@@ -1106,7 +1117,7 @@ async fn main() {
 
     // 2.4.1.4 Query domain that has DS Data
     info!("Querying DNSSEC domain");
-    epp_proxy::client::domain::info("dsdomain1.org", None, None, None, None, &mut cmd_tx)
+    epp_proxy::client::domain::info("dsdomain1.org", None, None, None, None, false, &mut cmd_tx)
         .await
         .unwrap();
 
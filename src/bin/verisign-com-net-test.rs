@@ -133,7 +133,7 @@ async fn main() {
     // Using your OT&E1 account, perform a CHECK domain command until you find an available domain
     info!(
         "{:#?}",
-        epp_proxy::client::domain::check(domain, None, None, None, &mut cmd_tx_1)
+        epp_proxy::client::domain::check(domain, None, None, None, None, &mut cmd_tx_1)
             .await
             .unwrap()
     );
@@ -162,6 +162,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx_1,
     )
@@ -301,7 +302,7 @@ async fn main() {
     info!("Getting domain info");
     info!(
         "{:#?}",
-        epp_proxy::client::domain::info(domain, None, None, None, None, &mut cmd_tx_1)
+        epp_proxy::client::domain::info(domain, None, None, None, None, false, &mut cmd_tx_1)
             .await
             .unwrap()
     );
@@ -356,7 +357,7 @@ async fn main() {
     info!("Getting domain info");
     info!(
         "{:#?}",
-        epp_proxy::client::domain::info(domain, None, None, None, None, &mut cmd_tx_1)
+        epp_proxy::client::domain::info(domain, None, None, None, None, false, &mut cmd_tx_1)
             .await
             .unwrap()
     );
@@ -445,6 +446,7 @@ async fn main() {
         None,
         None,
         None,
+        None,
         &mut cmd_tx_1,
     )
     .await
@@ -470,6 +472,7 @@ async fn main() {
             None,
             None,
             None,
+            false,
             &mut cmd_tx_2
         )
         .await
@@ -491,6 +494,7 @@ async fn main() {
             None,
             None,
             None,
+            None,
             &mut cmd_tx_2
         )
         .await
@@ -553,6 +557,7 @@ async fn main() {
             None,
             None,
             None,
+            None,
             &mut cmd_tx_1
         )
         .await
@@ -593,6 +598,7 @@ async fn main() {
             domain,
             (renew_res.response.data.new_expiry_date.unwrap().month() % 12) + 1,
             15,
+            None,
             &mut cmd_tx_2
         )
         .await
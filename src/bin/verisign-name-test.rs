@@ -170,7 +170,7 @@ async fn main() {
     info!("Checking second level domain");
     info!(
         "{:#?}",
-        epp_proxy::client::domain::check(&test_2ld, None, None, None, &mut cmd_tx)
+        epp_proxy::client::domain::check(&test_2ld, None, None, None, None, &mut cmd_tx)
             .await
             .unwrap()
     );
@@ -211,6 +211,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx,
     )
@@ -260,6 +261,7 @@ async fn main() {
         None,
         None,
         None,
+        None,
         &mut cmd_tx,
     )
     .await
@@ -305,7 +307,7 @@ async fn main() {
     info!("Getting second level domain info");
     info!(
         "{:#?}",
-        epp_proxy::client::domain::info(&test_2ld, None, None, None, None, &mut cmd_tx)
+        epp_proxy::client::domain::info(&test_2ld, None, None, None, None, false, &mut cmd_tx)
             .await
             .unwrap()
     );
@@ -325,7 +327,7 @@ async fn main() {
     info!("Checking third level domain");
     info!(
         "{:#?}",
-        epp_proxy::client::domain::check(&test_3ld, None, None, None, &mut cmd_tx)
+        epp_proxy::client::domain::check(&test_3ld, None, None, None, None, &mut cmd_tx)
             .await
             .unwrap()
     );
@@ -366,6 +368,7 @@ async fn main() {
             personal_registration: None,
             keysys: None,
             nominet_ext: None,
+            allocation_token: None,
         },
         &mut cmd_tx,
     )
@@ -415,6 +418,7 @@ async fn main() {
         None,
         None,
         None,
+        None,
         &mut cmd_tx,
     )
     .await
@@ -460,7 +464,7 @@ async fn main() {
     info!("Getting third level domain info");
     info!(
         "{:#?}",
-        epp_proxy::client::domain::info(&test_3ld, None, None, None, None, &mut cmd_tx)
+        epp_proxy::client::domain::info(&test_3ld, None, None, None, None, false, &mut cmd_tx)
             .await
             .unwrap()
     );
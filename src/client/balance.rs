@@ -27,7 +27,7 @@ pub enum CreditThreshold {
 /// # Arguments
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn balance_info(
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<BalanceResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -19,6 +19,27 @@ pub struct CheckResponse {
     pub reason: Option<String>,
 }
 
+#[derive(Debug)]
+pub struct BatchCheckRequest {
+    pub(super) ids: Vec<String>,
+    pub return_path: Sender<BatchCheckResponse>,
+}
+
+/// Response to a check of several contact IDs at once
+#[derive(Debug)]
+pub struct BatchCheckResponse {
+    pub results: Vec<BatchCheckResult>,
+}
+
+#[derive(Debug)]
+pub struct BatchCheckResult {
+    pub id: String,
+    /// Is the contact available for creation commands
+    pub avail: bool,
+    /// An optional reason for the ID's status
+    pub reason: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct InfoRequest {
     pub(super) id: String,
@@ -280,7 +301,7 @@ pub struct PanData {
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn check(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<CheckResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -294,6 +315,27 @@ pub async fn check(
     .await
 }
 
+/// Checks if several contact IDs exist in one command
+///
+/// # Arguments
+/// * `ids` - The IDs in question
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn batch_check(
+    ids: &[String],
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<BatchCheckResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::ContactBatchCheck(Box::new(BatchCheckRequest {
+            ids: ids.to_vec(),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
 /// Fetches information about a specific contact
 ///
 /// # Arguments
@@ -301,7 +343,7 @@ pub async fn check(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn info(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<InfoResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -354,7 +396,7 @@ pub struct NewContactData {
 pub async fn create(
     id: &str,
     data: NewContactData,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<CreateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -389,7 +431,7 @@ pub async fn create(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn delete(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<DeleteResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -446,7 +488,7 @@ pub async fn update(
     add_statuses: Vec<Status>,
     remove_statuses: Vec<Status>,
     new_data: UpdateContactData,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<UpdateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -483,7 +525,7 @@ pub async fn update(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn transfer_query(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -506,7 +548,7 @@ pub async fn transfer_query(
 pub async fn transfer_request(
     id: &str,
     auth_info: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -530,7 +572,7 @@ pub async fn transfer_request(
 pub async fn transfer_accept(
     id: &str,
     auth_info: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -554,7 +596,7 @@ pub async fn transfer_accept(
 pub async fn transfer_reject(
     id: &str,
     auth_info: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
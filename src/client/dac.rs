@@ -62,7 +62,7 @@ pub struct DACUsageResponse {
 pub async fn domain(
     name: &str,
     env: DACEnv,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<DACDomainResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -84,7 +84,7 @@ pub async fn domain(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn usage(
     env: DACEnv,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<DACUsageResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -105,7 +105,7 @@ pub async fn usage(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn limits(
     env: DACEnv,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<DACUsageResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
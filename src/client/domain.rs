@@ -10,9 +10,17 @@ pub struct CheckRequest {
     pub(super) fee_check: Option<fee::FeeCheck>,
     pub(super) launch_check: Option<launch::LaunchAvailabilityCheck>,
     pub(super) keysys: Option<keysys::DomainCheck>,
+    pub(super) allocation_token: Option<String>,
     pub return_path: Sender<CheckResponse>,
 }
 
+#[derive(Debug)]
+pub struct BatchCheckRequest {
+    pub(crate) names: Vec<String>,
+    pub(super) fee_check: Option<fee::FeeCheck>,
+    pub return_path: Sender<BatchCheckResponse>,
+}
+
 #[derive(Debug)]
 pub struct ClaimsCheckRequest {
     pub(super) name: String,
@@ -40,6 +48,25 @@ pub struct CheckResponse {
     pub eurid_idn: Option<super::eurid::Idn>,
 }
 
+/// Response to a check of several domains at once
+#[derive(Debug)]
+pub struct BatchCheckResponse {
+    /// Results in the order the registry returned them
+    pub results: Vec<BatchCheckResult>,
+}
+
+#[derive(Debug)]
+pub struct BatchCheckResult {
+    pub name: String,
+    /// Is the domain available for registration
+    pub avail: bool,
+    /// An optional reason for the domain's status
+    pub reason: Option<String>,
+    /// Fee information (if supplied by the registry)
+    pub fee_check: Option<fee::FeeCheckData>,
+    pub donuts_fee_check: Option<fee::DonutsFeeData>,
+}
+
 /// Response to a domain claims check query
 #[derive(Debug)]
 pub struct ClaimsCheckResponse {
@@ -64,6 +91,7 @@ pub struct InfoRequest {
     pub(super) launch_info: Option<launch::LaunchInfo>,
     pub(super) hosts: Option<InfoHost>,
     pub(super) eurid_data: Option<super::eurid::DomainInfoRequest>,
+    pub(super) allocation_token: bool,
     pub return_path: Sender<InfoResponse>,
 }
 
@@ -112,6 +140,8 @@ pub struct InfoResponse {
     pub personal_registration: Option<super::personal_registration::PersonalRegistrationInfo>,
     pub keysys: Option<super::keysys::DomainInfo>,
     pub nominet_ext: Option<super::nominet::DomainInfo>,
+    /// RFC 8495 allocation token, if requested and the client is authorised to see it
+    pub allocation_token: Option<String>,
 }
 
 /// Additional contact associated with a domain
@@ -184,6 +214,7 @@ pub struct CreateRequest {
         Option<super::personal_registration::PersonalRegistrationInfo>,
     pub(super) keysys: Option<super::keysys::DomainCreate>,
     pub(super) nominet_ext: Option<super::nominet::DomainCreate>,
+    pub(super) allocation_token: Option<String>,
     pub return_path: Sender<CreateResponse>,
 }
 
@@ -282,6 +313,7 @@ pub struct VerisignSyncRequest {
     pub(super) name: String,
     pub(super) month: u32,
     pub(super) day: u32,
+    pub(super) fee_agreement: Option<fee::FeeAgreement>,
     pub return_path: Sender<UpdateResponse>,
 }
 
@@ -294,6 +326,7 @@ pub struct RenewRequest {
     pub(super) donuts_fee_agreement: Option<fee::DonutsFeeData>,
     pub(super) isnic_payment: Option<super::isnic::PaymentInfo>,
     pub(super) keysys: Option<super::keysys::DomainRenew>,
+    pub(super) allocation_token: Option<String>,
     pub return_path: Sender<RenewResponse>,
 }
 
@@ -332,6 +365,7 @@ pub struct TransferRequestRequest {
     pub(super) donuts_fee_agreement: Option<fee::DonutsFeeData>,
     pub(super) eurid_data: Option<super::eurid::DomainTransfer>,
     pub(super) keysys: Option<super::keysys::DomainTransfer>,
+    pub(super) allocation_token: Option<String>,
     pub return_path: Sender<TransferResponse>,
 }
 
@@ -406,13 +440,15 @@ pub struct PanData {
 /// # Arguments
 /// * `domain` - The domain in question
 /// * `launch_check` - Launch availability info
+/// * `allocation_token` - RFC 8495 allocation token for a reserved or premium domain
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn check(
     domain: &str,
     fee_check: Option<fee::FeeCheck>,
     launch_check: Option<launch::LaunchAvailabilityCheck>,
     keysys: Option<keysys::DomainCheck>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    allocation_token: Option<&str>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<CheckResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -422,6 +458,33 @@ pub async fn check(
             fee_check,
             launch_check,
             keysys,
+            allocation_token: allocation_token.map(Into::into),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Checks if several domain names are available in one command
+///
+/// The caller is responsible for keeping the number of names within the registry's limit.
+///
+/// # Arguments
+/// * `domains` - The domains in question
+/// * `fee_check` - Fee information to request for every domain
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn batch_check(
+    domains: &[String],
+    fee_check: Option<fee::FeeCheck>,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<BatchCheckResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::DomainBatchCheck(Box::new(BatchCheckRequest {
+            names: domains.to_vec(),
+            fee_check,
             return_path: sender,
         })),
         receiver,
@@ -438,7 +501,7 @@ pub async fn check(
 pub async fn launch_claims_check(
     domain: &str,
     launch_check: launch::LaunchClaimsCheck,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<ClaimsCheckResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -460,7 +523,7 @@ pub async fn launch_claims_check(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn launch_trademark_check(
     domain: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<ClaimsCheckResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -478,6 +541,7 @@ pub async fn launch_trademark_check(
 ///
 /// # Arguments
 /// * `domain` - The domain in question
+/// * `allocation_token` - Request the domain's RFC 8495 allocation token
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn info(
     domain: &str,
@@ -485,7 +549,8 @@ pub async fn info(
     hosts: Option<InfoHost>,
     launch_info: Option<launch::LaunchInfo>,
     eurid_data: Option<super::eurid::DomainInfoRequest>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    allocation_token: bool,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<InfoResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -496,6 +561,7 @@ pub async fn info(
             hosts,
             launch_info,
             eurid_data,
+            allocation_token,
             return_path: sender,
         })),
         receiver,
@@ -519,6 +585,7 @@ pub struct CreateInfo<'a> {
     pub personal_registration: Option<super::personal_registration::PersonalRegistrationInfo>,
     pub keysys: Option<super::keysys::DomainCreate>,
     pub nominet_ext: Option<super::nominet::DomainCreate>,
+    pub allocation_token: Option<&'a str>,
 }
 
 /// Registers a new domain
@@ -533,7 +600,7 @@ pub struct CreateInfo<'a> {
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn create(
     info: CreateInfo<'_>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<CreateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -554,6 +621,7 @@ pub async fn create(
             personal_registration: info.personal_registration,
             keysys: info.keysys,
             nominet_ext: info.nominet_ext,
+            allocation_token: info.allocation_token.map(Into::into),
             return_path: sender,
         })),
         receiver,
@@ -572,7 +640,7 @@ pub async fn delete(
     donuts_fee_agreement: Option<fee::DonutsFeeData>,
     eurid_data: Option<super::eurid::DomainDelete>,
     keysys: Option<super::keysys::DomainDelete>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<DeleteResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -618,7 +686,7 @@ pub struct UpdateInfo<'a> {
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn update(
     info: UpdateInfo<'_>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<UpdateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -646,15 +714,20 @@ pub async fn update(
 
 /// Performs a Verisign ConsoliDate
 ///
+/// The pro-rated fee can be previewed beforehand with [`verisign_sync_fee_check`].
+///
 /// # Arguments
 /// * `domain` - The domain to be updated
 /// * `month` - Month to move renewal to
 /// * `day` - Day of month to move renewal to
+/// * `fee_agreement` - Agreement to the pro-rated sync fee, if required by the registry
+/// * `client_sender` - Reference to the tokio channel into the client
 pub async fn verisign_sync(
     domain: &str,
     month: u32,
     day: u32,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    fee_agreement: Option<fee::FeeAgreement>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<UpdateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -663,6 +736,7 @@ pub async fn verisign_sync(
             name: domain.to_string(),
             month,
             day,
+            fee_agreement,
             return_path: sender,
         })),
         receiver,
@@ -670,12 +744,40 @@ pub async fn verisign_sync(
     .await
 }
 
+/// Fetches the pro-rated fee for a Verisign ConsoliDate, as a fee check for the custom `sync`
+/// command
+///
+/// Only registries that negotiated fee-1.0 can quote custom commands.
+///
+/// # Arguments
+/// * `domain` - The domain to be updated
+/// * `currency` - Currency to quote the fee in, if not the registry default
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn verisign_sync_fee_check(
+    domain: &str,
+    currency: Option<String>,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<CheckResponse>, super::Error> {
+    let fee_check = fee::FeeCheck {
+        currency,
+        commands: vec![fee::FeeCheckCommand {
+            command: fee::Command::Custom,
+            period: None,
+            custom_name: Some("sync".to_string()),
+            phase: None,
+            sub_phase: None,
+        }],
+    };
+    check(domain, Some(fee_check), None, None, None, client_sender).await
+}
+
 /// Renews a domain name
 ///
 /// # Arguments
 /// * `domain` - The domain in question
 /// * `add_period` - How much time to add to the domain
 /// * `cur_expiry_date` - The current expiry date
+/// * `allocation_token` - RFC 8495 allocation token for a reserved or premium domain
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn renew(
     domain: &str,
@@ -685,7 +787,8 @@ pub async fn renew(
     donuts_fee_agreement: Option<fee::DonutsFeeData>,
     isnic_payment: Option<super::isnic::PaymentInfo>,
     keysys: Option<super::keysys::DomainRenew>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    allocation_token: Option<&str>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<RenewResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -698,6 +801,7 @@ pub async fn renew(
             donuts_fee_agreement,
             isnic_payment,
             keysys,
+            allocation_token: allocation_token.map(Into::into),
             return_path: sender,
         })),
         receiver,
@@ -714,7 +818,7 @@ pub async fn renew(
 pub async fn transfer_query(
     domain: &str,
     auth_info: Option<&str>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -735,6 +839,7 @@ pub async fn transfer_query(
 /// * `domain` - The domain to be transferred
 /// * `add_period` - How much time to add to the domain's expiry on transfer
 /// * `auth_info` - Auth info for the domain
+/// * `allocation_token` - RFC 8495 allocation token for a reserved or premium domain
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn transfer_request(
     domain: &str,
@@ -744,7 +849,8 @@ pub async fn transfer_request(
     donuts_fee_agreement: Option<fee::DonutsFeeData>,
     eurid_data: Option<super::eurid::DomainTransfer>,
     keysys: Option<super::keysys::DomainTransfer>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    allocation_token: Option<&str>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -757,6 +863,7 @@ pub async fn transfer_request(
             donuts_fee_agreement,
             eurid_data,
             keysys,
+            allocation_token: allocation_token.map(Into::into),
             return_path: sender,
         })),
         receiver,
@@ -773,7 +880,7 @@ pub async fn transfer_request(
 pub async fn transfer_cancel(
     domain: &str,
     auth_info: Option<&str>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -797,7 +904,7 @@ pub async fn transfer_cancel(
 pub async fn transfer_accept(
     domain: &str,
     auth_info: Option<&str>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -821,7 +928,7 @@ pub async fn transfer_accept(
 pub async fn transfer_reject(
     domain: &str,
     auth_info: Option<&str>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -230,7 +230,7 @@ pub struct PanData {
 pub async fn check(
     email: &str,
     fee_check: Option<fee::FeeCheck>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<CheckResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -253,7 +253,7 @@ pub async fn check(
 pub async fn info(
     email: &str,
     auth_info: Option<&str>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<InfoResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -290,7 +290,7 @@ pub struct CreateInfo<'a> {
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn create(
     info: CreateInfo<'_>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<CreateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -318,7 +318,7 @@ pub async fn create(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn delete(
     email: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<DeleteResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -354,7 +354,7 @@ pub struct UpdateInfo<'a> {
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn update(
     info: UpdateInfo<'_>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<UpdateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -386,7 +386,7 @@ pub async fn renew(
     add_period: Option<super::Period>,
     cur_expiry_date: DateTime<Utc>,
     fee_agreement: Option<fee::FeeAgreement>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<RenewResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -412,7 +412,7 @@ pub async fn renew(
 pub async fn transfer_query(
     email: &str,
     auth_info: Option<&str>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -439,7 +439,7 @@ pub async fn transfer_request(
     add_period: Option<super::Period>,
     auth_info: &str,
     fee_agreement: Option<fee::FeeAgreement>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -465,7 +465,7 @@ pub async fn transfer_request(
 pub async fn transfer_cancel(
     email: &str,
     auth_info: Option<&str>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -489,7 +489,7 @@ pub async fn transfer_cancel(
 pub async fn transfer_accept(
     email: &str,
     auth_info: Option<&str>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -513,7 +513,7 @@ pub async fn transfer_accept(
 pub async fn transfer_reject(
     email: &str,
     auth_info: Option<&str>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -5,10 +5,11 @@ use std::convert::{TryFrom, TryInto};
 use regex::Regex;
 
 use super::super::contact::{
-    Address, CheckRequest, CheckResponse, CreateRequest, CreateResponse, DeleteRequest,
-    DeleteResponse, DisclosureType, EntityType, InfoRequest, InfoResponse, PanData,
-    QualifiedLawyerInfo, Status, TransferData, TransferQueryRequest, TransferRequestRequest,
-    TransferResponse, UpdateRequest, UpdateResponse,
+    Address, BatchCheckRequest, BatchCheckResponse, BatchCheckResult, CheckRequest,
+    CheckResponse, CreateRequest, CreateResponse, DeleteRequest, DeleteResponse, DisclosureType,
+    EntityType, InfoRequest, InfoResponse, PanData, QualifiedLawyerInfo, Status, TransferData,
+    TransferQueryRequest, TransferRequestRequest, TransferResponse, UpdateRequest,
+    UpdateResponse,
 };
 use super::super::{proto, Error, Phone, Response};
 use super::router::HandleReqReturn;
@@ -577,6 +578,57 @@ pub fn handle_check_response<M: crate::metrics::Metrics>(
     }
 }
 
+pub fn handle_batch_check(
+    client: &ServerFeatures,
+    req: &BatchCheckRequest,
+) -> HandleReqReturn<BatchCheckResponse> {
+    if !client.contact_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    if req.ids.is_empty() {
+        return Err(Err(Error::Err("at least one contact must be checked".to_string())));
+    }
+    for id in &req.ids {
+        check_id(id)?;
+    }
+    let command = proto::EPPCheck::ContactBatch(proto::contact::EPPContactBatchCheck {
+        ids: req.ids.clone(),
+    });
+    let mut ext = vec![];
+    super::verisign::handle_verisign_namestore_erratum(client, &mut ext);
+    Ok((
+        proto::EPPCommandType::Check(command),
+        match ext.is_empty() {
+            true => None,
+            false => Some(ext),
+        },
+    ))
+}
+
+pub fn handle_batch_check_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<BatchCheckResponse> {
+    match response.data {
+        Some(value) => match value.value {
+            proto::EPPResultDataValue::EPPContactCheckResult(contact_check) => {
+                Ok(BatchCheckResponse {
+                    results: contact_check
+                        .data
+                        .into_iter()
+                        .map(|d| BatchCheckResult {
+                            id: d.id.id,
+                            avail: d.id.available,
+                            reason: d.reason,
+                        })
+                        .collect(),
+                })
+            }
+            _ => Err(Error::ServerInternal),
+        },
+        None => Err(Error::ServerInternal),
+    }
+}
+
 pub fn handle_info(client: &ServerFeatures, req: &InfoRequest) -> HandleReqReturn<InfoResponse> {
     if !client.contact_supported {
         return Err(Err(Error::Unsupported));
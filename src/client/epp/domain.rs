@@ -3,13 +3,13 @@
 use std::convert::{TryFrom, TryInto};
 
 use super::super::domain::{
-    CheckRequest, CheckResponse, ClaimsCheckRequest, ClaimsCheckResponse, CreateData,
-    CreateRequest, CreateResponse, DeleteRequest, DeleteResponse, InfoContact, InfoHost,
-    InfoNameserver, InfoRequest, InfoResponse, PanData, RenewData, RenewRequest, RenewResponse,
-    SecDNSDSData, SecDNSData, SecDNSDataType, SecDNSKeyData, Status, TrademarkCheckRequest,
-    TransferAcceptRejectRequest, TransferData, TransferQueryRequest, TransferRequestRequest,
-    TransferResponse, UpdateObject, UpdateRequest, UpdateResponse, UpdateSecDNSRemove,
-    VerisignSyncRequest,
+    BatchCheckRequest, BatchCheckResponse, BatchCheckResult, CheckRequest, CheckResponse,
+    ClaimsCheckRequest, ClaimsCheckResponse, CreateData, CreateRequest, CreateResponse,
+    DeleteRequest, DeleteResponse, InfoContact, InfoHost, InfoNameserver, InfoRequest,
+    InfoResponse, PanData, RenewData, RenewRequest, RenewResponse, SecDNSDSData, SecDNSData,
+    SecDNSDataType, SecDNSKeyData, Status, TrademarkCheckRequest, TransferAcceptRejectRequest,
+    TransferData, TransferQueryRequest, TransferRequestRequest, TransferResponse, UpdateObject,
+    UpdateRequest, UpdateResponse, UpdateSecDNSRemove, VerisignSyncRequest,
 };
 use super::super::{fee, launch, proto, Error, Period, PeriodUnit, Response};
 use super::router::HandleReqReturn;
@@ -287,6 +287,14 @@ impl
             None => None,
         };
 
+        let allocation_token = match extension {
+            Some(ext) => ext.value.iter().find_map(|p| match p {
+                proto::EPPResponseExtensionType::AllocationToken(t) => Some(t.token.clone()),
+                _ => None,
+            }),
+            None => None,
+        };
+
         Ok(InfoResponse {
             eurid_idn: super::eurid::extract_eurid_idn_singular(extension, domain_info.name.as_str())?,
             name: domain_info.name,
@@ -362,6 +370,7 @@ impl
             personal_registration,
             keysys,
             nominet_ext,
+            allocation_token,
         })
     }
 }
@@ -749,6 +758,407 @@ pub(crate) fn check_pass<T>(id: &str) -> Result<(), Response<T>> {
     }
 }
 
+/// Adds an RFC 8495 allocation token to a command, if one was given
+fn handle_allocation_token<T>(
+    client: &ServerFeatures,
+    allocation_token: &Option<String>,
+    exts: &mut Vec<proto::EPPCommandExtensionType>,
+) -> Result<(), Response<T>> {
+    if let Some(token) = allocation_token {
+        if client.allocation_token_supported {
+            exts.push(proto::EPPCommandExtensionType::AllocationToken(
+                proto::allocation_token::EPPAllocationToken {
+                    token: token.clone(),
+                },
+            ));
+        } else {
+            return Err(Err(Error::Unsupported));
+        }
+    }
+    Ok(())
+}
+
+/// Builds the fee extension for a check command in whichever fee dialect the server supports
+///
+/// # Arguments
+/// * `client` - Features of the current session
+/// * `names` - Every domain being checked
+/// * `fee_check` - Fee information requested
+fn fee_check_extension(
+    client: &ServerFeatures,
+    names: &[&str],
+    fee_check: &fee::FeeCheck,
+) -> Result<Vec<proto::EPPCommandExtensionType>, Error> {
+    let fee_command = |c: &fee::FeeCheckCommand| -> Result<proto::fee::EPPFeeCommand, Error> {
+        Ok(proto::fee::EPPFeeCommand {
+            command: match (&c.command).into() {
+                Some(n) => n,
+                None => return Err(Error::Unsupported),
+            },
+            phase: c.phase.as_ref().map(Into::into),
+            subphase: c.sub_phase.as_ref().map(Into::into),
+        })
+    };
+    let mut ext = vec![];
+
+    if client.fee_supported {
+        ext.push(proto::EPPCommandExtensionType::EPPFee10Check(
+            proto::fee::EPPFee10Check {
+                currency: fee_check.currency.to_owned(),
+                commands: fee_check
+                    .commands
+                    .iter()
+                    .map(|c| {
+                        // Only fee-1.0 has custom commands, such as Verisign's sync
+                        let (name, custom_name) = match (c.command, &c.custom_name) {
+                            (fee::Command::Custom, Some(n)) => {
+                                (proto::fee::EPPFeeCommandType::Custom, Some(n.clone()))
+                            }
+                            (fee::Command::Custom, None) => {
+                                return Err(Error::Err(
+                                    "custom fee commands must have a name".to_string(),
+                                ))
+                            }
+                            (command, _) => match (&command).into() {
+                                Some(n) => (n, None),
+                                None => return Err(Error::Unsupported),
+                            },
+                        };
+                        Ok(proto::fee::EPPFee10CheckCommand {
+                            name,
+                            custom_name,
+                            phase: c.phase.as_ref().map(Into::into),
+                            subphase: c.sub_phase.as_ref().map(Into::into),
+                            period: c.period.as_ref().map(Into::into),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            },
+        ))
+    } else if client.fee_011_supported {
+        for c in &fee_check.commands {
+            ext.push(proto::EPPCommandExtensionType::EPPFee011Check(
+                proto::fee::EPPFee011Check {
+                    currency: fee_check.currency.to_owned(),
+                    command: fee_command(c)?,
+                    period: c.period.as_ref().map(Into::into),
+                },
+            ));
+        }
+    } else if client.fee_09_supported {
+        let mut objects = vec![];
+        for name in names {
+            for c in &fee_check.commands {
+                objects.push(proto::fee::EPPFee09CheckObject {
+                    object_uri: Some("urn:ietf:params:xml:ns:domain-1.0".to_string()),
+                    object_id: proto::fee::EPPFee10ObjectID {
+                        element: "name".to_string(),
+                        id: name.to_string(),
+                    },
+                    currency: fee_check.currency.to_owned(),
+                    command: fee_command(c)?,
+                    period: c.period.as_ref().map(Into::into),
+                });
+            }
+        }
+        ext.push(proto::EPPCommandExtensionType::EPPFee09Check(
+            proto::fee::EPPFee09Check { objects },
+        ))
+    } else if client.fee_08_supported {
+        let mut domains = vec![];
+        for name in names {
+            for c in &fee_check.commands {
+                domains.push(proto::fee::EPPFee08CheckDomain {
+                    name: name.to_string(),
+                    currency: fee_check.currency.to_owned(),
+                    command: fee_command(c)?,
+                    period: c.period.as_ref().map(Into::into),
+                });
+            }
+        }
+        ext.push(proto::EPPCommandExtensionType::EPPFee08Check(
+            proto::fee::EPPFee08Check { domains },
+        ))
+    } else if client.fee_07_supported {
+        let mut domains = vec![];
+        for name in names {
+            for c in &fee_check.commands {
+                domains.push(proto::fee::EPPFee07CheckDomain {
+                    name: name.to_string(),
+                    currency: fee_check.currency.to_owned(),
+                    command: fee_command(c)?,
+                    period: c.period.as_ref().map(Into::into),
+                });
+            }
+        }
+        ext.push(proto::EPPCommandExtensionType::EPPFee07Check(
+            proto::fee::EPPFee07Check { domains },
+        ))
+    } else if client.fee_06_supported {
+        let mut domains = vec![];
+        for name in names {
+            for c in &fee_check.commands {
+                domains.push(proto::fee::EPPFee06CheckDomain {
+                    name: name.to_string(),
+                    currency: fee_check.currency.to_owned(),
+                    command: fee_command(c)?,
+                    period: c.period.as_ref().map(Into::into),
+                });
+            }
+        }
+        ext.push(proto::EPPCommandExtensionType::EPPFee06Check(
+            proto::fee::EPPFee06Check { domains },
+        ))
+    } else if client.fee_05_supported {
+        let mut domains = vec![];
+        for name in names {
+            for c in &fee_check.commands {
+                domains.push(proto::fee::EPPFee05CheckDomain {
+                    name: name.to_string(),
+                    currency: fee_check.currency.to_owned(),
+                    command: fee_command(c)?,
+                    period: c.period.as_ref().map(Into::into),
+                });
+            }
+        }
+        ext.push(proto::EPPCommandExtensionType::EPPFee05Check(
+            proto::fee::EPPFee05Check { domains },
+        ))
+    } else {
+        return Err(Error::Unsupported);
+    }
+
+    Ok(ext)
+}
+
+/// Extracts fee check data from a check response in whichever fee dialect the server used
+///
+/// # Arguments
+/// * `extension` - Extensions of the response
+/// * `name` - Domain to extract data for, or `None` if only one domain was checked
+fn fee_check_data(
+    extension: &Option<proto::EPPResponseExtension>,
+    name: Option<&str>,
+) -> Result<Option<fee::FeeCheckData>, Error> {
+    let ext = match extension {
+        Some(ext) => ext,
+        None => return Ok(None),
+    };
+    let for_name = |n: &str| match name {
+        Some(name) => n.eq_ignore_ascii_case(name),
+        None => true,
+    };
+    let some_commands = |commands: Vec<fee::FeeCommand>| match (name, commands.is_empty()) {
+        (Some(_), true) => None,
+        _ => Some(fee::FeeCheckData {
+            available: true,
+            commands,
+            reason: None,
+        }),
+    };
+
+    let fee10 = ext.value.iter().find_map(|p| match p {
+        proto::EPPResponseExtensionType::EPPFee10CheckData(i) => Some(i),
+        _ => None,
+    });
+    let fee011 = ext.value.iter().find_map(|p| match p {
+        proto::EPPResponseExtensionType::EPPFee011CheckData(i) => Some(i),
+        _ => None,
+    });
+    let fee09 = ext.value.iter().find_map(|p| match p {
+        proto::EPPResponseExtensionType::EPPFee09CheckData(i) => Some(i),
+        _ => None,
+    });
+    let fee08 = ext.value.iter().find_map(|p| match p {
+        proto::EPPResponseExtensionType::EPPFee08CheckData(i) => Some(i),
+        _ => None,
+    });
+    let fee07 = ext.value.iter().find_map(|p| match p {
+        proto::EPPResponseExtensionType::EPPFee07CheckData(i) => Some(i),
+        _ => None,
+    });
+    let fee06 = ext.value.iter().find_map(|p| match p {
+        proto::EPPResponseExtensionType::EPPFee06CheckData(i) => Some(i),
+        _ => None,
+    });
+    let fee05 = ext.value.iter().find_map(|p| match p {
+        proto::EPPResponseExtensionType::EPPFee05CheckData(i) => Some(i),
+        _ => None,
+    });
+
+    Ok(if let Some(f) = fee10 {
+        let d = match (name, f.objects.iter().find(|o| for_name(&o.object_id.id))) {
+            (_, Some(o)) => o,
+            (Some(_), None) => return Ok(None),
+            (None, None) => return Err(Error::ServerInternal),
+        };
+        Some(fee::FeeCheckData {
+            available: d.available,
+            commands: d
+                .commands
+                .iter()
+                .map(|c| fee::FeeCommand {
+                    command: (&c.name).into(),
+                    period: c.period.as_ref().map(Into::into),
+                    standard: Some(c.standard),
+                    currency: f.currency.to_owned(),
+                    fees: c.fee.iter().map(Into::into).collect(),
+                    credits: c.credit.iter().map(Into::into).collect(),
+                    reason: c.reason.to_owned(),
+                    class: d.class.to_owned(),
+                })
+                .collect(),
+            reason: d.reason.to_owned(),
+        })
+    } else if let Some(f) = fee011 {
+        let objects = f
+            .objects
+            .iter()
+            .filter(|o| for_name(o.object.trim()))
+            .collect::<Vec<_>>();
+        let d = match (name, objects.first()) {
+            (_, Some(o)) => o,
+            (Some(_), None) => return Ok(None),
+            (None, None) => return Err(Error::ServerInternal),
+        };
+        Some(fee::FeeCheckData {
+            available: d.available,
+            commands: objects
+                .iter()
+                .map(|c| fee::FeeCommand {
+                    command: (&c.command.name.command).into(),
+                    period: c.period.as_ref().map(Into::into),
+                    standard: Some(c.command.standard),
+                    currency: c.currency.to_owned(),
+                    fees: c.fee.iter().map(Into::into).collect(),
+                    credits: c.credit.iter().map(Into::into).collect(),
+                    reason: c.reason.to_owned(),
+                    class: c.class.to_owned(),
+                })
+                .collect(),
+            reason: d.reason.to_owned(),
+        })
+    } else if let Some(f) = fee09 {
+        some_commands(
+            f.objects
+                .iter()
+                .filter(|d| for_name(&d.object_id.id))
+                .map(|d| fee::FeeCommand {
+                    command: (&d.command.command).into(),
+                    period: d.period.as_ref().map(Into::into),
+                    standard: None,
+                    currency: d.currency.to_owned(),
+                    fees: d.fee.iter().map(Into::into).collect(),
+                    credits: d.credit.iter().map(Into::into).collect(),
+                    class: d.class.to_owned(),
+                    reason: None,
+                })
+                .collect(),
+        )
+    } else if let Some(f) = fee08 {
+        some_commands(
+            f.domains
+                .iter()
+                .filter(|d| for_name(&d.name))
+                .map(|d| fee::FeeCommand {
+                    command: (&d.command.command).into(),
+                    period: d.period.as_ref().map(Into::into),
+                    standard: None,
+                    currency: d.currency.to_owned(),
+                    fees: d.fee.iter().map(Into::into).collect(),
+                    credits: d.credit.iter().map(Into::into).collect(),
+                    class: d.class.to_owned(),
+                    reason: None,
+                })
+                .collect(),
+        )
+    } else if let Some(f) = fee07 {
+        some_commands(
+            f.domains
+                .iter()
+                .filter(|d| for_name(&d.name))
+                .map(|d| fee::FeeCommand {
+                    command: (&d.command.command).into(),
+                    period: d.period.as_ref().map(Into::into),
+                    standard: None,
+                    currency: d.currency.to_owned().unwrap_or_default(),
+                    fees: d.fee.iter().map(Into::into).collect(),
+                    credits: d.credit.iter().map(Into::into).collect(),
+                    class: d.class.to_owned(),
+                    reason: None,
+                })
+                .collect(),
+        )
+    } else if let Some(f) = fee06 {
+        some_commands(
+            f.domains
+                .iter()
+                .filter(|d| for_name(&d.name))
+                .map(|d| fee::FeeCommand {
+                    command: (&d.command.command).into(),
+                    period: Some((&d.period).into()),
+                    standard: None,
+                    currency: d.currency.to_owned(),
+                    fees: d.fee.iter().map(Into::into).collect(),
+                    class: d.class.to_owned(),
+                    credits: vec![],
+                    reason: None,
+                })
+                .collect(),
+        )
+    } else if let Some(f) = fee05 {
+        some_commands(
+            f.domains
+                .iter()
+                .filter(|d| for_name(&d.name))
+                .map(|d| fee::FeeCommand {
+                    command: (&d.command.command).into(),
+                    period: Some((&d.period).into()),
+                    standard: None,
+                    currency: d.currency.to_owned(),
+                    fees: d.fee.iter().map(Into::into).collect(),
+                    class: d.class.to_owned(),
+                    credits: vec![],
+                    reason: None,
+                })
+                .collect(),
+        )
+    } else {
+        None
+    })
+}
+
+/// Extracts Donuts charge data from a check response
+///
+/// # Arguments
+/// * `extension` - Extensions of the response
+/// * `name` - Domain to extract data for, or `None` if only one domain was checked
+fn donuts_fee_check_data(
+    extension: &Option<proto::EPPResponseExtension>,
+    name: Option<&str>,
+) -> Result<Option<fee::DonutsFeeData>, Error> {
+    let charge = match extension {
+        Some(ext) => ext.value.iter().find_map(|p| match p {
+            proto::EPPResponseExtensionType::EPPDonutsChargeCheckData(i) => Some(i),
+            _ => None,
+        }),
+        None => None,
+    };
+
+    match (charge, name) {
+        (Some(c), Some(name)) => Ok(c
+            .domains
+            .iter()
+            .find(|d| d.name.eq_ignore_ascii_case(name))
+            .map(Into::into)),
+        (Some(c), None) => match c.domains.get(0) {
+            Some(d) => Ok(Some(d.into())),
+            None => Err(Error::ServerInternal),
+        },
+        (None, _) => Ok(None),
+    }
+}
+
 pub fn handle_check(client: &ServerFeatures, req: &CheckRequest) -> HandleReqReturn<CheckResponse> {
     if !client.domain_supported {
         return Err(Err(Error::Unsupported));
@@ -763,176 +1173,7 @@ pub fn handle_check(client: &ServerFeatures, req: &CheckRequest) -> HandleReqRet
     super::verisign::handle_verisign_namestore_erratum(client, &mut ext);
 
     if let Some(fee_check) = &req.fee_check {
-        if client.fee_supported {
-            ext.push(proto::EPPCommandExtensionType::EPPFee10Check(
-                proto::fee::EPPFee10Check {
-                    currency: fee_check.currency.to_owned(),
-                    commands: fee_check
-                        .commands
-                        .iter()
-                        .map(|c| {
-                            Ok(proto::fee::EPPFee10CheckCommand {
-                                name: match (&c.command).into() {
-                                    Some(n) => n,
-                                    None => return Err(Err(Error::Unsupported)),
-                                },
-                                phase: c.phase.as_ref().map(Into::into),
-                                subphase: c.sub_phase.as_ref().map(Into::into),
-                                period: c.period.as_ref().map(Into::into),
-                            })
-                        })
-                        .collect::<Result<Vec<_>, _>>()?,
-                },
-            ))
-        } else if client.fee_011_supported {
-            fee_check
-                .commands
-                .iter()
-                .map(|c| {
-                    ext.push(proto::EPPCommandExtensionType::EPPFee011Check(
-                        proto::fee::EPPFee011Check {
-                            currency: fee_check.currency.to_owned(),
-                            command: proto::fee::EPPFeeCommand {
-                                command: match (&c.command).into() {
-                                    Some(n) => n,
-                                    None => return Err(Err(Error::Unsupported)),
-                                },
-                                phase: c.phase.as_ref().map(Into::into),
-                                subphase: c.sub_phase.as_ref().map(Into::into),
-                            },
-                            period: c.period.as_ref().map(Into::into),
-                        },
-                    ));
-                    Ok(())
-                })
-                .collect::<Result<Vec<_>, _>>()?;
-        } else if client.fee_09_supported {
-            ext.push(proto::EPPCommandExtensionType::EPPFee09Check(
-                proto::fee::EPPFee09Check {
-                    objects: fee_check
-                        .commands
-                        .iter()
-                        .map(|c| {
-                            Ok(proto::fee::EPPFee09CheckObject {
-                                object_uri: Some("urn:ietf:params:xml:ns:domain-1.0".to_string()),
-                                object_id: proto::fee::EPPFee10ObjectID {
-                                    element: "name".to_string(),
-                                    id: req.name.to_owned(),
-                                },
-                                currency: fee_check.currency.to_owned(),
-                                command: proto::fee::EPPFeeCommand {
-                                    command: match (&c.command).into() {
-                                        Some(n) => n,
-                                        None => return Err(Err(Error::Unsupported)),
-                                    },
-                                    phase: c.phase.as_ref().map(Into::into),
-                                    subphase: c.sub_phase.as_ref().map(Into::into),
-                                },
-                                period: c.period.as_ref().map(Into::into),
-                            })
-                        })
-                        .collect::<Result<Vec<_>, _>>()?,
-                },
-            ))
-        } else if client.fee_08_supported {
-            ext.push(proto::EPPCommandExtensionType::EPPFee08Check(
-                proto::fee::EPPFee08Check {
-                    domains: fee_check
-                        .commands
-                        .iter()
-                        .map(|c| {
-                            Ok(proto::fee::EPPFee08CheckDomain {
-                                name: req.name.to_owned(),
-                                currency: fee_check.currency.to_owned(),
-                                command: proto::fee::EPPFeeCommand {
-                                    command: match (&c.command).into() {
-                                        Some(n) => n,
-                                        None => return Err(Err(Error::Unsupported)),
-                                    },
-                                    phase: c.phase.as_ref().map(Into::into),
-                                    subphase: c.sub_phase.as_ref().map(Into::into),
-                                },
-                                period: c.period.as_ref().map(Into::into),
-                            })
-                        })
-                        .collect::<Result<Vec<_>, _>>()?,
-                },
-            ))
-        } else if client.fee_07_supported {
-            ext.push(proto::EPPCommandExtensionType::EPPFee07Check(
-                proto::fee::EPPFee07Check {
-                    domains: fee_check
-                        .commands
-                        .iter()
-                        .map(|c| {
-                            Ok(proto::fee::EPPFee07CheckDomain {
-                                name: req.name.to_owned(),
-                                currency: fee_check.currency.to_owned(),
-                                command: proto::fee::EPPFeeCommand {
-                                    command: match (&c.command).into() {
-                                        Some(n) => n,
-                                        None => return Err(Err(Error::Unsupported)),
-                                    },
-                                    phase: c.phase.as_ref().map(Into::into),
-                                    subphase: c.sub_phase.as_ref().map(Into::into),
-                                },
-                                period: c.period.as_ref().map(Into::into),
-                            })
-                        })
-                        .collect::<Result<Vec<_>, _>>()?,
-                },
-            ))
-        } else if client.fee_06_supported {
-            ext.push(proto::EPPCommandExtensionType::EPPFee06Check(
-                proto::fee::EPPFee06Check {
-                    domains: fee_check
-                        .commands
-                        .iter()
-                        .map(|c| {
-                            Ok(proto::fee::EPPFee06CheckDomain {
-                                name: req.name.to_owned(),
-                                currency: fee_check.currency.to_owned(),
-                                command: proto::fee::EPPFeeCommand {
-                                    command: match (&c.command).into() {
-                                        Some(n) => n,
-                                        None => return Err(Err(Error::Unsupported)),
-                                    },
-                                    phase: c.phase.as_ref().map(Into::into),
-                                    subphase: c.sub_phase.as_ref().map(Into::into),
-                                },
-                                period: c.period.as_ref().map(Into::into),
-                            })
-                        })
-                        .collect::<Result<Vec<_>, _>>()?,
-                },
-            ))
-        } else if client.fee_05_supported {
-            ext.push(proto::EPPCommandExtensionType::EPPFee05Check(
-                proto::fee::EPPFee05Check {
-                    domains: fee_check
-                        .commands
-                        .iter()
-                        .map(|c| {
-                            Ok(proto::fee::EPPFee05CheckDomain {
-                                name: req.name.to_owned(),
-                                currency: fee_check.currency.to_owned(),
-                                command: proto::fee::EPPFeeCommand {
-                                    command: match (&c.command).into() {
-                                        Some(n) => n,
-                                        None => return Err(Err(Error::Unsupported)),
-                                    },
-                                    phase: c.phase.as_ref().map(Into::into),
-                                    subphase: c.sub_phase.as_ref().map(Into::into),
-                                },
-                                period: c.period.as_ref().map(Into::into),
-                            })
-                        })
-                        .collect::<Result<Vec<_>, _>>()?,
-                },
-            ))
-        } else {
-            return Err(Err(Error::Unsupported));
-        }
+        ext.append(&mut fee_check_extension(client, &[req.name.as_str()], fee_check).map_err(Err)?);
     }
 
     if let Some(launch_check) = &req.launch_check {
@@ -957,6 +1198,8 @@ pub fn handle_check(client: &ServerFeatures, req: &CheckRequest) -> HandleReqRet
         }
     }
 
+    handle_allocation_token(client, &req.allocation_token, &mut ext)?;
+
     Ok((
         proto::EPPCommandType::Check(command),
         match ext.is_empty() {
@@ -969,202 +1212,8 @@ pub fn handle_check(client: &ServerFeatures, req: &CheckRequest) -> HandleReqRet
 pub fn handle_check_response<M: crate::metrics::Metrics>(
     response: proto::EPPResponse, _metrics: &M
 ) -> Response<CheckResponse> {
-    let fee_check = match &response.extension {
-        Some(ext) => {
-            let fee10 = ext.value.iter().find_map(|p| match p {
-                proto::EPPResponseExtensionType::EPPFee10CheckData(i) => Some(i),
-                _ => None,
-            });
-            let fee011 = ext.value.iter().find_map(|p| match p {
-                proto::EPPResponseExtensionType::EPPFee011CheckData(i) => Some(i),
-                _ => None,
-            });
-            let fee09 = ext.value.iter().find_map(|p| match p {
-                proto::EPPResponseExtensionType::EPPFee09CheckData(i) => Some(i),
-                _ => None,
-            });
-            let fee08 = ext.value.iter().find_map(|p| match p {
-                proto::EPPResponseExtensionType::EPPFee08CheckData(i) => Some(i),
-                _ => None,
-            });
-            let fee07 = ext.value.iter().find_map(|p| match p {
-                proto::EPPResponseExtensionType::EPPFee07CheckData(i) => Some(i),
-                _ => None,
-            });
-            let fee06 = ext.value.iter().find_map(|p| match p {
-                proto::EPPResponseExtensionType::EPPFee06CheckData(i) => Some(i),
-                _ => None,
-            });
-            let fee05 = ext.value.iter().find_map(|p| match p {
-                proto::EPPResponseExtensionType::EPPFee05CheckData(i) => Some(i),
-                _ => None,
-            });
-
-            if let Some(f) = fee10 {
-                let d = match f.objects.get(0) {
-                    Some(o) => o,
-                    None => return Err(Error::ServerInternal),
-                };
-                Some(fee::FeeCheckData {
-                    available: d.available,
-                    commands: d
-                        .commands
-                        .iter()
-                        .map(|c| fee::FeeCommand {
-                            command: (&c.name).into(),
-                            period: c.period.as_ref().map(Into::into),
-                            standard: Some(c.standard),
-                            currency: f.currency.to_owned(),
-                            fees: c.fee.iter().map(Into::into).collect(),
-                            credits: c.credit.iter().map(Into::into).collect(),
-                            reason: c.reason.to_owned(),
-                            class: d.class.to_owned(),
-                        })
-                        .collect(),
-                    reason: d.reason.to_owned(),
-                })
-            } else if let Some(f) = fee011 {
-                let d = match f.objects.get(0) {
-                    Some(o) => o,
-                    None => return Err(Error::ServerInternal),
-                };
-                Some(fee::FeeCheckData {
-                    available: d.available,
-                    commands: f
-                        .objects
-                        .iter()
-                        .map(|c| fee::FeeCommand {
-                            command: (&c.command.name.command).into(),
-                            period: c.period.as_ref().map(Into::into),
-                            standard: Some(c.command.standard),
-                            currency: c.currency.to_owned(),
-                            fees: c.fee.iter().map(Into::into).collect(),
-                            credits: c.credit.iter().map(Into::into).collect(),
-                            reason: c.reason.to_owned(),
-                            class: c.class.to_owned(),
-                        })
-                        .collect(),
-                    reason: d.reason.to_owned(),
-                })
-            } else if let Some(f) = fee09 {
-                Some(fee::FeeCheckData {
-                    available: true,
-                    commands: f
-                        .objects
-                        .iter()
-                        .map(|d| fee::FeeCommand {
-                            command: (&d.command.command).into(),
-                            period: d.period.as_ref().map(Into::into),
-                            standard: None,
-                            currency: d.currency.to_owned(),
-                            fees: d.fee.iter().map(Into::into).collect(),
-                            credits: d.credit.iter().map(Into::into).collect(),
-                            class: d.class.to_owned(),
-                            reason: None,
-                        })
-                        .collect(),
-                    reason: None,
-                })
-            } else if let Some(f) = fee08 {
-                Some(fee::FeeCheckData {
-                    available: true,
-                    commands: f
-                        .domains
-                        .iter()
-                        .map(|d| fee::FeeCommand {
-                            command: (&d.command.command).into(),
-                            period: d.period.as_ref().map(Into::into),
-                            standard: None,
-                            currency: d.currency.to_owned(),
-                            fees: d.fee.iter().map(Into::into).collect(),
-                            credits: d.credit.iter().map(Into::into).collect(),
-                            class: d.class.to_owned(),
-                            reason: None,
-                        })
-                        .collect(),
-                    reason: None,
-                })
-            } else if let Some(f) = fee07 {
-                Some(fee::FeeCheckData {
-                    available: true,
-                    commands: f
-                        .domains
-                        .iter()
-                        .map(|d| fee::FeeCommand {
-                            command: (&d.command.command).into(),
-                            period: d.period.as_ref().map(Into::into),
-                            standard: None,
-                            currency: d.currency.to_owned().unwrap_or_default(),
-                            fees: d.fee.iter().map(Into::into).collect(),
-                            credits: d.credit.iter().map(Into::into).collect(),
-                            class: d.class.to_owned(),
-                            reason: None,
-                        })
-                        .collect(),
-                    reason: None,
-                })
-            } else if let Some(f) = fee06 {
-                Some(fee::FeeCheckData {
-                    available: true,
-                    commands: f
-                        .domains
-                        .iter()
-                        .map(|d| fee::FeeCommand {
-                            command: (&d.command.command).into(),
-                            period: Some((&d.period).into()),
-                            standard: None,
-                            currency: d.currency.to_owned(),
-                            fees: d.fee.iter().map(Into::into).collect(),
-                            class: d.class.to_owned(),
-                            credits: vec![],
-                            reason: None,
-                        })
-                        .collect(),
-                    reason: None,
-                })
-            } else {
-                fee05.map(|f| fee::FeeCheckData {
-                    available: true,
-                    commands: f
-                        .domains
-                        .iter()
-                        .map(|d| fee::FeeCommand {
-                            command: (&d.command.command).into(),
-                            period: Some((&d.period).into()),
-                            standard: None,
-                            currency: d.currency.to_owned(),
-                            fees: d.fee.iter().map(Into::into).collect(),
-                            class: d.class.to_owned(),
-                            credits: vec![],
-                            reason: None,
-                        })
-                        .collect(),
-                    reason: None,
-                })
-            }
-        }
-        None => None,
-    };
-
-    let donuts_fee_check = match &response.extension {
-        Some(ext) => {
-            let charge = ext.value.iter().find_map(|p| match p {
-                proto::EPPResponseExtensionType::EPPDonutsChargeCheckData(i) => Some(i),
-                _ => None,
-            });
-
-            if let Some(c) = charge {
-                let d = match c.domains.get(0) {
-                    Some(o) => o,
-                    None => return Err(Error::ServerInternal),
-                };
-                Some(d.into())
-            } else {
-                None
-            }
-        }
-        None => None,
-    };
+    let fee_check = fee_check_data(&response.extension, None)?;
+    let donuts_fee_check = donuts_fee_check_data(&response.extension, None)?;
 
     match response.data {
         Some(value) => match value.value {
@@ -1193,6 +1242,74 @@ pub fn handle_check_response<M: crate::metrics::Metrics>(
     }
 }
 
+pub fn handle_batch_check(
+    client: &ServerFeatures,
+    req: &BatchCheckRequest,
+) -> HandleReqReturn<BatchCheckResponse> {
+    if !client.domain_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    if req.names.is_empty() {
+        return Err(Err(Error::Err("at least one domain must be checked".to_string())));
+    }
+    for name in &req.names {
+        check_domain(name)?;
+    }
+    let command = proto::EPPCheck::DomainBatch(proto::domain::EPPDomainBatchCheck {
+        names: req.names.clone(),
+    });
+    let mut ext = vec![];
+
+    super::verisign::handle_verisign_namestore_erratum(client, &mut ext);
+
+    if let Some(fee_check) = &req.fee_check {
+        let names = req.names.iter().map(String::as_str).collect::<Vec<_>>();
+        ext.append(&mut fee_check_extension(client, &names, fee_check).map_err(Err)?);
+    }
+
+    Ok((
+        proto::EPPCommandType::Check(command),
+        match ext.is_empty() {
+            true => None,
+            false => Some(ext),
+        },
+    ))
+}
+
+pub fn handle_batch_check_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<BatchCheckResponse> {
+    match response.data {
+        Some(value) => match value.value {
+            proto::EPPResultDataValue::EPPDomainCheckResult(domain_check) => {
+                Response::Ok(BatchCheckResponse {
+                    results: domain_check
+                        .data
+                        .into_iter()
+                        .map(|d| {
+                            Ok(BatchCheckResult {
+                                fee_check: fee_check_data(
+                                    &response.extension,
+                                    Some(&d.name.name),
+                                )?,
+                                donuts_fee_check: donuts_fee_check_data(
+                                    &response.extension,
+                                    Some(&d.name.name),
+                                )?,
+                                avail: d.name.available,
+                                reason: d.reason,
+                                name: d.name.name,
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                })
+            }
+            _ => Err(Error::ServerInternal),
+        },
+        None => Err(Error::ServerInternal),
+    }
+}
+
 pub fn handle_claims_check(
     client: &ServerFeatures,
     req: &ClaimsCheckRequest,
@@ -1326,6 +1443,16 @@ pub fn handle_info(client: &ServerFeatures, req: &InfoRequest) -> HandleReqRetur
         }
     }
 
+    if req.allocation_token {
+        if client.allocation_token_supported {
+            exts.push(proto::EPPCommandExtensionType::AllocationTokenInfo(
+                proto::allocation_token::EPPAllocationTokenInfo {},
+            ))
+        } else {
+            return Err(Err(Error::Unsupported));
+        }
+    }
+
     super::verisign::handle_verisign_namestore_erratum(client, &mut exts);
 
     Ok((
@@ -1878,6 +2005,8 @@ pub fn handle_create(
         }
     }
 
+    handle_allocation_token(client, &req.allocation_token, &mut exts)?;
+
     super::verisign::handle_verisign_namestore_erratum(client, &mut exts);
     super::fee::handle_donuts_fee_agreement(client, &req.donuts_fee_agreement, &mut exts)?;
 
@@ -2798,6 +2927,20 @@ pub fn handle_verisign_sync(
     let mut exts = vec![];
     super::verisign::handle_verisign_namestore_erratum(client, &mut exts);
 
+    if let Some(fee_agreement) = &req.fee_agreement {
+        if client.fee_supported {
+            exts.push(proto::EPPCommandExtensionType::EPPFee10Update(
+                fee_agreement.into(),
+            ));
+        } else if client.fee_011_supported {
+            exts.push(proto::EPPCommandExtensionType::EPPFee011Update(
+                fee_agreement.into(),
+            ));
+        } else {
+            return Err(Err(Error::Unsupported));
+        }
+    }
+
     exts.push(proto::EPPCommandExtensionType::VerisignSyncUpdate(
         proto::verisign::EPPSyncUpdate {
             month_day: proto::verisign::EPPSyncUpdateMonthDay {
@@ -2834,6 +2977,10 @@ pub fn handle_update_response<M: crate::metrics::Metrics>(
                 proto::EPPResponseExtensionType::EPPFee10UpdateData(i) => Some(i),
                 _ => None,
             });
+            let fee011 = ext.value.iter().find_map(|p| match p {
+                proto::EPPResponseExtensionType::EPPFee011UpdateData(i) => Some(i),
+                _ => None,
+            });
             let fee09 = ext.value.iter().find_map(|p| match p {
                 proto::EPPResponseExtensionType::EPPFee09UpdateData(i) => Some(i),
                 _ => None,
@@ -2857,6 +3004,8 @@ pub fn handle_update_response<M: crate::metrics::Metrics>(
 
             if let Some(f) = fee10 {
                 Some(f.into())
+            } else if let Some(f) = fee011 {
+                Some(f.into())
             } else if let Some(f) = fee09 {
                 Some(f.into())
             } else if let Some(f) = fee08 {
@@ -2947,6 +3096,8 @@ pub fn handle_renew(client: &ServerFeatures, req: &RenewRequest) -> HandleReqRet
         }
     }
 
+    handle_allocation_token(client, &req.allocation_token, &mut ext)?;
+
     super::verisign::handle_verisign_namestore_erratum(client, &mut ext);
     super::fee::handle_donuts_fee_agreement(client, &req.donuts_fee_agreement, &mut ext)?;
 
@@ -3088,6 +3239,8 @@ pub fn handle_transfer_request(
         }
     }
 
+    handle_allocation_token(client, &req.allocation_token, &mut ext)?;
+
     super::verisign::handle_verisign_namestore_erratum(client, &mut ext);
     super::fee::handle_donuts_fee_agreement(client, &req.donuts_fee_agreement, &mut ext)?;
 
@@ -3502,4 +3655,478 @@ mod domain_tests {
         assert_eq!(command.currency, "USD");
         assert_eq!(command.fees.len(), 1);
     }
+
+    #[test]
+    fn batch_check_command() {
+        let features = super::ServerFeatures {
+            domain_supported: true,
+            fee_08_supported: true,
+            ..Default::default()
+        };
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_batch_check(
+            &features,
+            &super::BatchCheckRequest {
+                names: vec!["example.com".to_string(), "example.net".to_string()],
+                fee_check: Some(super::fee::FeeCheck {
+                    currency: Some("USD".to_string()),
+                    commands: vec![super::fee::FeeCheckCommand {
+                        command: super::fee::Command::Create,
+                        period: None,
+                        custom_name: None,
+                        phase: None,
+                        sub_phase: None,
+                    }],
+                }),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let msg = super::proto::EPPMessage {
+            message: super::proto::EPPMessageType::Command(Box::new(super::proto::EPPCommand {
+                command,
+                extension: extension.map(|value| super::proto::EPPCommandExtension { value }),
+                client_transaction_id: None,
+            })),
+        };
+        let res = xml_serde::to_string(&msg).unwrap();
+        assert!(res.contains("<domain:name>example.com</domain:name>"));
+        assert!(res.contains("<domain:name>example.net</domain:name>"));
+        assert!(res.contains("<fee:name>example.com</fee:name>"));
+        assert!(res.contains("<fee:name>example.net</fee:name>"));
+    }
+
+    #[test]
+    fn batch_check_fee_10() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg lang="en">Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:chkData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:cd>
+          <domain:name avail="1">example.com</domain:name>
+        </domain:cd>
+        <domain:cd>
+          <domain:name avail="0">example.net</domain:name>
+          <domain:reason>In use</domain:reason>
+        </domain:cd>
+      </domain:chkData>
+    </resData>
+    <extension>
+      <fee:chkData xmlns:fee="urn:ietf:params:xml:ns:epp:fee-1.0">
+        <fee:currency>USD</fee:currency>
+        <fee:cd avail="1">
+          <fee:objID>example.com</fee:objID>
+          <fee:class>premium</fee:class>
+          <fee:command name="create">
+            <fee:period unit="y">1</fee:period>
+            <fee:fee description="Registration Fee" refundable="1" grace-period="P5D">100.00</fee:fee>
+          </fee:command>
+        </fee:cd>
+        <fee:cd avail="1">
+          <fee:objID>example.net</fee:objID>
+          <fee:command name="create" standard="1">
+            <fee:period unit="y">1</fee:period>
+            <fee:fee description="Registration Fee" refundable="1" grace-period="P5D">10.00</fee:fee>
+          </fee:command>
+        </fee:cd>
+      </fee:chkData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_batch_check_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap();
+        assert_eq!(data.results.len(), 2);
+        let com = &data.results[0];
+        assert_eq!(com.name, "example.com");
+        assert!(com.avail);
+        let com_fee = com.fee_check.as_ref().unwrap();
+        assert_eq!(com_fee.commands[0].class.as_deref(), Some("premium"));
+        assert_eq!(com_fee.commands[0].fees[0].value, "100.00");
+        let net = &data.results[1];
+        assert_eq!(net.name, "example.net");
+        assert!(!net.avail);
+        assert_eq!(net.reason.as_deref(), Some("In use"));
+        let net_fee = net.fee_check.as_ref().unwrap();
+        assert_eq!(net_fee.commands[0].standard, Some(true));
+        assert_eq!(net_fee.commands[0].fees[0].value, "10.00");
+    }
+
+    fn command_xml(
+        command: super::proto::EPPCommandType,
+        extension: Option<Vec<super::proto::EPPCommandExtensionType>>,
+    ) -> String {
+        let msg = super::proto::EPPMessage {
+            message: super::proto::EPPMessageType::Command(Box::new(super::proto::EPPCommand {
+                command,
+                extension: extension.map(|value| super::proto::EPPCommandExtension { value }),
+                client_transaction_id: None,
+            })),
+        };
+        xml_serde::to_string(&msg).unwrap()
+    }
+
+    fn assert_allocation_token(res: &str) {
+        assert!(
+            res.contains("xmlns:allocationToken=\"urn:ietf:params:xml:ns:allocationToken-1.0\"")
+        );
+        assert!(res.contains(">abc123</allocationToken:allocationToken>"));
+    }
+
+    #[test]
+    fn allocation_token_commands() {
+        let features = super::ServerFeatures {
+            domain_supported: true,
+            allocation_token_supported: true,
+            ..Default::default()
+        };
+
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_check(
+            &features,
+            &super::CheckRequest {
+                name: "example.com".to_string(),
+                fee_check: None,
+                launch_check: None,
+                keysys: None,
+                allocation_token: Some("abc123".to_string()),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        assert_allocation_token(&command_xml(command, extension));
+
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_create(
+            &features,
+            &super::CreateRequest {
+                name: "example.com".to_string(),
+                period: None,
+                registrant: "contact1".to_string(),
+                contacts: vec![],
+                nameservers: vec![],
+                auth_info: "2fooBAR".to_string(),
+                sec_dns: None,
+                launch_create: None,
+                fee_agreement: None,
+                donuts_fee_agreement: None,
+                eurid_data: None,
+                isnic_payment: None,
+                personal_registration: None,
+                keysys: None,
+                nominet_ext: None,
+                allocation_token: Some("abc123".to_string()),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        assert_allocation_token(&command_xml(command, extension));
+
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_transfer_request(
+            &features,
+            &super::TransferRequestRequest {
+                name: "example.com".to_string(),
+                auth_info: "2fooBAR".to_string(),
+                add_period: None,
+                fee_agreement: None,
+                donuts_fee_agreement: None,
+                eurid_data: None,
+                keysys: None,
+                allocation_token: Some("abc123".to_string()),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        assert_allocation_token(&command_xml(command, extension));
+
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_renew(
+            &features,
+            &super::RenewRequest {
+                name: "example.com".to_string(),
+                add_period: None,
+                cur_expiry_date: chrono::Utc::now(),
+                fee_agreement: None,
+                donuts_fee_agreement: None,
+                isnic_payment: None,
+                keysys: None,
+                allocation_token: Some("abc123".to_string()),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        assert_allocation_token(&command_xml(command, extension));
+    }
+
+    #[test]
+    fn allocation_token_unsupported() {
+        let features = super::ServerFeatures {
+            domain_supported: true,
+            ..Default::default()
+        };
+        let (sender, _) = futures::channel::oneshot::channel();
+        let res = super::handle_check(
+            &features,
+            &super::CheckRequest {
+                name: "example.com".to_string(),
+                fee_check: None,
+                launch_check: None,
+                keysys: None,
+                allocation_token: Some("abc123".to_string()),
+                return_path: sender,
+            },
+        );
+        assert!(matches!(res, Err(Err(super::Error::Unsupported))));
+    }
+
+    #[test]
+    fn allocation_token_info_command() {
+        let features = super::ServerFeatures {
+            domain_supported: true,
+            allocation_token_supported: true,
+            ..Default::default()
+        };
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_info(
+            &features,
+            &super::InfoRequest {
+                name: "example.com".to_string(),
+                auth_info: None,
+                launch_info: None,
+                hosts: None,
+                eurid_data: None,
+                allocation_token: true,
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let res = command_xml(command, extension);
+        assert!(
+            res.contains("xmlns:allocationToken=\"urn:ietf:params:xml:ns:allocationToken-1.0\"")
+        );
+        assert!(res.contains("<allocationToken:info"));
+        assert!(!res.contains("<allocationToken:allocationToken"));
+    }
+
+    #[test]
+    fn allocation_token_info_response() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:infData
+       xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:name>example1.tld</domain:name>
+        <domain:roid>EXAMPLE1-REP</domain:roid>
+        <domain:status s="ok"/>
+        <domain:registrant>jd1234</domain:registrant>
+        <domain:contact type="admin">sh8013</domain:contact>
+        <domain:contact type="tech">sh8013</domain:contact>
+        <domain:clID>ClientX</domain:clID>
+        <domain:crID>ClientY</domain:crID>
+        <domain:crDate>2012-04-03T22:00:00.0Z</domain:crDate>
+        <domain:exDate>2015-04-03T22:00:00.0Z</domain:exDate>
+        <domain:authInfo>
+          <domain:pw>2fooBAR</domain:pw>
+        </domain:authInfo>
+      </domain:infData>
+    </resData>
+    <extension>
+      <allocationToken:allocationToken xmlns:allocationToken="urn:ietf:params:xml:ns:allocationToken-1.0">abc123</allocationToken:allocationToken>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54321-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_info_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap();
+        assert_eq!(data.name, "example1.tld");
+        assert_eq!(data.allocation_token.as_deref(), Some("abc123"));
+    }
+
+    fn sync_fee_check(custom_name: Option<&str>) -> super::CheckRequest {
+        let (sender, _) = futures::channel::oneshot::channel();
+        super::CheckRequest {
+            name: "example.com".to_string(),
+            fee_check: Some(super::fee::FeeCheck {
+                currency: Some("USD".to_string()),
+                commands: vec![super::fee::FeeCheckCommand {
+                    command: super::fee::Command::Custom,
+                    period: None,
+                    custom_name: custom_name.map(Into::into),
+                    phase: None,
+                    sub_phase: None,
+                }],
+            }),
+            launch_check: None,
+            keysys: None,
+            allocation_token: None,
+            return_path: sender,
+        }
+    }
+
+    #[test]
+    fn verisign_sync_fee_check_command() {
+        let features = super::ServerFeatures {
+            domain_supported: true,
+            fee_supported: true,
+            ..Default::default()
+        };
+        let (command, extension) =
+            super::handle_check(&features, &sync_fee_check(Some("sync"))).unwrap();
+        let res = command_xml(command, extension);
+        assert!(res.contains("xmlns:fee=\"urn:ietf:params:xml:ns:epp:fee-1.0\""));
+        assert!(res.contains("name=\"custom\""));
+        assert!(res.contains("customName=\"sync\""));
+
+        let res = super::handle_check(&features, &sync_fee_check(None));
+        assert!(matches!(res, Err(Err(super::Error::Err(_)))));
+
+        let features = super::ServerFeatures {
+            domain_supported: true,
+            fee_011_supported: true,
+            ..Default::default()
+        };
+        let res = super::handle_check(&features, &sync_fee_check(Some("sync")));
+        assert!(matches!(res, Err(Err(super::Error::Unsupported))));
+    }
+
+    #[test]
+    fn verisign_sync_fee_check_response() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg lang="en">Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:chkData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:cd>
+          <domain:name avail="0">example.com</domain:name>
+          <domain:reason>In use</domain:reason>
+        </domain:cd>
+      </domain:chkData>
+    </resData>
+    <extension>
+      <fee:chkData xmlns:fee="urn:ietf:params:xml:ns:epp:fee-1.0">
+        <fee:currency>USD</fee:currency>
+        <fee:cd avail="1">
+          <fee:objID>example.com</fee:objID>
+          <fee:command name="custom" customName="sync">
+            <fee:fee description="Sync Fee">4.17</fee:fee>
+          </fee:command>
+        </fee:cd>
+      </fee:chkData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_check_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap();
+        let fee_check = data.fee_check.unwrap();
+        let command = fee_check.commands.get(0).unwrap();
+        assert_eq!(command.command, super::fee::Command::Custom);
+        assert_eq!(command.currency, "USD");
+        assert_eq!(command.fees[0].value, "4.17");
+    }
+
+    #[test]
+    fn verisign_sync_fee_011() {
+        let features = super::ServerFeatures {
+            domain_supported: true,
+            verisign_sync_supported: true,
+            fee_011_supported: true,
+            ..Default::default()
+        };
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_verisign_sync(
+            &features,
+            &super::VerisignSyncRequest {
+                name: "example.com".to_string(),
+                month: 6,
+                day: 15,
+                fee_agreement: Some(super::fee::FeeAgreement {
+                    currency: Some("USD".to_string()),
+                    fees: vec![super::fee::Fee {
+                        value: "4.17".to_string(),
+                        description: None,
+                        refundable: None,
+                        grace_period: None,
+                        applied: super::fee::Applied::Unspecified,
+                    }],
+                }),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let res = command_xml(command, extension);
+        assert!(res.contains("xmlns:fee=\"urn:ietf:params:xml:ns:fee-0.11\""));
+        assert!(res.contains("<fee:currency>USD</fee:currency>"));
+        assert!(res.contains(">4.17</fee:fee>"));
+        assert!(res.contains("<sync:expMonthDay>--06-15</sync:expMonthDay>"));
+
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg lang="en">Command completed successfully</msg>
+    </result>
+    <extension>
+      <fee:updData xmlns:fee="urn:ietf:params:xml:ns:fee-0.11">
+        <fee:currency>USD</fee:currency>
+        <fee:fee>4.17</fee:fee>
+      </fee:updData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_update_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap();
+        let fee_data = data.fee_data.unwrap();
+        assert_eq!(fee_data.currency, "USD");
+        assert_eq!(fee_data.fees[0].value, "4.17");
+    }
 }
@@ -423,6 +423,7 @@ pub fn handle_check(client: &ServerFeatures, req: &CheckRequest) -> HandleReqRet
                                     Some(n) => n,
                                     None => return Err(Err(Error::Unsupported)),
                                 },
+                                custom_name: None,
                                 phase: None,
                                 subphase: None,
                                 period: c.period.as_ref().map(Into::into),
@@ -0,0 +1,200 @@
+//! Parsing of `<extValue>` elements in response results, including RFC 9038 unhandled namespaces
+//!
+//! The content of `<value>` is arbitrary XML, so these are read from the raw message rather than
+//! through the serde structs.
+
+use super::super::router::{CommandExtraValue, UnhandledNamespace};
+use quick_xml::events::Event;
+use quick_xml::name::{Namespace, ResolveResult};
+
+const EPP_NS: &[u8] = b"urn:ietf:params:xml:ns:epp-1.0";
+const UNHANDLED_NS_REASON: &str = " not in login services";
+
+/// Extra values and unhandled namespace data from the results of a response
+#[derive(Debug, Default)]
+pub struct ExtValues {
+    pub extra_values: Vec<CommandExtraValue>,
+    pub unhandled_namespaces: Vec<UnhandledNamespace>,
+}
+
+/// Reads all `<extValue>` elements from a raw response
+///
+/// Values whose reason is of the RFC 9038 form `<namespace URI> not in login services` are
+/// returned as unhandled namespaces, everything else as extra values.
+///
+/// # Arguments
+/// * `raw` - The response as received from the server
+pub fn parse(raw: &str) -> Result<ExtValues, quick_xml::Error> {
+    let mut reader = quick_xml::reader::NsReader::from_str(raw);
+    let mut out = ExtValues::default();
+
+    let mut in_ext_value = false;
+    let mut in_reason = false;
+    // Depth of nesting inside the current `<value>`
+    let mut value_depth = 0usize;
+    let mut value_start = 0;
+    let mut value_ns: Option<String> = None;
+    let mut value = String::new();
+    let mut reason = String::new();
+
+    loop {
+        let before = reader.buffer_position();
+        let (ns, event) = reader.read_resolved_event()?;
+        let ns = match ns {
+            ResolveResult::Bound(Namespace(n)) => Some(String::from_utf8_lossy(n).into_owned()),
+            _ => None,
+        };
+        let is_epp = ns.as_deref().map(str::as_bytes) == Some(EPP_NS);
+
+        if value_depth > 0 {
+            match event {
+                Event::Start(_) => {
+                    if value_depth == 1 && value_ns.is_none() {
+                        value_ns = ns;
+                    }
+                    value_depth += 1;
+                }
+                Event::Empty(_) => {
+                    if value_depth == 1 && value_ns.is_none() {
+                        value_ns = ns;
+                    }
+                }
+                Event::End(_) => {
+                    value_depth -= 1;
+                    if value_depth == 0 {
+                        value = raw[value_start..before].trim().to_string();
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            continue;
+        }
+
+        match event {
+            Event::Start(e) if is_epp => match e.local_name().as_ref() {
+                b"extValue" => {
+                    in_ext_value = true;
+                    value_ns = None;
+                    value.clear();
+                    reason.clear();
+                }
+                b"value" if in_ext_value => {
+                    value_depth = 1;
+                    value_start = reader.buffer_position();
+                }
+                b"reason" if in_ext_value => in_reason = true,
+                _ => {}
+            },
+            Event::Text(t) if in_reason => reason.push_str(&t.unescape()?),
+            Event::End(e) if is_epp => match e.local_name().as_ref() {
+                b"reason" => in_reason = false,
+                b"extValue" if in_ext_value => {
+                    in_ext_value = false;
+                    let reason = reason.trim();
+                    match reason.strip_suffix(UNHANDLED_NS_REASON) {
+                        Some(namespace) => out.unhandled_namespaces.push(UnhandledNamespace {
+                            namespace: match namespace.trim() {
+                                "" => value_ns.take().unwrap_or_default(),
+                                n => n.to_string(),
+                            },
+                            xml: std::mem::take(&mut value),
+                        }),
+                        None => out.extra_values.push(CommandExtraValue {
+                            value: std::mem::take(&mut value),
+                            reason: reason.to_string(),
+                        }),
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod ext_value_tests {
+    #[test]
+    fn unhandled_namespace() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+      <extValue>
+        <value>
+          <secDNS:infData xmlns:secDNS="urn:ietf:params:xml:ns:secDNS-1.1">
+            <secDNS:dsData>
+              <secDNS:keyTag>12345</secDNS:keyTag>
+              <secDNS:alg>3</secDNS:alg>
+              <secDNS:digestType>1</secDNS:digestType>
+              <secDNS:digest>49FD46E6C4B45C55D4AC</secDNS:digest>
+            </secDNS:dsData>
+          </secDNS:infData>
+        </value>
+        <reason>
+          urn:ietf:params:xml:ns:secDNS-1.1 not in login services
+        </reason>
+      </extValue>
+    </result>
+    <resData>
+      <domain:infData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:name>example.com</domain:name>
+        <domain:roid>EXAMPLE1-REP</domain:roid>
+      </domain:infData>
+    </resData>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res = super::parse(XML_DATA.trim()).unwrap();
+        assert!(res.extra_values.is_empty());
+        assert_eq!(res.unhandled_namespaces.len(), 1);
+        let ns = &res.unhandled_namespaces[0];
+        assert_eq!(ns.namespace, "urn:ietf:params:xml:ns:secDNS-1.1");
+        assert!(ns.xml.starts_with("<secDNS:infData"));
+        assert!(ns.xml.ends_with("</secDNS:infData>"));
+        assert!(ns.xml.contains("<secDNS:keyTag>12345</secDNS:keyTag>"));
+    }
+
+    #[test]
+    fn extra_value() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="2004">
+      <msg>Parameter value range error</msg>
+      <extValue>
+        <value xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+          <domain:period unit="y">11</domain:period>
+        </value>
+        <reason>Maximum registration period is 10 years</reason>
+      </extValue>
+    </result>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res = super::parse(XML_DATA.trim()).unwrap();
+        assert!(res.unhandled_namespaces.is_empty());
+        assert_eq!(res.extra_values.len(), 1);
+        assert_eq!(
+            res.extra_values[0].value,
+            r#"<domain:period unit="y">11</domain:period>"#
+        );
+        assert_eq!(
+            res.extra_values[0].reason,
+            "Maximum registration period is 10 years"
+        );
+    }
+}
@@ -13,6 +13,7 @@ impl From<&proto::fee::EPPFeeCommandType> for Command {
             proto::fee::EPPFeeCommandType::Transfer => Command::Transfer,
             proto::fee::EPPFeeCommandType::Delete => Command::Delete,
             proto::fee::EPPFeeCommandType::Restore => Command::Restore,
+            proto::fee::EPPFeeCommandType::Custom => Command::Custom,
         }
     }
 }
@@ -495,3 +496,490 @@ pub fn handle_donuts_fee_agreement<T>(
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod fee_tests {
+    use crate::client::domain::{CheckResponse, CreateResponse};
+    use crate::client::fee::{Command, NormalisedFees};
+
+    fn check_response(xml: &str) -> CheckResponse {
+        let res: super::proto::EPPMessage = xml_serde::from_str(xml.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        super::super::domain::handle_check_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap()
+    }
+
+    fn create_response(xml: &str) -> CreateResponse {
+        let res: super::proto::EPPMessage = xml_serde::from_str(xml.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        super::super::domain::handle_create_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap()
+    }
+
+    fn check_fees(res: &CheckResponse) -> NormalisedFees {
+        NormalisedFees::from_check(res.fee_check.as_ref(), res.donuts_fee_check.as_ref()).unwrap()
+    }
+
+    #[test]
+    fn check_05() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:chkData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:cd>
+          <domain:name avail="1">example.com</domain:name>
+        </domain:cd>
+      </domain:chkData>
+    </resData>
+    <extension>
+      <fee:chkData xmlns:fee="urn:ietf:params:xml:ns:fee-0.5">
+        <fee:cd>
+          <fee:name>example.com</fee:name>
+          <fee:currency>USD</fee:currency>
+          <fee:command>create</fee:command>
+          <fee:period unit="y">1</fee:period>
+          <fee:fee description="Registration Fee" grace-period="P5D">10.00</fee:fee>
+          <fee:fee description="ICANN Fee" refundable="0">0.18</fee:fee>
+        </fee:cd>
+        <fee:cd>
+          <fee:name>example.com</fee:name>
+          <fee:currency>USD</fee:currency>
+          <fee:command>renew</fee:command>
+          <fee:period unit="y">2</fee:period>
+          <fee:fee>20.00</fee:fee>
+        </fee:cd>
+      </fee:chkData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let fees = check_fees(&check_response(XML_DATA));
+        assert_eq!(fees.currency.as_deref(), Some("USD"));
+        assert!(!fees.premium);
+        assert_eq!(fees.commands.len(), 2);
+        let create = &fees.commands[0];
+        assert_eq!(create.command, Command::Create);
+        assert_eq!(create.period.as_ref().unwrap().value, 1);
+        assert_eq!(create.total.as_deref(), Some("10.18"));
+        assert_eq!(create.components.len(), 2);
+        assert_eq!(create.components[0].refundable, Some(true));
+        assert_eq!(create.components[0].grace_period.as_deref(), Some("P5D"));
+        assert!(!create.components[0].delayed);
+        assert_eq!(create.components[1].refundable, Some(false));
+        let renew = &fees.commands[1];
+        assert_eq!(renew.command, Command::Renew);
+        assert_eq!(renew.period.as_ref().unwrap().value, 2);
+        assert_eq!(renew.total.as_deref(), Some("20.00"));
+    }
+
+    #[test]
+    fn check_07() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:chkData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:cd>
+          <domain:name avail="1">premium.example</domain:name>
+        </domain:cd>
+      </domain:chkData>
+    </resData>
+    <extension>
+      <fee:chkData xmlns:fee="urn:ietf:params:xml:ns:fee-0.7">
+        <fee:cd>
+          <fee:name>premium.example</fee:name>
+          <fee:currency>EUR</fee:currency>
+          <fee:command>transfer</fee:command>
+          <fee:period unit="y">1</fee:period>
+          <fee:fee description="Transfer Fee">150.00</fee:fee>
+          <fee:credit description="Promotional credit">10.00</fee:credit>
+          <fee:class>premium-tier1</fee:class>
+        </fee:cd>
+      </fee:chkData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let fees = check_fees(&check_response(XML_DATA));
+        assert_eq!(fees.currency.as_deref(), Some("EUR"));
+        assert!(fees.premium);
+        assert_eq!(fees.class.as_deref(), Some("premium-tier1"));
+        let transfer = &fees.commands[0];
+        assert_eq!(transfer.command, Command::Transfer);
+        assert_eq!(transfer.components.len(), 2);
+        assert_eq!(transfer.components[1].amount, "-10.00");
+        assert_eq!(transfer.total.as_deref(), Some("140.00"));
+    }
+
+    #[test]
+    fn check_08() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:chkData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:cd>
+          <domain:name avail="0">example.net</domain:name>
+          <domain:reason>In use</domain:reason>
+        </domain:cd>
+      </domain:chkData>
+    </resData>
+    <extension>
+      <fee:chkData xmlns:fee="urn:ietf:params:xml:ns:fee-0.8">
+        <fee:cd>
+          <fee:name>example.net</fee:name>
+          <fee:currency>USD</fee:currency>
+          <fee:command phase="sunrise">create</fee:command>
+          <fee:period unit="y">1</fee:period>
+          <fee:fee description="Application Fee" refundable="0" applied="immediate">5.00</fee:fee>
+          <fee:fee description="Registration Fee" refundable="1" applied="delayed">10.00</fee:fee>
+          <fee:class>standard</fee:class>
+        </fee:cd>
+      </fee:chkData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let fees = check_fees(&check_response(XML_DATA));
+        assert!(!fees.premium);
+        assert_eq!(fees.class.as_deref(), Some("standard"));
+        let create = &fees.commands[0];
+        assert_eq!(create.command, Command::Create);
+        assert_eq!(create.total.as_deref(), Some("15.00"));
+        assert!(!create.components[0].delayed);
+        assert_eq!(create.components[0].refundable, Some(false));
+        assert!(create.components[1].delayed);
+        assert_eq!(create.components[1].refundable, Some(true));
+    }
+
+    #[test]
+    fn check_09() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:chkData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:cd>
+          <domain:name avail="1">example.com</domain:name>
+        </domain:cd>
+      </domain:chkData>
+    </resData>
+    <extension>
+      <fee:chkData xmlns:fee="urn:ietf:params:xml:ns:fee-0.9">
+        <fee:cd objURI="urn:ietf:params:xml:ns:domain-1.0">
+          <fee:objID element="name">example.com</fee:objID>
+          <fee:currency>USD</fee:currency>
+          <fee:command>restore</fee:command>
+          <fee:fee description="Restore Fee" refundable="0">40.00</fee:fee>
+        </fee:cd>
+      </fee:chkData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let fees = check_fees(&check_response(XML_DATA));
+        assert_eq!(fees.currency.as_deref(), Some("USD"));
+        let restore = &fees.commands[0];
+        assert_eq!(restore.command, Command::Restore);
+        assert!(restore.period.is_none());
+        assert_eq!(restore.total.as_deref(), Some("40.00"));
+        assert_eq!(restore.components[0].description.as_deref(), Some("Restore Fee"));
+    }
+
+    #[test]
+    fn check_011() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:chkData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:cd>
+          <domain:name avail="1">example.xyz</domain:name>
+        </domain:cd>
+      </domain:chkData>
+    </resData>
+    <extension>
+      <fee:chkData xmlns:fee="urn:ietf:params:xml:ns:fee-0.11">
+        <fee:cd avail="1">
+          <fee:object>example.xyz</fee:object>
+          <fee:command standard="0">create</fee:command>
+          <fee:currency>USD</fee:currency>
+          <fee:period unit="y">1</fee:period>
+          <fee:fee description="Registration Fee" refundable="1" grace-period="P5D">500.00</fee:fee>
+          <fee:class>Tier 2</fee:class>
+        </fee:cd>
+        <fee:cd avail="1">
+          <fee:object>example.xyz</fee:object>
+          <fee:command standard="0">renew</fee:command>
+          <fee:currency>USD</fee:currency>
+          <fee:period unit="y">1</fee:period>
+          <fee:fee description="Renewal Fee" refundable="1" grace-period="P5D">500.00</fee:fee>
+          <fee:class>Tier 2</fee:class>
+        </fee:cd>
+      </fee:chkData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let fees = check_fees(&check_response(XML_DATA));
+        assert!(fees.premium);
+        assert_eq!(fees.class.as_deref(), Some("Tier 2"));
+        assert_eq!(fees.commands.len(), 2);
+        assert_eq!(fees.commands[0].command, Command::Create);
+        assert_eq!(fees.commands[0].total.as_deref(), Some("500.00"));
+        assert_eq!(fees.commands[1].command, Command::Renew);
+        assert_eq!(fees.commands[1].total.as_deref(), Some("500.00"));
+    }
+
+    #[test]
+    fn check_10() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:chkData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:cd>
+          <domain:name avail="1">example.com</domain:name>
+        </domain:cd>
+      </domain:chkData>
+    </resData>
+    <extension>
+      <fee:chkData xmlns:fee="urn:ietf:params:xml:ns:epp:fee-1.0">
+        <fee:currency>USD</fee:currency>
+        <fee:cd avail="1">
+          <fee:objID>example.com</fee:objID>
+          <fee:class>standard</fee:class>
+          <fee:command name="create" standard="1">
+            <fee:period unit="y">2</fee:period>
+            <fee:fee description="Registration Fee" refundable="1" grace-period="P5D">10.00</fee:fee>
+            <fee:credit description="Bulk discount">-1.50</fee:credit>
+          </fee:command>
+          <fee:command name="transfer" standard="1">
+            <fee:period unit="y">1</fee:period>
+            <fee:fee description="Transfer Fee" refundable="1" grace-period="P5D" applied="delayed">5.00</fee:fee>
+          </fee:command>
+          <fee:command name="restore">
+            <fee:reason>Fee not available for restore</fee:reason>
+          </fee:command>
+        </fee:cd>
+      </fee:chkData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let fees = check_fees(&check_response(XML_DATA));
+        assert_eq!(fees.currency.as_deref(), Some("USD"));
+        assert!(!fees.premium);
+        assert_eq!(fees.commands.len(), 3);
+        let create = &fees.commands[0];
+        assert_eq!(create.command, Command::Create);
+        assert_eq!(create.period.as_ref().unwrap().value, 2);
+        assert_eq!(create.total.as_deref(), Some("8.50"));
+        assert_eq!(create.components[1].amount, "-1.50");
+        let transfer = &fees.commands[1];
+        assert!(transfer.components[0].delayed);
+        let restore = &fees.commands[2];
+        assert_eq!(restore.command, Command::Restore);
+        assert!(restore.total.is_none());
+        assert_eq!(
+            restore.reason.as_deref(),
+            Some("Fee not available for restore")
+        );
+    }
+
+    #[test]
+    fn check_donuts_charge() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:chkData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:cd>
+          <domain:name avail="1">premium.guru</domain:name>
+        </domain:cd>
+      </domain:chkData>
+    </resData>
+    <extension>
+      <charge:chkData xmlns:charge="http://www.unitedtld.com/epp/charge-1.0">
+        <charge:cd>
+          <charge:name>premium.guru</charge:name>
+          <charge:set>
+            <charge:category name="BBB+">premium</charge:category>
+            <charge:type>price</charge:type>
+            <charge:amount command="create">100.00</charge:amount>
+            <charge:amount command="renew">100.00</charge:amount>
+            <charge:amount command="transfer">100.00</charge:amount>
+            <charge:amount command="update" name="restore">50.00</charge:amount>
+          </charge:set>
+        </charge:cd>
+      </charge:chkData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let fees = check_fees(&check_response(XML_DATA));
+        assert!(fees.currency.is_none());
+        assert!(fees.premium);
+        assert_eq!(fees.class.as_deref(), Some("premium"));
+        assert_eq!(fees.commands.len(), 4);
+        assert_eq!(fees.commands[0].command, Command::Create);
+        assert_eq!(fees.commands[0].total.as_deref(), Some("100.00"));
+        assert_eq!(
+            fees.commands[0].components[0].description.as_deref(),
+            Some("price")
+        );
+        let restore = &fees.commands[3];
+        assert_eq!(restore.command, Command::Restore);
+        assert!(restore.command_name.is_none());
+        assert_eq!(restore.total.as_deref(), Some("50.00"));
+    }
+
+    #[test]
+    fn create_05() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:creData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:name>example.com</domain:name>
+        <domain:crDate>1999-04-03T22:00:00.0Z</domain:crDate>
+        <domain:exDate>2001-04-03T22:00:00.0Z</domain:exDate>
+      </domain:creData>
+    </resData>
+    <extension>
+      <fee:creData xmlns:fee="urn:ietf:params:xml:ns:fee-0.5">
+        <fee:currency>USD</fee:currency>
+        <fee:fee>5.00</fee:fee>
+        <fee:balance>-5.00</fee:balance>
+        <fee:creditLimit>1000.00</fee:creditLimit>
+      </fee:creData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res = create_response(XML_DATA);
+        let fees = NormalisedFees::from_data(
+            Command::Create,
+            res.fee_data.as_ref(),
+            res.donuts_fee_data.as_ref(),
+        )
+        .unwrap();
+        assert_eq!(fees.currency.as_deref(), Some("USD"));
+        assert_eq!(fees.balance.as_deref(), Some("-5.00"));
+        assert_eq!(fees.credit_limit.as_deref(), Some("1000.00"));
+        assert_eq!(fees.commands.len(), 1);
+        assert_eq!(fees.commands[0].command, Command::Create);
+        assert_eq!(fees.commands[0].total.as_deref(), Some("5.00"));
+        assert_eq!(fees.commands[0].components[0].refundable, Some(true));
+    }
+
+    #[test]
+    fn create_10() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:creData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:name>example.com</domain:name>
+        <domain:crDate>1999-04-03T22:00:00.0Z</domain:crDate>
+        <domain:exDate>2001-04-03T22:00:00.0Z</domain:exDate>
+      </domain:creData>
+    </resData>
+    <extension>
+      <fee:creData xmlns:fee="urn:ietf:params:xml:ns:epp:fee-1.0">
+        <fee:currency>USD</fee:currency>
+        <fee:fee description="Registration Fee" refundable="1" grace-period="P5D">5.00</fee:fee>
+        <fee:fee description="Early Access Fee" refundable="0" applied="immediate">100.00</fee:fee>
+        <fee:balance>-105.00</fee:balance>
+        <fee:creditLimit>1000.00</fee:creditLimit>
+      </fee:creData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res = create_response(XML_DATA);
+        let fees = NormalisedFees::from_data(
+            Command::Create,
+            res.fee_data.as_ref(),
+            res.donuts_fee_data.as_ref(),
+        )
+        .unwrap();
+        assert_eq!(fees.currency.as_deref(), Some("USD"));
+        assert_eq!(fees.balance.as_deref(), Some("-105.00"));
+        let create = &fees.commands[0];
+        assert_eq!(create.total.as_deref(), Some("105.00"));
+        assert_eq!(create.components[0].grace_period.as_deref(), Some("P5D"));
+        assert_eq!(create.components[1].refundable, Some(false));
+        assert!(!create.components[1].delayed);
+    }
+}
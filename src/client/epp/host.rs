@@ -1,9 +1,9 @@
 //! EPP commands relating to host (nameserver) objects
 
 use super::super::host::{
-    Address, AddressVersion, CheckRequest, CheckResponse, CreateRequest, CreateResponse,
-    DeleteRequest, DeleteResponse, InfoRequest, InfoResponse, Status, UpdateObject, UpdateRequest,
-    UpdateResponse,
+    Address, AddressVersion, BatchCheckRequest, BatchCheckResponse, BatchCheckResult,
+    CheckRequest, CheckResponse, CreateRequest, CreateResponse, DeleteRequest, DeleteResponse,
+    InfoRequest, InfoResponse, Status, UpdateObject, UpdateRequest, UpdateResponse,
 };
 use super::super::{proto, Error, Response};
 use super::router::HandleReqReturn;
@@ -129,6 +129,57 @@ pub fn handle_check_response<M: crate::metrics::Metrics>(
     }
 }
 
+pub fn handle_batch_check(
+    client: &ServerFeatures,
+    req: &BatchCheckRequest,
+) -> HandleReqReturn<BatchCheckResponse> {
+    if !(client.host_supported || client.nsset_supported) {
+        return Err(Err(Error::Unsupported));
+    }
+    if req.names.is_empty() {
+        return Err(Err(Error::Err("at least one host must be checked".to_string())));
+    }
+    for name in &req.names {
+        check_host(name)?;
+    }
+    let mut ext = vec![];
+    super::verisign::handle_verisign_namestore_erratum(client, &mut ext);
+    let command = proto::EPPCheck::HostBatch(proto::host::EPPHostBatchCheck {
+        names: req.names.clone(),
+    });
+    Ok((
+        proto::EPPCommandType::Check(command),
+        match ext.is_empty() {
+            true => None,
+            false => Some(ext),
+        },
+    ))
+}
+
+pub fn handle_batch_check_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<BatchCheckResponse> {
+    match response.data {
+        Some(value) => match value.value {
+            proto::EPPResultDataValue::EPPHostCheckResult(host_check) => {
+                Response::Ok(BatchCheckResponse {
+                    results: host_check
+                        .data
+                        .into_iter()
+                        .map(|d| BatchCheckResult {
+                            name: d.name.name,
+                            avail: d.name.available,
+                            reason: d.reason,
+                        })
+                        .collect(),
+                })
+            }
+            _ => Err(Error::ServerInternal),
+        },
+        None => Err(Error::ServerInternal),
+    }
+}
+
 pub fn handle_info(client: &ServerFeatures, req: &InfoRequest) -> HandleReqReturn<InfoResponse> {
     if !(client.host_supported || client.nsset_supported) {
         return Err(Err(Error::Unsupported));
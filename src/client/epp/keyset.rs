@@ -0,0 +1,702 @@
+//! EPP commands relating to FRED KEYSET (DNSSEC key set) objects
+
+use super::super::keyset::{
+    CheckRequest, CheckResponse, CreateRequest, CreateResponse, DNSKey, DeleteRequest,
+    DeleteResponse, InfoRequest, InfoResponse, TransferRequest, TransferResponse, UpdateRequest,
+    UpdateResponse,
+};
+use super::super::fred::{IdleDeleteData, ObjectType, TransferData};
+use super::super::{proto, Error, Response};
+use super::router::HandleReqReturn;
+use super::ServerFeatures;
+
+impl From<proto::keyset::EPPKeySetInfoData> for InfoResponse {
+    fn from(keyset_info: proto::keyset::EPPKeySetInfoData) -> Self {
+        InfoResponse {
+            id: keyset_info.id,
+            registry_id: keyset_info.registry_id.unwrap_or_default(),
+            statuses: keyset_info
+                .statuses
+                .into_iter()
+                .map(|s| s.status)
+                .collect(),
+            client_id: keyset_info.client_id,
+            client_created_id: keyset_info.client_created_id,
+            creation_date: keyset_info.creation_date,
+            last_updated_client: keyset_info.last_updated_client,
+            last_updated_date: keyset_info.last_updated_date,
+            last_transfer_date: keyset_info.last_transfer_date,
+            auth_info: keyset_info.auth_info,
+            keys: keyset_info
+                .keys
+                .into_iter()
+                .map(|k| DNSKey {
+                    flags: k.flags,
+                    protocol: k.protocol,
+                    algorithm: k.algorithm,
+                    public_key: k.public_key,
+                })
+                .collect(),
+            tech_contacts: keyset_info.tech_contacts,
+        }
+    }
+}
+
+impl From<proto::keyset::EPPKeySetTransferData> for TransferData {
+    fn from(from: proto::keyset::EPPKeySetTransferData) -> Self {
+        TransferData {
+            object_type: ObjectType::KeySet,
+            id: from.id,
+            transfer_date: from.transfer_date,
+            client_id: from.client_id,
+        }
+    }
+}
+
+impl From<proto::keyset::EPPKeySetDeleteData> for IdleDeleteData {
+    fn from(from: proto::keyset::EPPKeySetDeleteData) -> Self {
+        IdleDeleteData {
+            object_type: ObjectType::KeySet,
+            id: from.id,
+        }
+    }
+}
+
+pub(crate) fn check_id<T>(id: &str) -> Result<(), Response<T>> {
+    if let 1..=30 = id.len() {
+        Ok(())
+    } else {
+        Err(Err(Error::Err(
+            "KEYSET id has a min length of 1 and a max length of 30".to_string(),
+        )))
+    }
+}
+
+fn map_keys<T>(keys: &[DNSKey]) -> Result<Vec<proto::keyset::EPPKeySetDNSKeyParam>, Response<T>> {
+    keys.iter()
+        .map(|k| {
+            if k.public_key.is_empty() {
+                return Err(Err(Error::Err(
+                    "public key has a min length of 1".to_string(),
+                )));
+            }
+            Ok(proto::keyset::EPPKeySetDNSKeyParam {
+                flags: k.flags,
+                protocol: k.protocol,
+                algorithm: k.algorithm,
+                public_key: k.public_key.clone(),
+            })
+        })
+        .collect()
+}
+
+pub fn handle_check(client: &ServerFeatures, req: &CheckRequest) -> HandleReqReturn<CheckResponse> {
+    if !client.keyset_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    check_id(&req.id)?;
+    let command = proto::EPPCheck::KeySet(proto::keyset::EPPKeySetCheck { id: req.id.clone() });
+    Ok((proto::EPPCommandType::Check(command), None))
+}
+
+pub fn handle_check_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<CheckResponse> {
+    match response.data {
+        Some(value) => match value.value {
+            proto::EPPResultDataValue::EPPKeySetCheckResult(keyset_check) => {
+                if let Some(keyset_check) = keyset_check.data.first() {
+                    Response::Ok(CheckResponse {
+                        avail: keyset_check.id.available,
+                        reason: keyset_check.reason.to_owned(),
+                    })
+                } else {
+                    Err(Error::ServerInternal)
+                }
+            }
+            _ => Err(Error::ServerInternal),
+        },
+        None => Err(Error::ServerInternal),
+    }
+}
+
+pub fn handle_info(client: &ServerFeatures, req: &InfoRequest) -> HandleReqReturn<InfoResponse> {
+    if !client.keyset_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    check_id(&req.id)?;
+    let command = proto::EPPInfo::KeySet(proto::keyset::EPPKeySetCheck { id: req.id.clone() });
+    Ok((proto::EPPCommandType::Info(command), None))
+}
+
+pub fn handle_info_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<InfoResponse> {
+    match response.data {
+        Some(value) => match value.value {
+            proto::EPPResultDataValue::EPPKeySetInfoResult(keyset_info) => {
+                Response::Ok((*keyset_info).into())
+            }
+            _ => Err(Error::ServerInternal),
+        },
+        None => Err(Error::ServerInternal),
+    }
+}
+
+pub fn handle_create(
+    client: &ServerFeatures,
+    req: &CreateRequest,
+) -> HandleReqReturn<CreateResponse> {
+    if !client.keyset_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    check_id(&req.id)?;
+    if req.keys.is_empty() {
+        return Err(Err(Error::Err(
+            "at least one key must be specified".to_string(),
+        )));
+    }
+    if req.tech_contacts.is_empty() {
+        return Err(Err(Error::Err(
+            "at least one technical contact must be specified".to_string(),
+        )));
+    }
+    for tech in &req.tech_contacts {
+        super::contact::check_id(tech)?;
+    }
+    let command = proto::EPPCreate::KeySet(proto::keyset::EPPKeySetCreate {
+        id: req.id.clone(),
+        keys: map_keys(&req.keys)?,
+        tech_contacts: req.tech_contacts.clone(),
+        auth_info: req.auth_info.clone(),
+    });
+    Ok((proto::EPPCommandType::Create(command), None))
+}
+
+pub fn handle_create_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<CreateResponse> {
+    let pending = response.is_pending();
+    let transaction_id = response
+        .transaction_id
+        .server_transaction_id
+        .unwrap_or_default();
+    match response.data {
+        Some(value) => match value.value {
+            proto::EPPResultDataValue::EPPKeySetCreateResult(keyset_create) => {
+                Response::Ok(CreateResponse {
+                    id: keyset_create.id,
+                    pending,
+                    transaction_id,
+                    creation_date: keyset_create.creation_date,
+                })
+            }
+            _ => Err(Error::ServerInternal),
+        },
+        None => Response::Ok(CreateResponse {
+            id: "".to_string(),
+            pending,
+            transaction_id,
+            creation_date: None,
+        }),
+    }
+}
+
+pub fn handle_delete(
+    client: &ServerFeatures,
+    req: &DeleteRequest,
+) -> HandleReqReturn<DeleteResponse> {
+    if !client.keyset_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    check_id(&req.id)?;
+    let command = proto::EPPDelete::KeySet(proto::keyset::EPPKeySetCheck { id: req.id.clone() });
+    Ok((proto::EPPCommandType::Delete(command), None))
+}
+
+pub fn handle_delete_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<DeleteResponse> {
+    Response::Ok(DeleteResponse {
+        pending: response.is_pending(),
+        transaction_id: response
+            .transaction_id
+            .server_transaction_id
+            .unwrap_or_default(),
+    })
+}
+
+pub fn handle_update(
+    client: &ServerFeatures,
+    req: &UpdateRequest,
+) -> HandleReqReturn<UpdateResponse> {
+    if !client.keyset_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    check_id(&req.id)?;
+    if req.add_keys.is_empty()
+        && req.remove_keys.is_empty()
+        && req.add_tech_contacts.is_empty()
+        && req.remove_tech_contacts.is_empty()
+        && req.new_auth_info.is_none()
+    {
+        return Err(Err(Error::Err(
+            "at least one operation must be specified".to_string(),
+        )));
+    }
+    for tech in req.add_tech_contacts.iter().chain(req.remove_tech_contacts.iter()) {
+        super::contact::check_id(tech)?;
+    }
+
+    let add_keys = map_keys(&req.add_keys)?;
+    let remove_keys = map_keys(&req.remove_keys)?;
+    let command = proto::EPPUpdate::KeySet(proto::keyset::EPPKeySetUpdate {
+        id: req.id.clone(),
+        add: if add_keys.is_empty() && req.add_tech_contacts.is_empty() {
+            None
+        } else {
+            Some(proto::keyset::EPPKeySetUpdateParams {
+                keys: add_keys,
+                tech_contacts: req.add_tech_contacts.clone(),
+            })
+        },
+        remove: if remove_keys.is_empty() && req.remove_tech_contacts.is_empty() {
+            None
+        } else {
+            Some(proto::keyset::EPPKeySetUpdateParams {
+                keys: remove_keys,
+                tech_contacts: req.remove_tech_contacts.clone(),
+            })
+        },
+        change: req
+            .new_auth_info
+            .as_ref()
+            .map(|a| proto::keyset::EPPKeySetUpdateChange {
+                auth_info: a.clone(),
+            }),
+    });
+    Ok((proto::EPPCommandType::Update(Box::new(command)), None))
+}
+
+pub fn handle_update_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<UpdateResponse> {
+    Response::Ok(UpdateResponse {
+        pending: response.is_pending(),
+        transaction_id: response
+            .transaction_id
+            .server_transaction_id
+            .unwrap_or_default(),
+    })
+}
+
+pub fn handle_transfer(
+    client: &ServerFeatures,
+    req: &TransferRequest,
+) -> HandleReqReturn<TransferResponse> {
+    if !client.keyset_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    check_id(&req.id)?;
+    if req.auth_info.is_empty() {
+        return Err(Err(Error::Err(
+            "auth info has a min length of 1".to_string(),
+        )));
+    }
+    let command = proto::EPPTransfer {
+        operation: proto::EPPTransferOperation::Request,
+        command: proto::EPPTransferCommand::KeySetRequest(proto::keyset::EPPKeySetTransfer {
+            id: req.id.clone(),
+            auth_info: req.auth_info.clone(),
+        }),
+    };
+    Ok((proto::EPPCommandType::Transfer(command), None))
+}
+
+pub fn handle_transfer_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<TransferResponse> {
+    Response::Ok(TransferResponse {
+        pending: response.is_pending(),
+        transaction_id: response
+            .transaction_id
+            .server_transaction_id
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod keyset_tests {
+    fn features() -> super::ServerFeatures {
+        super::ServerFeatures {
+            keyset_supported: true,
+            ..Default::default()
+        }
+    }
+
+    fn command_xml(
+        command: super::proto::EPPCommandType,
+        extension: Option<Vec<super::proto::EPPCommandExtensionType>>,
+    ) -> String {
+        let msg = super::proto::EPPMessage {
+            message: super::proto::EPPMessageType::Command(Box::new(super::proto::EPPCommand {
+                command,
+                extension: extension.map(|value| super::proto::EPPCommandExtension { value }),
+                client_transaction_id: None,
+            })),
+        };
+        xml_serde::to_string(&msg).unwrap()
+    }
+
+    fn response(xml: &str) -> super::proto::EPPResponse {
+        let res: super::proto::EPPMessage = xml_serde::from_str(xml.trim()).unwrap();
+        match res.message {
+            super::proto::EPPMessageType::Response(r) => *r,
+            _ => unreachable!(),
+        }
+    }
+
+    fn dnskey() -> super::DNSKey {
+        super::DNSKey {
+            flags: 257,
+            protocol: 3,
+            algorithm: 5,
+            public_key: "AwEAAddt2AkLfYGKgiEZB5SmIF8EvrjxNMH6HtxWEA4RJ9Ao6LCWheg8".to_string(),
+        }
+    }
+
+    #[test]
+    fn check_command() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_check(
+            &features(),
+            &super::CheckRequest {
+                id: "KID-EXAMPLE".to_string(),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let res = command_xml(command, extension);
+        assert!(res.contains("xmlns:keyset=\"urn:ietf:params:xml:ns:keyset-1.3\""));
+        assert!(res.contains("<keyset:check"));
+        assert!(res.contains("<keyset:id>KID-EXAMPLE</keyset:id>"));
+    }
+
+    #[test]
+    fn check_unsupported() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        assert!(super::handle_check(
+            &Default::default(),
+            &super::CheckRequest {
+                id: "KID-EXAMPLE".to_string(),
+                return_path: sender,
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn check_response() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <keyset:chkData xmlns:keyset="urn:ietf:params:xml:ns:keyset-1.3">
+        <keyset:cd>
+          <keyset:id avail="0">KID-EXAMPLE</keyset:id>
+          <keyset:reason>already registered.</keyset:reason>
+        </keyset:cd>
+      </keyset:chkData>
+    </resData>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>ReqID-0000140896</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let data = super::handle_check_response(
+            response(XML_DATA), &crate::metrics::DummyMetrics::default()).unwrap();
+        assert!(!data.avail);
+        assert_eq!(data.reason.as_deref(), Some("already registered."));
+    }
+
+    #[test]
+    fn info_command() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_info(
+            &features(),
+            &super::InfoRequest {
+                id: "KID-EXAMPLE".to_string(),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let res = command_xml(command, extension);
+        assert!(res.contains("<keyset:info"));
+        assert!(res.contains("<keyset:id>KID-EXAMPLE</keyset:id>"));
+    }
+
+    #[test]
+    fn info_response() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <keyset:infData xmlns:keyset="urn:ietf:params:xml:ns:keyset-1.3">
+        <keyset:id>KID-EXAMPLE</keyset:id>
+        <keyset:roid>K0009907597-CZ</keyset:roid>
+        <keyset:status s="ok"/>
+        <keyset:clID>REG-EXAMPLE</keyset:clID>
+        <keyset:crID>REG-EXAMPLE</keyset:crID>
+        <keyset:crDate>2017-07-11T13:28:48+02:00</keyset:crDate>
+        <keyset:upID>REG-EXAMPLE</keyset:upID>
+        <keyset:upDate>2017-07-12T09:02:11+02:00</keyset:upDate>
+        <keyset:authInfo>gnsAuth</keyset:authInfo>
+        <keyset:dnskey>
+          <keyset:flags>257</keyset:flags>
+          <keyset:protocol>3</keyset:protocol>
+          <keyset:alg>5</keyset:alg>
+          <keyset:pubKey>AwEAAddt2AkLfYGKgiEZB5SmIF8EvrjxNMH6HtxWEA4RJ9Ao6LCWheg8</keyset:pubKey>
+        </keyset:dnskey>
+        <keyset:tech>CID-TECH</keyset:tech>
+      </keyset:infData>
+    </resData>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>ReqID-0000140900</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let data = super::handle_info_response(
+            response(XML_DATA), &crate::metrics::DummyMetrics::default()).unwrap();
+        assert_eq!(data.id, "KID-EXAMPLE");
+        assert_eq!(data.registry_id, "K0009907597-CZ");
+        assert_eq!(data.statuses, vec!["ok"]);
+        assert_eq!(data.client_id, "REG-EXAMPLE");
+        assert_eq!(data.last_updated_client.as_deref(), Some("REG-EXAMPLE"));
+        assert!(data.last_updated_date.is_some());
+        assert_eq!(data.auth_info.as_deref(), Some("gnsAuth"));
+        assert_eq!(data.keys.len(), 1);
+        assert_eq!(data.keys[0].flags, 257);
+        assert_eq!(data.keys[0].protocol, 3);
+        assert_eq!(data.keys[0].algorithm, 5);
+        assert_eq!(data.keys[0].public_key, dnskey().public_key);
+        assert_eq!(data.tech_contacts, vec!["CID-TECH"]);
+    }
+
+    #[test]
+    fn create_command() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_create(
+            &features(),
+            &super::CreateRequest {
+                id: "KID-EXAMPLE".to_string(),
+                keys: vec![dnskey()],
+                tech_contacts: vec!["CID-TECH".to_string()],
+                auth_info: Some("gnsAuth".to_string()),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let res = command_xml(command, extension);
+        assert!(res.contains("<keyset:create"));
+        assert!(res.contains("<keyset:id>KID-EXAMPLE</keyset:id>"));
+        assert!(res.contains("<keyset:flags>257</keyset:flags>"));
+        assert!(res.contains("<keyset:protocol>3</keyset:protocol>"));
+        assert!(res.contains("<keyset:alg>5</keyset:alg>"));
+        assert!(res.contains(
+            "<keyset:pubKey>AwEAAddt2AkLfYGKgiEZB5SmIF8EvrjxNMH6HtxWEA4RJ9Ao6LCWheg8</keyset:pubKey>"
+        ));
+        assert!(res.contains("<keyset:tech>CID-TECH</keyset:tech>"));
+        assert!(res.contains("<keyset:authInfo>gnsAuth</keyset:authInfo>"));
+    }
+
+    #[test]
+    fn create_no_keys() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        assert!(super::handle_create(
+            &features(),
+            &super::CreateRequest {
+                id: "KID-EXAMPLE".to_string(),
+                keys: vec![],
+                tech_contacts: vec!["CID-TECH".to_string()],
+                auth_info: None,
+                return_path: sender,
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn create_response() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <keyset:creData xmlns:keyset="urn:ietf:params:xml:ns:keyset-1.3">
+        <keyset:id>KID-EXAMPLE</keyset:id>
+        <keyset:crDate>2017-07-11T13:28:48+02:00</keyset:crDate>
+      </keyset:creData>
+    </resData>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>ReqID-0000140901</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let data = super::handle_create_response(
+            response(XML_DATA), &crate::metrics::DummyMetrics::default()).unwrap();
+        assert_eq!(data.id, "KID-EXAMPLE");
+        assert!(!data.pending);
+        assert_eq!(data.transaction_id, "ReqID-0000140901");
+        assert!(data.creation_date.is_some());
+    }
+
+    #[test]
+    fn update_command() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_update(
+            &features(),
+            &super::UpdateRequest {
+                id: "KID-EXAMPLE".to_string(),
+                add_keys: vec![dnskey()],
+                remove_keys: vec![],
+                add_tech_contacts: vec![],
+                remove_tech_contacts: vec!["CID-OLD".to_string()],
+                new_auth_info: Some("newAuth".to_string()),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let res = command_xml(command, extension);
+        assert!(res.contains("<keyset:update"));
+        assert!(res.contains("<keyset:id>KID-EXAMPLE</keyset:id>"));
+        assert!(res.contains("<keyset:add>"));
+        assert!(res.contains("<keyset:flags>257</keyset:flags>"));
+        assert!(res.contains("<keyset:rem>"));
+        assert!(res.contains("<keyset:tech>CID-OLD</keyset:tech>"));
+        assert!(res.contains("<keyset:chg>"));
+        assert!(res.contains("<keyset:authInfo>newAuth</keyset:authInfo>"));
+    }
+
+    #[test]
+    fn update_no_changes() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        assert!(super::handle_update(
+            &features(),
+            &super::UpdateRequest {
+                id: "KID-EXAMPLE".to_string(),
+                add_keys: vec![],
+                remove_keys: vec![],
+                add_tech_contacts: vec![],
+                remove_tech_contacts: vec![],
+                new_auth_info: None,
+                return_path: sender,
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn delete_command() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_delete(
+            &features(),
+            &super::DeleteRequest {
+                id: "KID-EXAMPLE".to_string(),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let res = command_xml(command, extension);
+        assert!(res.contains("<keyset:delete"));
+        assert!(res.contains("<keyset:id>KID-EXAMPLE</keyset:id>"));
+    }
+
+    #[test]
+    fn delete_response() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>ReqID-0000140902</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let data = super::handle_delete_response(
+            response(XML_DATA), &crate::metrics::DummyMetrics::default()).unwrap();
+        assert!(!data.pending);
+        assert_eq!(data.transaction_id, "ReqID-0000140902");
+    }
+
+    #[test]
+    fn transfer_command() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_transfer(
+            &features(),
+            &super::TransferRequest {
+                id: "KID-EXAMPLE".to_string(),
+                auth_info: "gnsAuth".to_string(),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let res = command_xml(command, extension);
+        assert!(res.contains("op=\"request\""));
+        assert!(res.contains("<keyset:transfer"));
+        assert!(res.contains("<keyset:id>KID-EXAMPLE</keyset:id>"));
+        assert!(res.contains("<keyset:authInfo>gnsAuth</keyset:authInfo>"));
+    }
+
+    #[test]
+    fn transfer_no_auth_info() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        assert!(super::handle_transfer(
+            &features(),
+            &super::TransferRequest {
+                id: "KID-EXAMPLE".to_string(),
+                auth_info: "".to_string(),
+                return_path: sender,
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn transfer_response() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>ReqID-0000140903</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let data = super::handle_transfer_response(
+            response(XML_DATA), &crate::metrics::DummyMetrics::default()).unwrap();
+        assert!(!data.pending);
+        assert_eq!(data.transaction_id, "ReqID-0000140903");
+    }
+}
@@ -1,4 +1,4 @@
-use super::{router as outer_router, BlankRequest, RequestMessage};
+use super::{router as outer_router, BlankRequest};
 use crate::client::router::CommandTransactionID;
 use crate::{proto};
 use super::Client;
@@ -12,14 +12,17 @@ pub mod contact;
 pub mod domain;
 pub mod email_forward;
 pub mod eurid;
+pub mod ext_value;
 pub mod fee;
 pub mod host;
 pub mod isnic;
+pub mod keyset;
 pub mod keysys;
 pub mod launch;
 pub mod maintenance;
 pub mod mark;
 pub mod nominet;
+pub mod nsset;
 pub mod personal_registration;
 pub mod poll;
 pub mod rgp;
@@ -30,7 +33,7 @@ pub mod verisign;
 use crate::proto::EPPServiceExtension;
 
 fn recv_msg(data: String, host: &str) -> Result<proto::EPPMessage, ()> {
-    let message: proto::EPPMessage = match xml_serde::from_str(&data) {
+    let mut message: proto::EPPMessage = match xml_serde::from_str(&data) {
         Ok(m) => m,
         Err(err) => {
             error!("Invalid XML from {}: {}", host, err);
@@ -38,9 +41,41 @@ fn recv_msg(data: String, host: &str) -> Result<proto::EPPMessage, ()> {
         }
     };
     debug!("Decoded EPP message from {} to: {:#?}", host, message);
+    if let proto::EPPMessageType::Response(response) = &mut message.message {
+        response.raw = Some(data);
+    }
     Ok(message)
 }
 
+fn login_sec_event(
+    event: &proto::login_sec::EPPLoginSecurityEvent,
+) -> super::login_sec::LoginSecurityEvent {
+    use super::login_sec::{LoginSecurityEventLevel, LoginSecurityEventType};
+    use proto::login_sec::{EPPLoginSecurityEventLevel, EPPLoginSecurityEventType};
+
+    super::login_sec::LoginSecurityEvent {
+        event_type: match event.event_type {
+            EPPLoginSecurityEventType::Password => LoginSecurityEventType::Password,
+            EPPLoginSecurityEventType::Certificate => LoginSecurityEventType::Certificate,
+            EPPLoginSecurityEventType::Cipher => LoginSecurityEventType::Cipher,
+            EPPLoginSecurityEventType::TLSProtocol => LoginSecurityEventType::TLSProtocol,
+            EPPLoginSecurityEventType::NewPassword => LoginSecurityEventType::NewPassword,
+            EPPLoginSecurityEventType::Statistical => LoginSecurityEventType::Statistical,
+            EPPLoginSecurityEventType::Custom => LoginSecurityEventType::Custom,
+        },
+        name: event.event_name.clone(),
+        level: match event.level {
+            EPPLoginSecurityEventLevel::Warning => LoginSecurityEventLevel::Warning,
+            EPPLoginSecurityEventLevel::Error => LoginSecurityEventLevel::Error,
+        },
+        expiration_date: event.expiration_date,
+        value: event.value.clone(),
+        duration: event.duration.clone(),
+        message: event.msg.clone(),
+        received: Utc::now(),
+    }
+}
+
 fn send_msg(data: &proto::EPPMessage, host: &str) -> Result<String, ()> {
     let encoded_msg = match xml_serde::to_string(data) {
         Ok(m) => m,
@@ -84,6 +119,14 @@ pub struct ServerFeatures {
     nominet_release: bool,
     /// http://www.nominet.org.uk/epp/xml/std-release-1.0 support
     nominet_handshake: bool,
+    /// http://www.nominet.org.uk/epp/xml/std-unrenew-1.0 support
+    nominet_unrenew: bool,
+    /// http://www.nominet.org.uk/epp/xml/std-list-1.0 support
+    nominet_list: bool,
+    /// http://www.nominet.org.uk/epp/xml/std-fork-1.0 support
+    nominet_fork: bool,
+    /// http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0 support
+    nominet_abuse_feed: bool,
     /// https://www.nic.ch/epp/balance-1.0 support
     switch_balance: bool,
     /// http://www.verisign.com/epp/balance-1.0 support
@@ -100,6 +143,8 @@ pub struct ServerFeatures {
     corenic_mark: bool,
     /// urn:ietf:params:xml:ns:nsset-1.2 support (NOT AN ACTUAL IETF NAMESPACE)
     nsset_supported: bool,
+    /// urn:ietf:params:xml:ns:keyset-1.3 support (NOT AN ACTUAL IETF NAMESPACE)
+    keyset_supported: bool,
     /// RFC 8748 support
     fee_supported: bool,
     /// RFC 8334 support
@@ -170,6 +215,8 @@ pub struct ServerFeatures {
     personal_registration_supported: bool,
     /// http://www.key-systems.net/epp/keysys-1.0 support
     keysys_supported: bool,
+    /// urn:ietf:params:xml:ns:allocationToken-1.0 support
+    allocation_token_supported: bool,
 }
 
 impl ServerFeatures {
@@ -186,32 +233,44 @@ impl ServerFeatures {
 pub struct EPPClient<M: crate::metrics::Metrics> {
     log_storage: crate::StorageScoped,
     metrics_registry: M,
+    status: super::status::StatusHandle,
     host: String,
     tag: String,
     password: String,
     new_password: Option<String>,
+    password_rotation: Option<super::login_sec::PasswordRotation>,
+    /// Did the last login warn that the password is expiring
+    password_rotation_due: bool,
     server_id: String,
     pipelining: bool,
     keepalive: bool,
+    queue_conf: super::queue::QueueConf,
+    timeouts: super::Timeouts,
+    reconnect: super::reconnect::ReconnectConf,
+    backoff: super::reconnect::Backoff,
     is_awaiting_response: bool,
     is_closing: bool,
     router: outer_router::Router<router::Router, ServerFeatures, M>,
     /// What features does the server support
     features: ServerFeatures,
     nominet_tag_list_subordinate: bool,
-    nominet_tag_list_subordinate_client: Option<futures::channel::mpsc::Sender<RequestMessage>>,
-    nominet_dac_subordinate_client: Option<futures::channel::mpsc::Sender<RequestMessage>>,
+    nominet_tag_list_subordinate_client: Option<super::RequestSender>,
+    nominet_dac_subordinate_client: Option<super::RequestSender>,
     nominet_dac_client: Option<super::nominet_dac::DACClient<M::Subordinate>>,
     tls_client: super::epp_like::tls_client::TLSClient,
 }
 
 impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> Client for EPPClient<M> {
+    fn status(&self) -> super::status::StatusHandle {
+        self.status.clone()
+    }
+
     // Starts up the EPP client and returns the sending end of a tokio channel to inject
     // commands into the client to be processed
     fn start(
         mut self: Box<Self>,
     ) -> (
-        futures::channel::mpsc::Sender<RequestMessage>,
+        super::RequestSender,
         futures::channel::mpsc::UnboundedReceiver<CommandTransactionID>,
     ) {
         info!("EPP Client for {} starting...", &self.host);
@@ -219,7 +278,7 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> Client for EPPClient
             info!("This is a Nominet Tag list subordinate client");
         }
         self.metrics_registry.connection_status(false);
-        let (sender, receiver) = futures::channel::mpsc::channel::<RequestMessage>(16);
+        let (sender, receiver) = super::queue::channel(16, self.timeouts.request);
         let (ready_sender, ready_receiver) = futures::channel::mpsc::unbounded();
 
         if let Some(nominet_dac_client) = self.nominet_dac_client.take() {
@@ -249,6 +308,8 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                 nominet_dac_conf.real_time,
                 nominet_dac_conf.time_delay,
                 conf.source_address,
+                conf.timeouts,
+                conf.reconnect,
                 conf.metrics_registry.subordinate("dac"),
             )
         }) {
@@ -263,12 +324,23 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
             log_storage: conf.log_storage,
             router: outer_router::Router::new(&conf.metrics_registry),
             metrics_registry: conf.metrics_registry,
+            status: super::status::StatusHandle::new(super::status::ClientStatus {
+                errata: conf.errata.clone(),
+                pipelining: conf.pipelining,
+                ..Default::default()
+            }),
             host: conf.host.to_string(),
             tag: conf.tag.to_string(),
             password: conf.password.to_string(),
             new_password: conf.new_password.into().map(|c| c.to_string()),
+            password_rotation: conf.password_rotation,
+            password_rotation_due: false,
             pipelining: conf.pipelining,
             keepalive: conf.keepalive,
+            queue_conf: conf.queue,
+            timeouts: conf.timeouts,
+            reconnect: conf.reconnect,
+            backoff: super::reconnect::Backoff::new(conf.timeouts.reconnect_delay, conf.reconnect),
             features: ServerFeatures {
                 errata: conf.errata,
                 ..Default::default()
@@ -286,30 +358,54 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
 
     async fn _main_loop(
         &mut self,
-        receiver: futures::channel::mpsc::Receiver<RequestMessage>,
+        receiver: super::queue::RequestReceiver,
         mut ready_sender: futures::channel::mpsc::UnboundedSender<CommandTransactionID>,
     ) {
         let mut receiver = receiver.fuse();
+        let mut waiting = super::queue::WaitingQueue::new(self.queue_conf);
+        let mut expire_interval = tokio::time::interval(tokio::time::Duration::new(1, 0));
+        let mut retry_delay = None;
         loop {
             self.is_closing = false;
             self.is_awaiting_response = false;
 
             let mut sock = {
                 trace!("Getting connection for {}", self.host);
-                let connect_fut = self.tls_client.connect().fuse();
+                // The wait after a failure is part of connecting, so that requests keep being
+                // queued, or rejected while the breaker is open
+                let delay = retry_delay.take();
+                let connect_fut = async {
+                    if let Some(delay) = delay {
+                        self.backoff.wait(delay, &self.metrics_registry).await;
+                    }
+                    self.tls_client
+                        .connect(&self.backoff, &self.metrics_registry)
+                        .await
+                }
+                .fuse();
                 futures::pin_mut!(connect_fut);
 
                 loop {
                     futures::select! {
                         x = receiver.next() => {
                             match x {
-                                Some(x) => outer_router::Router::<router::Router, ServerFeatures, M>::reject_request(x),
+                                Some((_, x)) if self.backoff.state() == super::reconnect::BreakerState::Open => {
+                                    x.reject(super::Error::NotReady)
+                                }
+                                Some((ctx, x)) => waiting.push(ctx, x),
                                 None => {
                                     info!("All senders for {} dropped, exiting...", self.host);
                                     return
                                 }
                             };
                         }
+                        _ = expire_interval.tick().fuse() => {
+                            if self.backoff.state() == super::reconnect::BreakerState::Open {
+                                waiting.reject_all();
+                            } else {
+                                waiting.expire();
+                            }
+                        }
                         s = connect_fut => {
                             break s;
                         }
@@ -327,13 +423,14 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                     futures::select! {
                         x = receiver.next() => {
                             match x {
-                                Some(x) => outer_router::Router::<router::Router, ServerFeatures, M>::reject_request(x),
+                                Some((ctx, x)) => waiting.push(ctx, x),
                                 None => {
                                     info!("{}", exit_str);
                                     return
                                 }
                             };
                         }
+                        _ = expire_interval.tick().fuse() => waiting.expire(),
                         s = setup_fut => {
                             break s;
                         }
@@ -344,14 +441,16 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                         if r {
                             break;
                         } else {
-                            tokio::time::sleep(tokio::time::Duration::new(5, 0)).await;
+                            retry_delay = Some(self.backoff.failure(&self.metrics_registry));
                             continue;
                         }
                     }
                 }
             };
             trace!("Connection setup to {}", self.host);
+            self.backoff.success(&self.metrics_registry);
             self.metrics_registry.connection_status(true);
+            self.status.set_connected(true);
             let _ = ready_sender.send(setup_res).await;
 
             let (sock_read, mut sock_write) = tokio::io::split(sock);
@@ -363,20 +462,24 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                 decode_fn: recv_msg,
             };
             let mut message_channel = msg_receiver.run().fuse();
-            let mut keepalive_interval = tokio::time::interval(tokio::time::Duration::new(120, 0));
+            let mut keepalive_interval = tokio::time::interval(self.timeouts.keepalive_interval);
 
             trace!("Entering event loop for {}", self.host);
             loop {
                 if self.pipelining || !self.is_awaiting_response {
+                    if let Some((ctx, r)) = waiting.pop() {
+                        match self._handle_request(ctx, r, &mut sock_write).await {
+                            Ok(_) => self.status.set_pending_commands(self.router.pending()),
+                            Err(_) => break,
+                        }
+                        continue;
+                    }
                     futures::select! {
                         r = receiver.next() => {
                             match r {
-                                Some(r) => match self._handle_request(r, &mut sock_write).await {
-                                    Ok(_) => {},
-                                    Err(_) => {
-                                        tokio::time::sleep(tokio::time::Duration::new(5, 0)).await;
-                                        break;
-                                    }
+                                Some((ctx, r)) => match self._handle_request(ctx, r, &mut sock_write).await {
+                                    Ok(_) => self.status.set_pending_commands(self.router.pending()),
+                                    Err(_) => break,
                                 },
                                 None => {
                                     info!("All senders for {} dropped, exiting...", self.host);
@@ -415,11 +518,12 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                     }
                 } else {
                     let mut delay =
-                        Box::pin(tokio::time::sleep(tokio::time::Duration::new(15, 0)).fuse());
+                        Box::pin(tokio::time::sleep(self.timeouts.response).fuse());
                     let resp = futures::select! {
                         r = message_channel.next() => r,
                         _ = delay => {
                             warn!("Timeout awaiting response from {}", self.host);
+                            self.status.set_error("Timeout awaiting response");
                             break;
                         }
                     };
@@ -448,7 +552,8 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                 }
             }
             self.metrics_registry.connection_status(false);
-            tokio::time::sleep(tokio::time::Duration::new(5, 0)).await;
+            self.status.set_connected(false);
+            retry_delay = Some(self.backoff.failure(&self.metrics_registry));
         }
     }
 
@@ -470,7 +575,7 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
             )
             .fuse();
             self.metrics_registry.request_sent();
-            let mut delay = Box::pin(tokio::time::sleep(tokio::time::Duration::new(15, 0)).fuse());
+            let mut delay = Box::pin(tokio::time::sleep(self.timeouts.response).fuse());
             futures::pin_mut!(receiver);
             let resp = futures::select! {
                 r = receiver => r,
@@ -492,6 +597,7 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
 
     async fn _handle_request<W: std::marker::Unpin + tokio::io::AsyncWrite>(
         &mut self,
+        ctx: super::queue::RequestContext,
         req: outer_router::RequestMessage,
         sock_write: &mut W,
     ) -> Result<(), ()> {
@@ -505,8 +611,8 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                     Some(c) => c,
                     None => return Err(()),
                 };
-                match client
-                    .send(outer_router::RequestMessage::NominetTagList(t))
+                match ctx
+                    .scope(client.send(outer_router::RequestMessage::NominetTagList(t)))
                     .await
                 {
                     Ok(_) => Ok(()),
@@ -643,10 +749,11 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                     None => {}
                 };
                 self.is_closing = true;
-                match self
-                    .router
-                    .handle_request(&self.features, outer_router::RequestMessage::Logout(t))
-                {
+                match self.router.handle_request(
+                    &self.features,
+                    outer_router::RequestMessage::Logout(t),
+                    ctx.client_transaction_id.as_ref(),
+                ) {
                     Some(((command, extension), command_id)) => {
                         self.is_awaiting_response = true;
                         match self
@@ -660,7 +767,11 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                     None => Ok(()),
                 }
             }
-            (req, _, _) => match self.router.handle_request(&self.features, req) {
+            (req, _, _) => match self.router.handle_request(
+                &self.features,
+                req,
+                ctx.client_transaction_id.as_ref(),
+            ) {
                 Some(((command, extension), command_id)) => {
                     self.is_awaiting_response = true;
                     match self
@@ -687,6 +798,7 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                         self.host,
                         response.response_msg()
                     );
+                    self.status.set_error(response.response_msg());
                 }
                 let transaction_id = match &response.transaction_id.client_transaction_id {
                     Some(i) => i,
@@ -699,17 +811,9 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                     }
                 };
                 let is_closing = response.is_closing();
-                let transaction_id = match uuid::Uuid::parse_str(transaction_id) {
-                    Ok(i) => i,
-                    Err(e) => {
-                        error!(
-                            "Received response with invalid transaction UUID from {} ({}): {}",
-                            self.server_id, self.host, e
-                        );
-                        return Err(());
-                    }
-                };
+                let transaction_id = transaction_id.clone();
                 self.router.handle_response(&transaction_id, *response);
+                self.status.set_pending_commands(self.router.pending());
                 Ok(is_closing)
             }
             proto::EPPMessageType::Greeting(greeting) => {
@@ -719,6 +823,7 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                         greeting.server_id, self.host
                     );
                 }
+                self.status.update(|s| s.last_greeting = Some(Utc::now()));
                 Ok(false)
             }
             o => {
@@ -742,6 +847,7 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                 Ok(m) => m,
                 Err(_) => {
                     info!("Restarting connection...");
+                    self.status.set_error("Failed to receive greeting");
                     self._close(sock).await;
                     return Err(false);
                 }
@@ -750,11 +856,16 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
 
         if let proto::EPPMessageType::Greeting(greeting) = msg.message {
             self.server_id = greeting.server_id.clone();
+            self.status.update(|s| {
+                s.server_id = Some(greeting.server_id.clone());
+                s.last_greeting = Some(Utc::now());
+            });
             info!("Connection open with: {} ({})", self.server_id, self.host);
             match self._process_greeting(greeting).await {
                 Ok(_) => {}
                 Err(_) => {
                     info!("Will not attempt to reopen connection");
+                    self.status.set_error("Incompatible server greeting");
                     self._close(sock).await;
                     return Err(true);
                 }
@@ -767,6 +878,7 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                 }),
                 Err(_) => {
                     info!("Restarting connection...");
+                    self.status.set_error("Login failed");
                     self._close(sock).await;
                     Err(false)
                 }
@@ -844,6 +956,18 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
         self.features.nominet_release = greeting
             .service_menu
             .supports_ext("http://www.nominet.org.uk/epp/xml/std-release-1.0");
+        self.features.nominet_unrenew = greeting
+            .service_menu
+            .supports_ext("http://www.nominet.org.uk/epp/xml/std-unrenew-1.0");
+        self.features.nominet_list = greeting
+            .service_menu
+            .supports_ext("http://www.nominet.org.uk/epp/xml/std-list-1.0");
+        self.features.nominet_fork = greeting
+            .service_menu
+            .supports_ext("http://www.nominet.org.uk/epp/xml/std-fork-1.0");
+        self.features.nominet_abuse_feed = greeting
+            .service_menu
+            .supports_ext("http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0");
         self.features.switch_balance = greeting
             .service_menu
             .supports_ext("https://www.nic.ch/epp/balance-1.0");
@@ -868,6 +992,9 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
         self.features.nsset_supported = greeting
             .service_menu
             .supports("urn:ietf:params:xml:ns:nsset-1.2");
+        self.features.keyset_supported = greeting
+            .service_menu
+            .supports("urn:ietf:params:xml:ns:keyset-1.3");
         self.features.fee_supported = greeting
             .service_menu
             .supports_ext("urn:ietf:params:xml:ns:epp:fee-1.0");
@@ -973,12 +1100,16 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
         self.features.keysys_supported = greeting
             .service_menu
             .supports_ext("http://www.key-systems.net/epp/keysys-1.0");
+        self.features.allocation_token_supported = greeting
+            .service_menu
+            .supports_ext("urn:ietf:params:xml:ns:allocationToken-1.0");
 
         if !(self.features.contact_supported
             | self.features.domain_supported
             | self.features.host_supported
             | self.features.nominet_tag_list
-            | self.features.nsset_supported)
+            | self.features.nsset_supported
+            | self.features.keyset_supported)
         {
             error!("No common supported objects with {}", greeting.server_id);
             return Err(());
@@ -1032,6 +1163,18 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
             if self.features.nominet_release {
                 ext_objects.push("http://www.nominet.org.uk/epp/xml/std-release-1.0".to_string())
             }
+            if self.features.nominet_unrenew {
+                ext_objects.push("http://www.nominet.org.uk/epp/xml/std-unrenew-1.0".to_string())
+            }
+            if self.features.nominet_list {
+                ext_objects.push("http://www.nominet.org.uk/epp/xml/std-list-1.0".to_string())
+            }
+            if self.features.nominet_fork {
+                ext_objects.push("http://www.nominet.org.uk/epp/xml/std-fork-1.0".to_string())
+            }
+            if self.features.nominet_abuse_feed {
+                ext_objects.push("http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0".to_string())
+            }
             if self.features.switch_balance {
                 ext_objects.push("https://www.nic.ch/epp/balance-1.0".to_string())
             }
@@ -1056,6 +1199,9 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
             if self.features.nsset_supported {
                 objects.push("urn:ietf:params:xml:ns:nsset-1.2".to_string())
             }
+            if self.features.keyset_supported {
+                objects.push("urn:ietf:params:xml:ns:keyset-1.3".to_string())
+            }
             if self.features.launch_supported {
                 ext_objects.push("urn:ietf:params:xml:ns:launch-1.0".to_string())
             }
@@ -1151,6 +1297,9 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
             if self.features.keysys_supported {
                 ext_objects.push("http://www.key-systems.net/epp/keysys-1.0".to_string())
             }
+            if self.features.allocation_token_supported {
+                ext_objects.push("urn:ietf:params:xml:ns:allocationToken-1.0".to_string())
+            }
             if self.features.nominet_tag_list {
                 let metrics_registry = self.metrics_registry.subordinate("tag_list");
                 let router = outer_router::Router::new(&metrics_registry);
@@ -1161,9 +1310,22 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                     nominet_tag_list_subordinate: true,
                     log_storage: self.log_storage.clone(),
                     metrics_registry,
+                    status: Default::default(),
                     new_password: None,
+                    password_rotation: self
+                        .password_rotation
+                        .as_ref()
+                        .map(super::login_sec::PasswordRotation::follower),
+                    password_rotation_due: false,
                     pipelining: self.pipelining,
                     keepalive: self.keepalive,
+                    queue_conf: self.queue_conf,
+                    timeouts: self.timeouts,
+                    reconnect: self.reconnect,
+                    backoff: super::reconnect::Backoff::new(
+                        self.timeouts.reconnect_delay,
+                        self.reconnect,
+                    ),
                     features: ServerFeatures {
                         errata: self.features.errata.clone(),
                         ..Default::default()
@@ -1181,23 +1343,71 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
             }
         }
 
-        if let Some(new_password) = &self.new_password {
-            let new_password = new_password.clone();
+        self.status.update(|s| {
+            s.objects = objects.clone();
+            s.extensions = ext_objects.clone();
+        });
+
+        // Another session of this registry may have changed the password since we last logged in
+        if let Some(password) = self
+            .password_rotation
+            .as_ref()
+            .and_then(|r| r.current())
+        {
+            self.password = password;
+        }
+
+        let (new_password, rotating) = match (&self.new_password, &self.password_rotation) {
+            (Some(new_password), _) => (Some(new_password.clone()), false),
+            (None, Some(rotation)) if self.password_rotation_due => {
+                let new_password = rotation.generate(if self.features.login_sec_supported {
+                    usize::MAX
+                } else {
+                    super::login_sec::MAX_BASE_PASSWORD_LENGTH
+                });
+                // Only set a password that can't be lost if we stop before storing it as current
+                match rotation.prepare(&new_password).await {
+                    Ok(()) => (Some(new_password), true),
+                    Err(e) => {
+                        error!(
+                            "Failed to store pending password for {} ({}), not changing it: {}",
+                            self.server_id, self.host, e
+                        );
+                        self.status
+                            .set_error(format!("Failed to store pending password: {}", e));
+                        self.metrics_registry.password_rotated(false);
+                        (None, false)
+                    }
+                }
+            }
+            _ => (None, false),
+        };
+        if let Some(new_password) = new_password {
             match self
                 ._try_login(
                     self.password.clone(),
-                    Some(new_password),
+                    Some(new_password.clone()),
                     objects.clone(),
                     ext_objects.clone(),
                     sock,
                 )
                 .await
             {
-                Ok(r) => return Ok(r),
+                Ok(r) => {
+                    self.password = new_password;
+                    self.new_password = None;
+                    if rotating {
+                        self._password_rotated().await;
+                    }
+                    return Ok(r);
+                }
                 Err(e) => {
                     if e {
                         return Err(());
                     }
+                    if rotating {
+                        self._password_rejected().await;
+                    }
                 }
             }
         }
@@ -1210,6 +1420,56 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
         }
     }
 
+    /// Stores a password set by rotation, so that it isn't lost on restart
+    async fn _password_rotated(&mut self) {
+        self.password_rotation_due = false;
+        let now = Utc::now();
+        self.status.update(|s| {
+            s.password_rotation_due = false;
+            s.password_rotated = Some(now);
+        });
+        let rotation = match &self.password_rotation {
+            Some(r) => r,
+            None => return,
+        };
+        info!(
+            "Changed password on {} ({}) ahead of expiry",
+            self.server_id, self.host
+        );
+        match rotation.rotated(&self.password).await {
+            Ok(()) => self.metrics_registry.password_rotated(true),
+            Err(e) => {
+                error!(
+                    "Failed to store new password for {} ({}), it is only held in memory and as \
+                     pending: {}",
+                    self.server_id, self.host, e
+                );
+                self.status
+                    .set_error(format!("Failed to store new password: {}", e));
+                self.metrics_registry.password_rotated(false);
+            }
+        }
+    }
+
+    /// Drops a password set by rotation that the server refused, the old one still being in use
+    async fn _password_rejected(&self) {
+        let rotation = match &self.password_rotation {
+            Some(r) => r,
+            None => return,
+        };
+        warn!(
+            "{} ({}) refused the new password, keeping the old one",
+            self.server_id, self.host
+        );
+        self.metrics_registry.password_rotated(false);
+        if let Err(e) = rotation.rejected().await {
+            error!(
+                "Failed to drop pending password for {} ({}): {}",
+                self.server_id, self.host, e
+            );
+        }
+    }
+
     async fn _try_login(
         &mut self,
         password: String,
@@ -1321,6 +1581,26 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
                     }
                 }
             }
+            let events = login_sec_info
+                .map(|l| l.events.iter().map(login_sec_event).collect::<Vec<_>>())
+                .unwrap_or_default();
+            for event in &events {
+                self.metrics_registry.login_security_event(event);
+            }
+            if let Some(rotation) = &self.password_rotation {
+                self.password_rotation_due = rotation.is_due(&events, Utc::now());
+                if self.password_rotation_due {
+                    warn!(
+                        "Password for {} ({}) is expiring, it will be changed at the next login",
+                        self.server_id, self.host
+                    );
+                }
+            }
+            let password_rotation_due = self.password_rotation_due;
+            self.status.update(|s| {
+                s.login_security_events = events;
+                s.password_rotation_due = password_rotation_due;
+            });
             if !response.is_success() {
                 error!(
                     "Login to {} ({}) failed with error: {}",
@@ -1347,7 +1627,7 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
 
     async fn _send_command<
         W: std::marker::Unpin + tokio::io::AsyncWrite,
-        I: Into<Option<uuid::Uuid>>,
+        I: Into<Option<String>>,
         E: Into<Option<Vec<proto::EPPCommandExtensionType>>>,
     >(
         &self,
@@ -1355,17 +1635,17 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
         extension: E,
         sock: &mut W,
         message_id: I,
-    ) -> Result<uuid::Uuid, ()> {
+    ) -> Result<String, ()> {
         let message_id = match message_id.into() {
             Some(m) => m,
-            None => uuid::Uuid::new_v4(),
+            None => uuid::Uuid::new_v4().hyphenated().to_string(),
         };
         let command = proto::EPPCommand {
             command,
             extension: extension
                 .into()
                 .map(|e| proto::EPPCommandExtension { value: e }),
-            client_transaction_id: Some(message_id.hyphenated().to_string()),
+            client_transaction_id: Some(message_id.clone()),
         };
         let message = proto::EPPMessage {
             message: proto::EPPMessageType::Command(Box::new(command)),
@@ -1388,6 +1668,7 @@ impl<M: crate::metrics::Metrics<Subordinate = M> + 'static> EPPClient<M> {
 
     async fn _close(&mut self, sock: &mut super::epp_like::tls_client::TLSConnection) {
         self.router.drain();
+        self.status.set_pending_commands(0);
         sock.close().await
     }
 }
@@ -1,11 +1,13 @@
 //! EPP commands relating to nominet specific features
 
 use super::super::nominet::{
-    BillType, CancelData, DataQualityData, DataQualityStatus, DomainCreate, DomainFailData,
-    DomainInfo, DomainUpdate, HandshakeAcceptRequest, HandshakeRejectRequest, HandshakeResponse,
-    HostCancelData, LockRequest, LockResponse, Object, ProcessData, ProcessStage,
+    AbuseFeedData, BillType, CancelData, DataQualityData, DataQualityStatus, DomainCreate,
+    DomainFailData, DomainInfo, DomainUpdate, ForkRequest, ForkResponse, HandshakeAcceptRequest,
+    HandshakeRejectRequest, HandshakeResponse, HostCancelData, ListRequest, ListResponse,
+    ListType, LockRequest, LockResponse, Object, ProcessData, ProcessStage,
     RegistrantTransferData, RegistrarChangeData, RegistrationStatus, ReleaseData, ReleaseRequest,
-    ReleaseResponse, SuspendData, Tag, TagListRequest, TagListResponse,
+    ReleaseResponse, SuspendData, Tag, TagListRequest, TagListResponse, UnrenewRequest,
+    UnrenewResponse, UnrenewedDomain,
 };
 use super::super::{proto, Error, Response};
 use super::router::HandleReqReturn;
@@ -213,6 +215,24 @@ impl From<proto::nominet::EPPSuspendData> for SuspendData {
     }
 }
 
+impl From<proto::nominet::EPPAbuseFeedData> for AbuseFeedData {
+    fn from(from: proto::nominet::EPPAbuseFeedData) -> Self {
+        AbuseFeedData {
+            key: from.key,
+            activity: from.activity,
+            source: from.source,
+            hostname: from.hostname,
+            url: from.url,
+            date: from.date,
+            ip: from.ip,
+            nameserver: from.nameserver,
+            dns_admin: from.dns_admin,
+            target: from.target,
+            whole_domain: from.whole_domain,
+        }
+    }
+}
+
 impl From<proto::nominet::EPPDomainFailData> for DomainFailData {
     fn from(from: proto::nominet::EPPDomainFailData) -> Self {
         DomainFailData {
@@ -364,6 +384,133 @@ pub fn handle_release_response<M: crate::metrics::Metrics>(
     }
 }
 
+pub fn handle_unrenew(
+    client: &ServerFeatures,
+    req: &UnrenewRequest,
+) -> HandleReqReturn<UnrenewResponse> {
+    if !client.nominet_unrenew {
+        return Err(Err(Error::Unsupported));
+    }
+    if req.domains.is_empty() {
+        return Err(Err(Error::Err(
+            "at least one domain must be specified".to_string(),
+        )));
+    }
+    for domain in &req.domains {
+        super::domain::check_domain(domain)?;
+    }
+    let command = proto::nominet::EPPUnrenew {
+        domains: req.domains.clone(),
+    };
+    Ok((
+        proto::EPPCommandType::Update(Box::new(proto::EPPUpdate::NominetUnrenew(command))),
+        None,
+    ))
+}
+
+pub fn handle_unrenew_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<UnrenewResponse> {
+    match response.data {
+        Some(value) => Response::Ok(UnrenewResponse {
+            domains: std::iter::once(value.value)
+                .chain(value.others)
+                .map(|v| match v {
+                    proto::EPPResultDataValue::EPPDomainRenewResult(domain) => {
+                        Ok(UnrenewedDomain {
+                            name: domain.name,
+                            expiry_date: domain.expiry_date,
+                        })
+                    }
+                    _ => Err(Error::ServerInternal),
+                })
+                .collect::<Response<Vec<_>>>()?,
+        }),
+        None => Err(Error::ServerInternal),
+    }
+}
+
+pub fn handle_list(client: &ServerFeatures, req: &ListRequest) -> HandleReqReturn<ListResponse> {
+    if !client.nominet_list {
+        return Err(Err(Error::Unsupported));
+    }
+    if !(1..=12).contains(&req.month) || !(0..=9999).contains(&req.year) {
+        return Err(Err(Error::Err("invalid month to list".to_string())));
+    }
+    let month = format!("{:04}-{:02}", req.year, req.month);
+    let command = proto::nominet::EPPList {
+        date: match req.list_type {
+            ListType::Registration => proto::nominet::EPPListDate::Month(month),
+            ListType::Expiry => proto::nominet::EPPListDate::Expiry(month),
+        },
+    };
+    Ok((
+        proto::EPPCommandType::Info(proto::EPPInfo::NominetList(command)),
+        None,
+    ))
+}
+
+pub fn handle_list_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<ListResponse> {
+    match response.data {
+        Some(value) => match value.value {
+            proto::EPPResultDataValue::NominetListData(list) => Response::Ok(ListResponse {
+                domains: list.domain_names,
+            }),
+            _ => Err(Error::ServerInternal),
+        },
+        None => Response::Ok(ListResponse { domains: vec![] }),
+    }
+}
+
+pub fn handle_fork(client: &ServerFeatures, req: &ForkRequest) -> HandleReqReturn<ForkResponse> {
+    if !client.nominet_fork {
+        return Err(Err(Error::Unsupported));
+    }
+    super::contact::check_id(&req.contact_id)?;
+    if let Some(new_contact_id) = &req.new_contact_id {
+        super::contact::check_id(new_contact_id)?;
+    }
+    if req.domains.is_empty() {
+        return Err(Err(Error::Err(
+            "at least one domain must be specified".to_string(),
+        )));
+    }
+    for domain in &req.domains {
+        super::domain::check_domain(domain)?;
+    }
+    let command = proto::nominet::EPPFork {
+        contact_id: req.contact_id.clone(),
+        new_contact_id: req.new_contact_id.clone(),
+        domain_names: req.domains.clone(),
+    };
+    Ok((
+        proto::EPPCommandType::Update(Box::new(proto::EPPUpdate::NominetFork(command))),
+        None,
+    ))
+}
+
+pub fn handle_fork_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<ForkResponse> {
+    match response.data {
+        Some(value) => match value.value {
+            proto::EPPResultDataValue::EPPContactCreateResult(contact) => {
+                Response::Ok(ForkResponse {
+                    new_contact_id: Some(contact.id),
+                    creation_date: contact.creation_date,
+                })
+            }
+            _ => Err(Error::ServerInternal),
+        },
+        None => Response::Ok(ForkResponse {
+            new_contact_id: None,
+            creation_date: None,
+        }),
+    }
+}
+
 pub fn handle_tag_list(
     client: &ServerFeatures,
     _req: &TagListRequest,
@@ -483,3 +630,222 @@ pub fn handle_lock_response<M: crate::metrics::Metrics>(
         None => Response::Ok(LockResponse {}),
     }
 }
+
+#[cfg(test)]
+mod nominet_tests {
+    #[test]
+    fn unrenew_command() {
+        let features = super::ServerFeatures {
+            nominet_unrenew: true,
+            ..Default::default()
+        };
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_unrenew(
+            &features,
+            &super::UnrenewRequest {
+                domains: vec!["example-one.co.uk".to_string(), "example-two.co.uk".to_string()],
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let msg = super::proto::EPPMessage {
+            message: super::proto::EPPMessageType::Command(Box::new(super::proto::EPPCommand {
+                command,
+                extension: extension.map(|value| super::proto::EPPCommandExtension { value }),
+                client_transaction_id: None,
+            })),
+        };
+        let res = xml_serde::to_string(&msg).unwrap();
+        assert!(res.contains("xmlns:unrenew=\"http://www.nominet.org.uk/epp/xml/std-unrenew-1.0\""));
+        assert!(res.contains("<unrenew:domainName>example-one.co.uk</unrenew:domainName>"));
+        assert!(res.contains("<unrenew:domainName>example-two.co.uk</unrenew:domainName>"));
+    }
+
+    #[test]
+    fn unrenew_no_domains() {
+        let features = super::ServerFeatures {
+            nominet_unrenew: true,
+            ..Default::default()
+        };
+        let (sender, _) = futures::channel::oneshot::channel();
+        assert!(super::handle_unrenew(
+            &features,
+            &super::UnrenewRequest {
+                domains: vec![],
+                return_path: sender,
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn unrenew_response() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <domain:renData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:name>example-one.co.uk</domain:name>
+        <domain:exDate>2023-03-02T12:03:50Z</domain:exDate>
+      </domain:renData>
+      <domain:renData xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:name>example-two.co.uk</domain:name>
+        <domain:exDate>2024-07-15T09:21:04Z</domain:exDate>
+      </domain:renData>
+    </resData>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>123456</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_unrenew_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap();
+        assert_eq!(data.domains.len(), 2);
+        assert_eq!(data.domains[0].name, "example-one.co.uk");
+        assert_eq!(
+            data.domains[0].expiry_date.unwrap().to_rfc3339(),
+            "2023-03-02T12:03:50+00:00"
+        );
+        assert_eq!(data.domains[1].name, "example-two.co.uk");
+        assert_eq!(
+            data.domains[1].expiry_date.unwrap().to_rfc3339(),
+            "2024-07-15T09:21:04+00:00"
+        );
+    }
+
+    #[test]
+    fn list_command() {
+        let features = super::ServerFeatures {
+            nominet_list: true,
+            ..Default::default()
+        };
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_list(
+            &features,
+            &super::ListRequest {
+                list_type: super::ListType::Expiry,
+                year: 2007,
+                month: 9,
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let msg = super::proto::EPPMessage {
+            message: super::proto::EPPMessageType::Command(Box::new(super::proto::EPPCommand {
+                command,
+                extension: extension.map(|value| super::proto::EPPCommandExtension { value }),
+                client_transaction_id: None,
+            })),
+        };
+        let res = xml_serde::to_string(&msg).unwrap();
+        assert!(res.contains("xmlns:list=\"http://www.nominet.org.uk/epp/xml/std-list-1.0\""));
+        assert!(res.contains("<list:expiry>2007-09</list:expiry>"));
+    }
+
+    #[test]
+    fn list_response() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <list:listData xmlns:list="http://www.nominet.org.uk/epp/xml/std-list-1.0" noDomains="2">
+        <list:domainName>epp-example1.co.uk</list:domainName>
+        <list:domainName>epp-example2.co.uk</list:domainName>
+      </list:listData>
+    </resData>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>123456</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_list_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap();
+        assert_eq!(data.domains, vec!["epp-example1.co.uk", "epp-example2.co.uk"]);
+    }
+
+    #[test]
+    fn fork_command() {
+        let features = super::ServerFeatures {
+            nominet_fork: true,
+            ..Default::default()
+        };
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_fork(
+            &features,
+            &super::ForkRequest {
+                contact_id: "CONT-1".to_string(),
+                new_contact_id: Some("CONT-2".to_string()),
+                domains: vec!["epp-example1.co.uk".to_string()],
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let msg = super::proto::EPPMessage {
+            message: super::proto::EPPMessageType::Command(Box::new(super::proto::EPPCommand {
+                command,
+                extension: extension.map(|value| super::proto::EPPCommandExtension { value }),
+                client_transaction_id: None,
+            })),
+        };
+        let res = xml_serde::to_string(&msg).unwrap();
+        assert!(res.contains("xmlns:fork=\"http://www.nominet.org.uk/epp/xml/std-fork-1.0\""));
+        assert!(res.contains("<fork:contactId>CONT-1</fork:contactId>"));
+        assert!(res.contains("<fork:newContactId>CONT-2</fork:newContactId>"));
+        assert!(res.contains("<fork:domainName>epp-example1.co.uk</fork:domainName>"));
+    }
+
+    #[test]
+    fn fork_response() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <contact:creData xmlns:contact="urn:ietf:params:xml:ns:contact-1.0">
+        <contact:id>CONT-2</contact:id>
+        <contact:crDate>2009-04-08T11:38:23Z</contact:crDate>
+      </contact:creData>
+    </resData>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>123456</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_fork_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap();
+        assert_eq!(data.new_contact_id.unwrap(), "CONT-2");
+        assert_eq!(
+            data.creation_date.unwrap().to_rfc3339(),
+            "2009-04-08T11:38:23+00:00"
+        );
+    }
+}
@@ -0,0 +1,596 @@
+//! EPP commands relating to FRED NSSET (nameserver set) objects
+
+use super::super::nsset::{
+    CheckRequest, CheckResponse, CreateRequest, CreateResponse, DeleteRequest, DeleteResponse,
+    InfoRequest, InfoResponse, Nameserver, TransferRequest, TransferResponse, UpdateRequest,
+    UpdateResponse,
+};
+use super::super::fred::{
+    IdleDeleteData, NSSetTestData, NSSetTestResult, ObjectType, TransferData,
+};
+use super::super::{proto, Error, Response};
+use super::router::HandleReqReturn;
+use super::ServerFeatures;
+
+impl From<proto::nsset::EPPNSSetInfoData> for InfoResponse {
+    fn from(nsset_info: proto::nsset::EPPNSSetInfoData) -> Self {
+        InfoResponse {
+            id: nsset_info.id,
+            registry_id: nsset_info.registry_id.unwrap_or_default(),
+            statuses: nsset_info
+                .statuses
+                .into_iter()
+                .map(|s| s.status)
+                .collect(),
+            client_id: nsset_info.client_id,
+            client_created_id: nsset_info.client_created_id,
+            creation_date: nsset_info.creation_date,
+            last_updated_client: nsset_info.last_updated_client,
+            last_updated_date: nsset_info.last_updated_date,
+            last_transfer_date: nsset_info.last_transfer_date,
+            auth_info: nsset_info.auth_info,
+            nameservers: nsset_info
+                .nameservers
+                .into_iter()
+                .map(|n| Nameserver {
+                    name: n.name,
+                    addresses: n.addresses,
+                })
+                .collect(),
+            tech_contacts: nsset_info.tech_contacts,
+            report_level: nsset_info.report_level,
+        }
+    }
+}
+
+impl From<proto::nsset::EPPNSSetTransferData> for TransferData {
+    fn from(from: proto::nsset::EPPNSSetTransferData) -> Self {
+        TransferData {
+            object_type: ObjectType::NSSet,
+            id: from.id,
+            transfer_date: from.transfer_date,
+            client_id: from.client_id,
+        }
+    }
+}
+
+impl From<proto::nsset::EPPNSSetDeleteData> for IdleDeleteData {
+    fn from(from: proto::nsset::EPPNSSetDeleteData) -> Self {
+        IdleDeleteData {
+            object_type: ObjectType::NSSet,
+            id: from.id,
+        }
+    }
+}
+
+impl From<proto::nsset::EPPNSSetTestData> for NSSetTestData {
+    fn from(from: proto::nsset::EPPNSSetTestData) -> Self {
+        NSSetTestData {
+            id: from.id,
+            names: from.names,
+            results: from
+                .results
+                .into_iter()
+                .map(|r| NSSetTestResult {
+                    test_name: r.test_name,
+                    passed: r.status,
+                    note: r.note,
+                })
+                .collect(),
+        }
+    }
+}
+
+pub(crate) fn check_id<T>(id: &str) -> Result<(), Response<T>> {
+    if let 1..=30 = id.len() {
+        Ok(())
+    } else {
+        Err(Err(Error::Err(
+            "NSSET id has a min length of 1 and a max length of 30".to_string(),
+        )))
+    }
+}
+
+fn check_report_level<T>(report_level: Option<u8>) -> Result<(), Response<T>> {
+    match report_level {
+        Some(0..=10) | None => Ok(()),
+        Some(_) => Err(Err(Error::Err(
+            "report level must be between 0 and 10".to_string(),
+        ))),
+    }
+}
+
+fn map_nameservers<T>(
+    nameservers: &[Nameserver],
+) -> Result<Vec<proto::nsset::EPPNSSetNameserverParam>, Response<T>> {
+    nameservers
+        .iter()
+        .map(|n| {
+            if n.name.is_empty() {
+                return Err(Err(Error::Err(
+                    "nameserver name has a min length of 1".to_string(),
+                )));
+            }
+            Ok(proto::nsset::EPPNSSetNameserverParam {
+                name: n.name.clone(),
+                addresses: n.addresses.clone(),
+            })
+        })
+        .collect()
+}
+
+pub fn handle_check(client: &ServerFeatures, req: &CheckRequest) -> HandleReqReturn<CheckResponse> {
+    if !client.nsset_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    check_id(&req.id)?;
+    let command = proto::EPPCheck::NSSet(proto::nsset::EPPNSSetCheck { id: req.id.clone() });
+    Ok((proto::EPPCommandType::Check(command), None))
+}
+
+pub fn handle_check_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<CheckResponse> {
+    match response.data {
+        Some(value) => match value.value {
+            proto::EPPResultDataValue::EPPNSSetCheckResult(nsset_check) => {
+                if let Some(nsset_check) = nsset_check.data.first() {
+                    Response::Ok(CheckResponse {
+                        avail: nsset_check.id.available,
+                        reason: nsset_check.reason.to_owned(),
+                    })
+                } else {
+                    Err(Error::ServerInternal)
+                }
+            }
+            _ => Err(Error::ServerInternal),
+        },
+        None => Err(Error::ServerInternal),
+    }
+}
+
+pub fn handle_info(client: &ServerFeatures, req: &InfoRequest) -> HandleReqReturn<InfoResponse> {
+    if !client.nsset_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    check_id(&req.id)?;
+    let command = proto::EPPInfo::NSSet(proto::nsset::EPPNSSetCheck { id: req.id.clone() });
+    Ok((proto::EPPCommandType::Info(command), None))
+}
+
+pub fn handle_info_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<InfoResponse> {
+    match response.data {
+        Some(value) => match value.value {
+            proto::EPPResultDataValue::EPPNSSetInfoResult(nsset_info) => {
+                Response::Ok((*nsset_info).into())
+            }
+            _ => Err(Error::ServerInternal),
+        },
+        None => Err(Error::ServerInternal),
+    }
+}
+
+pub fn handle_create(
+    client: &ServerFeatures,
+    req: &CreateRequest,
+) -> HandleReqReturn<CreateResponse> {
+    if !client.nsset_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    check_id(&req.id)?;
+    check_report_level(req.report_level)?;
+    if req.nameservers.len() < 2 {
+        return Err(Err(Error::Err(
+            "at least two nameservers must be specified".to_string(),
+        )));
+    }
+    if req.tech_contacts.is_empty() {
+        return Err(Err(Error::Err(
+            "at least one technical contact must be specified".to_string(),
+        )));
+    }
+    for tech in &req.tech_contacts {
+        super::contact::check_id(tech)?;
+    }
+    let command = proto::EPPCreate::NSSet(proto::nsset::EPPNSSetCreate {
+        id: req.id.clone(),
+        nameservers: map_nameservers(&req.nameservers)?,
+        tech_contacts: req.tech_contacts.clone(),
+        auth_info: req.auth_info.clone(),
+        report_level: req.report_level,
+    });
+    Ok((proto::EPPCommandType::Create(command), None))
+}
+
+pub fn handle_create_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<CreateResponse> {
+    let pending = response.is_pending();
+    let transaction_id = response
+        .transaction_id
+        .server_transaction_id
+        .unwrap_or_default();
+    match response.data {
+        Some(value) => match value.value {
+            proto::EPPResultDataValue::EPPNSSetCreateResult(nsset_create) => {
+                Response::Ok(CreateResponse {
+                    id: nsset_create.id,
+                    pending,
+                    transaction_id,
+                    creation_date: nsset_create.creation_date,
+                })
+            }
+            _ => Err(Error::ServerInternal),
+        },
+        None => Response::Ok(CreateResponse {
+            id: "".to_string(),
+            pending,
+            transaction_id,
+            creation_date: None,
+        }),
+    }
+}
+
+pub fn handle_delete(
+    client: &ServerFeatures,
+    req: &DeleteRequest,
+) -> HandleReqReturn<DeleteResponse> {
+    if !client.nsset_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    check_id(&req.id)?;
+    let command = proto::EPPDelete::NSSet(proto::nsset::EPPNSSetCheck { id: req.id.clone() });
+    Ok((proto::EPPCommandType::Delete(command), None))
+}
+
+pub fn handle_delete_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<DeleteResponse> {
+    Response::Ok(DeleteResponse {
+        pending: response.is_pending(),
+        transaction_id: response
+            .transaction_id
+            .server_transaction_id
+            .unwrap_or_default(),
+    })
+}
+
+pub fn handle_update(
+    client: &ServerFeatures,
+    req: &UpdateRequest,
+) -> HandleReqReturn<UpdateResponse> {
+    if !client.nsset_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    check_id(&req.id)?;
+    check_report_level(req.new_report_level)?;
+    if req.add_nameservers.is_empty()
+        && req.remove_nameservers.is_empty()
+        && req.add_tech_contacts.is_empty()
+        && req.remove_tech_contacts.is_empty()
+        && req.new_auth_info.is_none()
+        && req.new_report_level.is_none()
+    {
+        return Err(Err(Error::Err(
+            "at least one operation must be specified".to_string(),
+        )));
+    }
+    for tech in req.add_tech_contacts.iter().chain(req.remove_tech_contacts.iter()) {
+        super::contact::check_id(tech)?;
+    }
+
+    let add_nameservers = map_nameservers(&req.add_nameservers)?;
+    let command = proto::EPPUpdate::NSSet(proto::nsset::EPPNSSetUpdate {
+        id: req.id.clone(),
+        add: if add_nameservers.is_empty() && req.add_tech_contacts.is_empty() {
+            None
+        } else {
+            Some(proto::nsset::EPPNSSetUpdateAdd {
+                nameservers: add_nameservers,
+                tech_contacts: req.add_tech_contacts.clone(),
+            })
+        },
+        remove: if req.remove_nameservers.is_empty() && req.remove_tech_contacts.is_empty() {
+            None
+        } else {
+            Some(proto::nsset::EPPNSSetUpdateRemove {
+                nameservers: req.remove_nameservers.clone(),
+                tech_contacts: req.remove_tech_contacts.clone(),
+            })
+        },
+        change: if req.new_auth_info.is_none() && req.new_report_level.is_none() {
+            None
+        } else {
+            Some(proto::nsset::EPPNSSetUpdateChange {
+                auth_info: req.new_auth_info.clone(),
+                report_level: req.new_report_level,
+            })
+        },
+    });
+    Ok((proto::EPPCommandType::Update(Box::new(command)), None))
+}
+
+pub fn handle_update_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<UpdateResponse> {
+    Response::Ok(UpdateResponse {
+        pending: response.is_pending(),
+        transaction_id: response
+            .transaction_id
+            .server_transaction_id
+            .unwrap_or_default(),
+    })
+}
+
+pub fn handle_transfer(
+    client: &ServerFeatures,
+    req: &TransferRequest,
+) -> HandleReqReturn<TransferResponse> {
+    if !client.nsset_supported {
+        return Err(Err(Error::Unsupported));
+    }
+    check_id(&req.id)?;
+    if req.auth_info.is_empty() {
+        return Err(Err(Error::Err(
+            "auth info has a min length of 1".to_string(),
+        )));
+    }
+    let command = proto::EPPTransfer {
+        operation: proto::EPPTransferOperation::Request,
+        command: proto::EPPTransferCommand::NSSetRequest(proto::nsset::EPPNSSetTransfer {
+            id: req.id.clone(),
+            auth_info: req.auth_info.clone(),
+        }),
+    };
+    Ok((proto::EPPCommandType::Transfer(command), None))
+}
+
+pub fn handle_transfer_response<M: crate::metrics::Metrics>(
+    response: proto::EPPResponse, _metrics: &M
+) -> Response<TransferResponse> {
+    Response::Ok(TransferResponse {
+        pending: response.is_pending(),
+        transaction_id: response
+            .transaction_id
+            .server_transaction_id
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod nsset_tests {
+    fn features() -> super::ServerFeatures {
+        super::ServerFeatures {
+            nsset_supported: true,
+            ..Default::default()
+        }
+    }
+
+    fn command_xml(
+        command: super::proto::EPPCommandType,
+        extension: Option<Vec<super::proto::EPPCommandExtensionType>>,
+    ) -> String {
+        let msg = super::proto::EPPMessage {
+            message: super::proto::EPPMessageType::Command(Box::new(super::proto::EPPCommand {
+                command,
+                extension: extension.map(|value| super::proto::EPPCommandExtension { value }),
+                client_transaction_id: None,
+            })),
+        };
+        xml_serde::to_string(&msg).unwrap()
+    }
+
+    fn nameserver(name: &str, addresses: &[&str]) -> super::Nameserver {
+        super::Nameserver {
+            name: name.to_string(),
+            addresses: addresses.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn create_command() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_create(
+            &features(),
+            &super::CreateRequest {
+                id: "NSSID-EXAMPLE".to_string(),
+                nameservers: vec![
+                    nameserver("ns1.example.cz", &["192.0.2.1", "2001:db8::1"]),
+                    nameserver("ns.example.net", &[]),
+                ],
+                tech_contacts: vec!["CID-TECH".to_string()],
+                auth_info: Some("gnsAuth".to_string()),
+                report_level: Some(1),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let res = command_xml(command, extension);
+        assert!(res.contains("xmlns:nsset=\"urn:ietf:params:xml:ns:nsset-1.2\""));
+        assert!(res.contains("<nsset:create"));
+        assert!(res.contains("<nsset:id>NSSID-EXAMPLE</nsset:id>"));
+        assert!(res.contains("<nsset:name>ns1.example.cz</nsset:name>"));
+        assert!(res.contains("<nsset:addr>192.0.2.1</nsset:addr>"));
+        assert!(res.contains("<nsset:addr>2001:db8::1</nsset:addr>"));
+        assert!(res.contains("<nsset:name>ns.example.net</nsset:name>"));
+        assert!(res.contains("<nsset:tech>CID-TECH</nsset:tech>"));
+        assert!(res.contains("<nsset:authInfo>gnsAuth</nsset:authInfo>"));
+        assert!(res.contains("<nsset:reportlevel>1</nsset:reportlevel>"));
+    }
+
+    #[test]
+    fn create_one_nameserver() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        assert!(super::handle_create(
+            &features(),
+            &super::CreateRequest {
+                id: "NSSID-EXAMPLE".to_string(),
+                nameservers: vec![nameserver("ns1.example.cz", &[])],
+                tech_contacts: vec!["CID-TECH".to_string()],
+                auth_info: None,
+                report_level: None,
+                return_path: sender,
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn create_report_level_out_of_range() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        assert!(super::handle_create(
+            &features(),
+            &super::CreateRequest {
+                id: "NSSID-EXAMPLE".to_string(),
+                nameservers: vec![
+                    nameserver("ns1.example.cz", &[]),
+                    nameserver("ns.example.net", &[]),
+                ],
+                tech_contacts: vec!["CID-TECH".to_string()],
+                auth_info: None,
+                report_level: Some(11),
+                return_path: sender,
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn update_command() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_update(
+            &features(),
+            &super::UpdateRequest {
+                id: "NSSID-EXAMPLE".to_string(),
+                add_nameservers: vec![nameserver("ns2.example.cz", &["192.0.2.2"])],
+                remove_nameservers: vec!["ns.example.net".to_string()],
+                add_tech_contacts: vec!["CID-NEW".to_string()],
+                remove_tech_contacts: vec![],
+                new_auth_info: None,
+                new_report_level: Some(5),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let res = command_xml(command, extension);
+        assert!(res.contains("<nsset:update"));
+        assert!(res.contains("<nsset:id>NSSID-EXAMPLE</nsset:id>"));
+        assert!(res.contains("<nsset:add>"));
+        assert!(res.contains("<nsset:name>ns2.example.cz</nsset:name>"));
+        assert!(res.contains("<nsset:addr>192.0.2.2</nsset:addr>"));
+        assert!(res.contains("<nsset:tech>CID-NEW</nsset:tech>"));
+        assert!(res.contains("<nsset:rem>"));
+        assert!(res.contains("<nsset:name>ns.example.net</nsset:name>"));
+        assert!(res.contains("<nsset:chg>"));
+        assert!(res.contains("<nsset:reportlevel>5</nsset:reportlevel>"));
+        assert!(!res.contains("<nsset:authInfo>"));
+    }
+
+    #[test]
+    fn update_no_changes() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        assert!(super::handle_update(
+            &features(),
+            &super::UpdateRequest {
+                id: "NSSID-EXAMPLE".to_string(),
+                add_nameservers: vec![],
+                remove_nameservers: vec![],
+                add_tech_contacts: vec![],
+                remove_tech_contacts: vec![],
+                new_auth_info: None,
+                new_report_level: None,
+                return_path: sender,
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn transfer_command() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        let (command, extension) = super::handle_transfer(
+            &features(),
+            &super::TransferRequest {
+                id: "NSSID-EXAMPLE".to_string(),
+                auth_info: "gnsAuth".to_string(),
+                return_path: sender,
+            },
+        )
+        .unwrap();
+        let res = command_xml(command, extension);
+        assert!(res.contains("op=\"request\""));
+        assert!(res.contains("<nsset:transfer"));
+        assert!(res.contains("<nsset:id>NSSID-EXAMPLE</nsset:id>"));
+        assert!(res.contains("<nsset:authInfo>gnsAuth</nsset:authInfo>"));
+    }
+
+    #[test]
+    fn transfer_unsupported() {
+        let (sender, _) = futures::channel::oneshot::channel();
+        assert!(super::handle_transfer(
+            &Default::default(),
+            &super::TransferRequest {
+                id: "NSSID-EXAMPLE".to_string(),
+                auth_info: "gnsAuth".to_string(),
+                return_path: sender,
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn info_response() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <nsset:infData xmlns:nsset="urn:ietf:params:xml:ns:nsset-1.2">
+        <nsset:id>NSSID-EXAMPLE</nsset:id>
+        <nsset:roid>N0001234-CZ</nsset:roid>
+        <nsset:status s="ok"/>
+        <nsset:clID>REG-EXAMPLE</nsset:clID>
+        <nsset:crID>REG-EXAMPLE</nsset:crID>
+        <nsset:crDate>2017-07-11T13:28:48+02:00</nsset:crDate>
+        <nsset:authInfo>gnsAuth</nsset:authInfo>
+        <nsset:ns>
+          <nsset:name>ns1.example.cz</nsset:name>
+          <nsset:addr>192.0.2.1</nsset:addr>
+          <nsset:addr>2001:db8::1</nsset:addr>
+        </nsset:ns>
+        <nsset:ns>
+          <nsset:name>ns.example.net</nsset:name>
+        </nsset:ns>
+        <nsset:tech>CID-TECH</nsset:tech>
+        <nsset:reportlevel>0</nsset:reportlevel>
+      </nsset:infData>
+    </resData>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>ReqID-0000140900</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_info_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap();
+        assert_eq!(data.id, "NSSID-EXAMPLE");
+        assert_eq!(data.statuses, vec!["ok"]);
+        assert_eq!(data.auth_info.unwrap(), "gnsAuth");
+        assert_eq!(data.nameservers.len(), 2);
+        assert_eq!(data.nameservers[0].name, "ns1.example.cz");
+        assert_eq!(data.nameservers[0].addresses, vec!["192.0.2.1", "2001:db8::1"]);
+        assert!(data.nameservers[1].addresses.is_empty());
+        assert_eq!(data.tech_contacts, vec!["CID-TECH"]);
+        assert_eq!(data.report_level, Some(0));
+    }
+}
@@ -4,6 +4,7 @@ use std::convert::TryInto;
 
 use chrono::prelude::*;
 
+use super::super::fred::LowCreditData;
 use super::super::poll::{
     ChangeCaseId, ChangeCaseIdType, ChangeData, ChangeOperation, ChangeOperationType, ChangeState,
     PollAckRequest, PollAckResponse, PollData, PollRequest, PollResponse,
@@ -80,6 +81,44 @@ fn change_data_from_response(
     }
 }
 
+/// Extracts the `<resData>` and `<extension>` elements from a raw response, as sent by the server
+fn raw_poll_data(raw: &str) -> Result<String, quick_xml::Error> {
+    let mut reader = quick_xml::reader::NsReader::from_str(raw);
+    let mut out = String::new();
+    loop {
+        let start = reader.buffer_position();
+        let (ns, event) = reader.read_resolved_event()?;
+        let is_epp = matches!(
+            ns,
+            quick_xml::name::ResolveResult::Bound(quick_xml::name::Namespace(
+                b"urn:ietf:params:xml:ns:epp-1.0"
+            ))
+        );
+        match event {
+            quick_xml::events::Event::Start(e)
+                if is_epp && matches!(e.local_name().as_ref(), b"resData" | b"extension") =>
+            {
+                let e = e.to_end().into_owned();
+                reader.read_to_end(e.name())?;
+                out.push_str(&raw[start..reader.buffer_position()]);
+            }
+            quick_xml::events::Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(out)
+}
+
+fn raw_poll_response(raw: &Option<String>) -> Result<PollData, Error> {
+    match raw {
+        Some(raw) => raw_poll_data(raw).map(PollData::Raw).map_err(|err| {
+            error!("Unable to extract raw poll message: {}", err);
+            Error::ServerInternal
+        }),
+        None => Err(Error::ServerInternal),
+    }
+}
+
 pub fn handle_poll(
     _client: &ServerFeatures,
     _req: &PollRequest,
@@ -104,6 +143,10 @@ pub fn handle_poll_response<M: crate::metrics::Metrics>(
                     enqueue_time: value.enqueue_date.unwrap_or_else(Utc::now),
                     message: value.message.unwrap_or_default(),
                     data: match response.data {
+                        Some(ref value) if matches!(value.value, proto::EPPResultDataValue::Unknown) => {
+                            metrics.poll_received(value.value.name());
+                            raw_poll_response(&response.raw)?
+                        }
                         Some(value) => {
                             metrics.poll_received(value.value.name());
                             match value.value {
@@ -211,6 +254,31 @@ pub fn handle_poll_response<M: crate::metrics::Metrics>(
                                         change_data: change_data_from_response(&response.extension)?,
                                     }
                                 }
+                                proto::EPPResultDataValue::NominetAbuseFeedData(abuse_data) => {
+                                    PollData::NominetAbuseFeedData(abuse_data.into())
+                                }
+                                proto::EPPResultDataValue::EPPNSSetTransferData(trn_data) => {
+                                    PollData::FredTransferData(trn_data.into())
+                                }
+                                proto::EPPResultDataValue::EPPKeySetTransferData(trn_data) => {
+                                    PollData::FredTransferData(trn_data.into())
+                                }
+                                proto::EPPResultDataValue::EPPNSSetDeleteData(del_data) => {
+                                    PollData::FredIdleDeleteData(del_data.into())
+                                }
+                                proto::EPPResultDataValue::EPPKeySetDeleteData(del_data) => {
+                                    PollData::FredIdleDeleteData(del_data.into())
+                                }
+                                proto::EPPResultDataValue::EPPNSSetTestData(test_data) => {
+                                    PollData::FredNSSetTestData(test_data.into())
+                                }
+                                proto::EPPResultDataValue::FredLowCreditData(credit_data) => {
+                                    PollData::FredLowCreditData(LowCreditData {
+                                        zone: credit_data.zone,
+                                        limit: credit_data.limit.credit,
+                                        credit: credit_data.credit.credit,
+                                    })
+                                }
                                 proto::EPPResultDataValue::VerisignLowBalanceData(bal_data) => {
                                     PollData::VerisignLowBalanceData(bal_data.try_into()?)
                                 }
@@ -226,7 +294,7 @@ pub fn handle_poll_response<M: crate::metrics::Metrics>(
                                 proto::EPPResultDataValue::EPPMaintenanceInfo02(
                                     proto::maintenance::EPPMaintenanceInfoData02::Maintenance(item),
                                 ) => PollData::MaintenanceData(item.into()),
-                                _ => return Err(Error::ServerInternal),
+                                _ => raw_poll_response(&response.raw)?,
                             }
                         },
                         None => PollData::None,
@@ -1606,4 +1674,360 @@ mod poll_tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn unknown_message() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1301">
+      <msg>Command completed successfully; ack to dequeue</msg>
+    </result>
+    <msgQ count="4" id="12345">
+      <qDate>2021-03-01T10:00:00.0Z</qDate>
+      <msg>Something happened</msg>
+    </msgQ>
+    <resData>
+      <example:notifyData xmlns:example="urn:example:params:xml:ns:notify-1.0">
+        <example:name>example.com</example:name>
+      </example:notifyData>
+    </resData>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54321-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let mut res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        if let super::proto::EPPMessageType::Response(r) = &mut res.message {
+            r.raw = Some(XML_DATA.trim().to_string());
+        }
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_poll_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap().unwrap();
+        assert_eq!(data.id, "12345");
+        match data.data {
+            super::PollData::Raw(raw) => {
+                assert!(raw.starts_with("<resData>"));
+                assert!(raw.ends_with("</resData>"));
+                assert!(raw.contains("<example:name>example.com</example:name>"));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn nominet_abuse_feed() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1301">
+      <msg>Command completed successfully; ack to dequeue</msg>
+    </result>
+    <msgQ count="1" id="123456">
+      <qDate>2009-11-12T11:06:46Z</qDate>
+      <msg>Domain Activity Notification</msg>
+    </msgQ>
+    <resData>
+      <abuse-feed:infData xmlns:abuse-feed="http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0">
+        <abuse-feed:key>epp-example.co.uk</abuse-feed:key>
+        <abuse-feed:activity>phishing</abuse-feed:activity>
+        <abuse-feed:source>Netcraft</abuse-feed:source>
+        <abuse-feed:hostname>www.epp-example.co.uk</abuse-feed:hostname>
+        <abuse-feed:url>http://www.epp-example.co.uk/login</abuse-feed:url>
+        <abuse-feed:date>2009-11-12T10:59:21Z</abuse-feed:date>
+        <abuse-feed:ip>192.0.2.1</abuse-feed:ip>
+        <abuse-feed:nameserver>ns1.epp-example.co.uk</abuse-feed:nameserver>
+        <abuse-feed:target>Example Bank</abuse-feed:target>
+        <abuse-feed:wholeDomain>Y</abuse-feed:wholeDomain>
+      </abuse-feed:infData>
+    </resData>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>123456</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_poll_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap().unwrap();
+        match data.data {
+            super::PollData::NominetAbuseFeedData(d) => {
+                assert_eq!(d.key, "epp-example.co.uk");
+                assert_eq!(d.activity, "phishing");
+                assert_eq!(d.hostname, "www.epp-example.co.uk");
+                assert_eq!(d.url.unwrap(), "http://www.epp-example.co.uk/login");
+                assert_eq!(d.ip.unwrap(), "192.0.2.1");
+                assert!(d.dns_admin.is_none());
+                assert_eq!(d.whole_domain.unwrap(), "Y");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn fred_nsset_test() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1301">
+      <msg>Command completed successfully; ack to dequeue</msg>
+    </result>
+    <msgQ count="1" id="19690">
+      <qDate>2021-03-10T14:06:28Z</qDate>
+      <msg>Technical check result</msg>
+    </msgQ>
+    <resData>
+      <nsset:testData xmlns:nsset="urn:ietf:params:xml:ns:nsset-1.2">
+        <nsset:id>NID-EXAMPLE</nsset:id>
+        <nsset:name>example.cz</nsset:name>
+        <nsset:result>
+          <nsset:testname>glue_ok</nsset:testname>
+          <nsset:status>true</nsset:status>
+        </nsset:result>
+        <nsset:result>
+          <nsset:testname>existence</nsset:testname>
+          <nsset:status>false</nsset:status>
+          <nsset:note>ns2.example.cz</nsset:note>
+        </nsset:result>
+      </nsset:testData>
+    </resData>
+    <trID>
+      <clTRID>abcde12345</clTRID>
+      <svTRID>ReqID-0000140900</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_poll_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap().unwrap();
+        match data.data {
+            super::PollData::FredNSSetTestData(d) => {
+                assert_eq!(d.id, "NID-EXAMPLE");
+                assert_eq!(d.names, vec!["example.cz".to_string()]);
+                assert_eq!(d.results.len(), 2);
+                assert!(d.results[0].passed);
+                assert!(d.results[0].note.is_none());
+                assert_eq!(d.results[1].test_name, "existence");
+                assert!(!d.results[1].passed);
+                assert_eq!(d.results[1].note.as_deref(), Some("ns2.example.cz"));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn fred_low_credit() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1301">
+      <msg>Command completed successfully; ack to dequeue</msg>
+    </result>
+    <msgQ count="1" id="19687">
+      <qDate>2021-03-10T14:06:28Z</qDate>
+      <msg>Credit of registrar is low</msg>
+    </msgQ>
+    <resData>
+      <fred:lowCreditData xmlns:fred="http://www.nic.cz/xml/epp/fred-1.5">
+        <fred:zone>cz</fred:zone>
+        <fred:limit>
+          <fred:zone>cz</fred:zone>
+          <fred:credit>5000.00</fred:credit>
+        </fred:limit>
+        <fred:credit>
+          <fred:zone>cz</fred:zone>
+          <fred:credit>4999.00</fred:credit>
+        </fred:credit>
+      </fred:lowCreditData>
+    </resData>
+    <trID>
+      <clTRID>abcde12345</clTRID>
+      <svTRID>ReqID-0000140897</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_poll_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap().unwrap();
+        match data.data {
+            super::PollData::FredLowCreditData(d) => {
+                assert_eq!(d.zone, "cz");
+                assert_eq!(d.limit, "5000.00");
+                assert_eq!(d.credit, "4999.00");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn fred_nsset_transfer() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1301">
+      <msg>Command completed successfully; ack to dequeue</msg>
+    </result>
+    <msgQ count="1" id="19688">
+      <qDate>2021-03-10T14:06:28Z</qDate>
+      <msg>Transfer of NSSET</msg>
+    </msgQ>
+    <resData>
+      <nsset:trnData xmlns:nsset="urn:ietf:params:xml:ns:nsset-1.2">
+        <nsset:id>NID-EXAMPLE</nsset:id>
+        <nsset:trDate>2021-03-10T14:06:28+01:00</nsset:trDate>
+        <nsset:clID>REG-OTHER</nsset:clID>
+      </nsset:trnData>
+    </resData>
+    <trID>
+      <clTRID>abcde12345</clTRID>
+      <svTRID>ReqID-0000140898</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_poll_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap().unwrap();
+        match data.data {
+            super::PollData::FredTransferData(d) => {
+                assert_eq!(d.object_type, crate::client::fred::ObjectType::NSSet);
+                assert_eq!(d.id, "NID-EXAMPLE");
+                assert_eq!(d.transfer_date.to_rfc3339(), "2021-03-10T13:06:28+00:00");
+                assert_eq!(d.client_id, "REG-OTHER");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn fred_keyset_idle_delete() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1301">
+      <msg>Command completed successfully; ack to dequeue</msg>
+    </result>
+    <msgQ count="1" id="19689">
+      <qDate>2021-03-10T14:06:28Z</qDate>
+      <msg>Idle object deletion</msg>
+    </msgQ>
+    <resData>
+      <keyset:idleDelData xmlns:keyset="urn:ietf:params:xml:ns:keyset-1.3">
+        <keyset:id>KID-EXAMPLE</keyset:id>
+      </keyset:idleDelData>
+    </resData>
+    <trID>
+      <clTRID>abcde12345</clTRID>
+      <svTRID>ReqID-0000140899</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let data = super::handle_poll_response(
+            *res, &crate::metrics::DummyMetrics::default()).unwrap().unwrap();
+        match data.data {
+            super::PollData::FredIdleDeleteData(d) => {
+                assert_eq!(d.object_type, crate::client::fred::ObjectType::KeySet);
+                assert_eq!(d.id, "KID-EXAMPLE");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn unknown_result_data() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <example:infData xmlns:example="urn:example:params:xml:ns:object-1.0">
+        <example:name type="a">example.com</example:name>
+        <example:status s="ok"/>
+      </example:infData>
+    </resData>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54321-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            res.data.unwrap().value,
+            super::proto::EPPResultDataValue::Unknown
+        ));
+        assert_eq!(res.transaction_id.client_transaction_id.as_deref(), Some("ABC-12345"));
+    }
+
+    #[test]
+    fn unknown_extension() {
+        const XML_DATA: &str = r#"
+<?xml version="1.0" encoding="UTF-8"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <extension>
+      <example:infData xmlns:example="urn:example:params:xml:ns:ext-1.0">
+        <example:flag enabled="true">yes</example:flag>
+        <example:empty/>
+      </example:infData>
+    </extension>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54321-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res: super::proto::EPPMessage = xml_serde::from_str(XML_DATA.trim()).unwrap();
+        let res = match res.message {
+            super::proto::EPPMessageType::Response(r) => r,
+            _ => unreachable!(),
+        };
+        let extension = res.extension.unwrap();
+        assert_eq!(extension.value.len(), 1);
+        assert!(matches!(
+            extension.value[0],
+            super::proto::EPPResponseExtensionType::Unknown
+        ));
+        assert_eq!(res.transaction_id.client_transaction_id.as_deref(), Some("ABC-12345"));
+    }
 }
@@ -59,6 +59,20 @@ pub fn handle_restore(
             },
         )];
         super::verisign::handle_verisign_namestore_erratum(client, &mut exts);
+
+        if let Some(fee_agreement) = &req.fee_agreement {
+            if client.fee_supported {
+                exts.push(proto::EPPCommandExtensionType::EPPFee10Update(
+                    fee_agreement.into(),
+                ));
+            } else if client.fee_011_supported {
+                exts.push(proto::EPPCommandExtensionType::EPPFee011Update(
+                    fee_agreement.into(),
+                ));
+            } else {
+                return Err(Err(Error::Unsupported));
+            }
+        }
         super::fee::handle_donuts_fee_agreement(client, &req.donuts_fee_agreement, &mut exts)?;
 
         Ok((proto::EPPCommandType::Update(Box::new(command)), Some(exts)))
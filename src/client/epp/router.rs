@@ -1,6 +1,6 @@
 use paste::paste;
 
-pub use super::super::{router, Error, Response};
+pub use super::super::{router, EPPError, Error, Response};
 use super::ServerFeatures;
 
 pub type HandleReqReturn<T> = Result<
@@ -21,7 +21,7 @@ macro_rules! router {
             type Response = super::proto::EPPResponse;
 
             paste! {
-                $(fn [<$n _request>](&mut self, client: &super::ServerFeatures, req: &router::[<$n Request>], _command_id: uuid::Uuid) -> HandleReqReturn<router::[<$n Response>]> {
+                $(fn [<$n _request>](&mut self, client: &super::ServerFeatures, req: &router::[<$n Request>], _command_id: String) -> HandleReqReturn<router::[<$n Response>]> {
                     $req_handle(client, &req)
                 })*
 
@@ -29,22 +29,32 @@ macro_rules! router {
                     &mut self, return_path: router::Sender<router::[<$n Response>]>,
                     response: Self::Response, metrics: &M
                 ) {
-                    let _ = if !response.is_success() {
-                        if response.is_server_error() {
-                            return_path.send(Err(Error::Err(format!("Server error: {}", response.response_msg()))))
-                        } else {
-                            return_path.send(Err(Error::Err(response.response_msg())))
+                    let ext_values = match response.raw.as_deref().map(super::ext_value::parse) {
+                        Some(Ok(v)) => v,
+                        Some(Err(e)) => {
+                            warn!("Unable to parse extValue elements from response: {}", e);
+                            Default::default()
                         }
+                        None => Default::default(),
+                    };
+                    let trans_id = router::CommandTransactionID {
+                        client: response.transaction_id.client_transaction_id.as_deref().unwrap_or_default().to_owned(),
+                        server: response.transaction_id.server_transaction_id.as_deref().unwrap_or_default().to_owned(),
+                    };
+                    let _ = if !response.is_success() {
+                        return_path.send(Err(Error::EPP(EPPError {
+                            code: response.results.first().map(|r| (&r.code).into()).unwrap_or_default(),
+                            message: response.response_msg(),
+                            reasons: ext_values.extra_values,
+                            transaction_id: trans_id,
+                        })))
                     } else {
-                        let trans_id = router::CommandTransactionID {
-                            client: response.transaction_id.client_transaction_id.as_deref().unwrap_or_default().to_owned(),
-                            server: response.transaction_id.server_transaction_id.as_deref().unwrap_or_default().to_owned(),
-                        };
                         match $res_handle(response, metrics) {
                             Ok(r) => return_path.send(Ok(router::CommandResponse {
                                 response: r,
-                                extra_values: vec![],
-                                 transaction_id: Some(trans_id)
+                                extra_values: ext_values.extra_values,
+                                unhandled_namespaces: ext_values.unhandled_namespaces,
+                                transaction_id: Some(trans_id)
                             })),
                             Err(e) => return_path.send(Err(e))
                         }
@@ -68,6 +78,7 @@ router!(
     Poll,                        super::poll::handle_poll,                      super::poll::handle_poll_response;
     PollAck,                     super::poll::handle_poll_ack,                  super::poll::handle_poll_ack_response;
     DomainCheck,                 super::domain::handle_check,                   super::domain::handle_check_response;
+    DomainBatchCheck,            super::domain::handle_batch_check,             super::domain::handle_batch_check_response;
     DomainClaimsCheck,           super::domain::handle_claims_check,            super::domain::handle_claims_check_response;
     DomainTrademarkCheck,        super::domain::handle_trademark_check,         super::domain::handle_claims_check_response;
     DomainInfo,                  super::domain::handle_info,                    super::domain::handle_info_response;
@@ -95,11 +106,25 @@ router!(
     RestoreRequest,              super::rgp::handle_restore,                    super::rgp::handle_restore_response;
     RestoreReport,               super::rgp::handle_restore_report,             super::rgp::handle_restore_report_response;
     HostCheck,                   super::host::handle_check,                     super::host::handle_check_response;
+    HostBatchCheck,              super::host::handle_batch_check,               super::host::handle_batch_check_response;
     HostInfo,                    super::host::handle_info,                      super::host::handle_info_response;
     HostCreate,                  super::host::handle_create,                    super::host::handle_create_response;
     HostDelete,                  super::host::handle_delete,                    super::host::handle_delete_response;
     HostUpdate,                  super::host::handle_update,                    super::host::handle_update_response;
+    NSSetCheck,                  super::nsset::handle_check,                    super::nsset::handle_check_response;
+    NSSetInfo,                   super::nsset::handle_info,                     super::nsset::handle_info_response;
+    NSSetCreate,                 super::nsset::handle_create,                   super::nsset::handle_create_response;
+    NSSetDelete,                 super::nsset::handle_delete,                   super::nsset::handle_delete_response;
+    NSSetUpdate,                 super::nsset::handle_update,                   super::nsset::handle_update_response;
+    NSSetTransfer,               super::nsset::handle_transfer,                 super::nsset::handle_transfer_response;
+    KeySetCheck,                 super::keyset::handle_check,                   super::keyset::handle_check_response;
+    KeySetInfo,                  super::keyset::handle_info,                    super::keyset::handle_info_response;
+    KeySetCreate,                super::keyset::handle_create,                  super::keyset::handle_create_response;
+    KeySetDelete,                super::keyset::handle_delete,                  super::keyset::handle_delete_response;
+    KeySetUpdate,                super::keyset::handle_update,                  super::keyset::handle_update_response;
+    KeySetTransfer,              super::keyset::handle_transfer,                super::keyset::handle_transfer_response;
     ContactCheck,                super::contact::handle_check,                  super::contact::handle_check_response;
+    ContactBatchCheck,           super::contact::handle_batch_check,            super::contact::handle_batch_check_response;
     ContactInfo,                 super::contact::handle_info,                   super::contact::handle_info_response;
     ContactCreate,               super::contact::handle_create,                 super::contact::handle_create_response;
     ContactDelete,               super::contact::handle_delete,                 super::contact::handle_delete_response;
@@ -115,6 +140,9 @@ router!(
     NominetContactValidate,      super::nominet::handle_contact_validate,       super::nominet::handle_contact_validate_response;
     NominetLock,                 super::nominet::handle_lock,                   super::nominet::handle_lock_response;
     NominetUnlock,               super::nominet::handle_unlock,                 super::nominet::handle_lock_response;
+    NominetUnrenew,              super::nominet::handle_unrenew,                super::nominet::handle_unrenew_response;
+    NominetList,                 super::nominet::handle_list,                   super::nominet::handle_list_response;
+    NominetFork,                 super::nominet::handle_fork,                   super::nominet::handle_fork_response;
     Balance,                     super::balance::handle_balance,                super::balance::handle_balance_response;
     MaintenanceList,             super::maintenance::handle_list,               super::maintenance::handle_list_response;
     MaintenanceInfo,             super::maintenance::handle_info,               super::maintenance::handle_info_response;
@@ -186,7 +186,11 @@ impl TLSClient {
         })
     }
 
-    pub async fn connect(&self) -> TLSConnection {
+    pub async fn connect<M: crate::metrics::Metrics>(
+        &self,
+        backoff: &crate::client::reconnect::Backoff,
+        metrics: &M,
+    ) -> TLSConnection {
         loop {
             match self._try_connect().await {
                 Ok(s) => {
@@ -194,7 +198,7 @@ impl TLSClient {
                     return s;
                 }
                 Err(_) => {
-                    tokio::time::sleep(tokio::time::Duration::new(5, 0)).await;
+                    backoff.wait(backoff.failure(metrics), metrics).await;
                 }
             }
         }
@@ -210,7 +210,7 @@ pub struct DomainRenewInfo {
 /// # Arguments
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn hit_points_info(
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<HitPointsResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -228,7 +228,7 @@ pub async fn hit_points_info(
 /// # Arguments
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn registration_limit_info(
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<RegistrationLimitResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -247,7 +247,7 @@ pub async fn registration_limit_info(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn dnssec_eligibility_info(
     name: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<DNSSECEligibilityResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -267,7 +267,7 @@ pub async fn dnssec_eligibility_info(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn dns_quality_info(
     name: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<DNSQualityResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
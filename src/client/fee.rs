@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FeeCheck {
     pub currency: Option<String>,
     pub commands: Vec<FeeCheckCommand>,
@@ -23,17 +23,19 @@ pub enum Command {
     Custom,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Applied {
     Immediate,
     Delayed,
     Unspecified,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FeeCheckCommand {
     pub command: Command,
     pub period: Option<super::Period>,
+    /// Name of a custom command, such as `sync` for a Verisign ConsoliDate
+    pub custom_name: Option<String>,
     pub phase: Option<String>,
     pub sub_phase: Option<String>,
 }
@@ -57,7 +59,7 @@ pub struct FeeCommand {
     pub class: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Fee {
     pub value: String,
     pub description: Option<String>,
@@ -119,3 +121,700 @@ pub struct DonutsAmount {
     pub command: Command,
     pub command_name: Option<String>,
 }
+
+/// Per-registry policy on acknowledging the price of billable commands
+#[derive(Debug, Copy, Clone)]
+pub struct FeePolicy {
+    /// Refuse billable commands that don't come with a maximum price
+    pub require_max_price: bool,
+    /// Let billable commands through when the registry doesn't quote a price for them
+    pub allow_unpriced: bool,
+}
+
+impl Default for FeePolicy {
+    fn default() -> Self {
+        FeePolicy {
+            require_max_price: true,
+            allow_unpriced: false,
+        }
+    }
+}
+
+/// The most a caller is willing to pay for a billable command
+#[derive(Debug, Clone)]
+pub struct FeeCeiling {
+    /// Maximum total price, as a decimal amount
+    pub max_price: String,
+    /// Currency of the maximum price, if the caller cares which currency it's charged in
+    pub currency: Option<String>,
+}
+
+/// Reasons a billable command is refused under a fee policy
+#[derive(Debug, PartialEq)]
+pub enum FeeCeilingError {
+    /// No maximum price was given and the policy requires one
+    NoCeiling,
+    /// The maximum price isn't a decimal amount
+    InvalidCeiling(String),
+    /// The registry didn't quote a price for the command
+    Unpriced,
+    /// The registry quoted a price that isn't a decimal amount
+    InvalidPrice(String),
+    /// The registry charges in a different currency to the one given
+    CurrencyMismatch { expected: String, quoted: String },
+    /// The registry price is over the maximum price
+    Exceeded { max_price: String, price: String },
+}
+
+impl std::fmt::Display for FeeCeilingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeeCeilingError::NoCeiling => write!(f, "maximum price required for this command"),
+            FeeCeilingError::InvalidCeiling(p) => write!(f, "invalid maximum price: {}", p),
+            FeeCeilingError::Unpriced => write!(f, "registry did not quote a price"),
+            FeeCeilingError::InvalidPrice(p) => write!(f, "invalid registry price: {}", p),
+            FeeCeilingError::CurrencyMismatch { expected, quoted } => write!(
+                f,
+                "registry charges in {}, maximum price is in {}",
+                quoted, expected
+            ),
+            FeeCeilingError::Exceeded { max_price, price } => write!(
+                f,
+                "registry price {} exceeds maximum price {}",
+                price, max_price
+            ),
+        }
+    }
+}
+
+impl FeeCeiling {
+    /// Checks the price a registry quoted for a command against this ceiling
+    ///
+    /// # Arguments
+    /// * `command` - Billable command about to be sent
+    /// * `fees` - Fees the registry quoted, if any
+    /// * `policy` - Fee policy of the registry
+    pub fn check(
+        &self,
+        command: Command,
+        fees: Option<&NormalisedFees>,
+        policy: &FeePolicy,
+    ) -> Result<(), FeeCeilingError> {
+        if compare_amounts(&self.max_price, "0").is_none() {
+            return Err(FeeCeilingError::InvalidCeiling(self.max_price.clone()));
+        }
+        let price = fees.and_then(|f| {
+            f.commands
+                .iter()
+                .filter(|c| c.command == command)
+                .find_map(|c| c.total.as_ref())
+        });
+        let price = match price {
+            Some(p) => p,
+            None if policy.allow_unpriced => return Ok(()),
+            None => return Err(FeeCeilingError::Unpriced),
+        };
+        // Donuts charge data has no currency, there's nothing to compare it against
+        if let (Some(expected), Some(quoted)) = (
+            self.currency.as_deref(),
+            fees.and_then(|f| f.currency.as_deref()),
+        ) {
+            if !expected.trim().eq_ignore_ascii_case(quoted.trim()) {
+                return Err(FeeCeilingError::CurrencyMismatch {
+                    expected: expected.to_string(),
+                    quoted: quoted.to_string(),
+                });
+            }
+        }
+        match compare_amounts(price, &self.max_price) {
+            Some(std::cmp::Ordering::Greater) => Err(FeeCeilingError::Exceeded {
+                max_price: self.max_price.clone(),
+                price: price.clone(),
+            }),
+            Some(_) => Ok(()),
+            None => Err(FeeCeilingError::InvalidPrice(price.clone())),
+        }
+    }
+}
+
+impl FeeCheckData {
+    /// Builds an agreement to the fees the registry quoted for a command
+    ///
+    /// Returns `None` if no fees were quoted for the command.
+    pub fn agreement(&self, command: Command) -> Option<FeeAgreement> {
+        let quote = self
+            .commands
+            .iter()
+            .find(|c| c.command == command && !c.fees.is_empty())?;
+        Some(FeeAgreement {
+            currency: non_empty(&quote.currency),
+            fees: quote.fees.clone(),
+        })
+    }
+}
+
+impl DonutsFeeData {
+    /// Builds an agreement to the charges the registry quoted for a command
+    ///
+    /// Returns `None` if no charges were quoted for the command.
+    pub fn agreement(&self, command: Command) -> Option<DonutsFeeData> {
+        let sets = self
+            .sets
+            .iter()
+            .filter_map(|set| {
+                let fees = set
+                    .fees
+                    .iter()
+                    .filter(|a| match (a.command, a.command_name.as_deref()) {
+                        (Command::Update, Some(n)) if n.eq_ignore_ascii_case("restore") => {
+                            command == Command::Restore
+                        }
+                        (c, _) => c == command,
+                    })
+                    .map(|a| DonutsAmount {
+                        value: a.value.clone(),
+                        command: a.command,
+                        command_name: a.command_name.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                if fees.is_empty() {
+                    None
+                } else {
+                    Some(DonutsFeeSet {
+                        fees,
+                        fee_type: set.fee_type.clone(),
+                        category: set.category.clone(),
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+        if sets.is_empty() {
+            None
+        } else {
+            Some(DonutsFeeData { sets })
+        }
+    }
+}
+
+/// Fee information in a single form, whichever fee extension draft or Donuts charge extension
+/// it came from
+#[derive(Debug, Default)]
+pub struct NormalisedFees {
+    /// Currency all amounts are in, if the registry gave one
+    pub currency: Option<String>,
+    /// Is the object priced differently to the standard for its TLD
+    pub premium: bool,
+    /// Registry assigned fee class or Donuts category, such as `premium-1`
+    pub class: Option<String>,
+    pub commands: Vec<NormalisedCommandFee>,
+    pub balance: Option<String>,
+    pub credit_limit: Option<String>,
+}
+
+/// Fees for a single command
+#[derive(Debug)]
+pub struct NormalisedCommandFee {
+    pub command: Command,
+    /// Registry name of the command, for custom commands
+    pub command_name: Option<String>,
+    pub period: Option<super::Period>,
+    /// Sum of all fees and credits, if there were any and they could be added up
+    pub total: Option<String>,
+    pub components: Vec<FeeComponent>,
+    /// Why the registry didn't give a fee for the command
+    pub reason: Option<String>,
+}
+
+/// A single fee or credit making up the fee for a command
+#[derive(Debug)]
+pub struct FeeComponent {
+    /// Amount charged, negative for credits
+    pub amount: String,
+    pub description: Option<String>,
+    /// Is the fee refunded if the object is deleted in its grace period, `None` if not stated
+    pub refundable: Option<bool>,
+    /// Grace period as an XML schema duration, such as `P5D`
+    pub grace_period: Option<String>,
+    /// Is the fee charged at some later point, rather than when the command is processed
+    pub delayed: bool,
+}
+
+impl From<&Fee> for FeeComponent {
+    fn from(from: &Fee) -> Self {
+        FeeComponent {
+            amount: from.value.trim().to_string(),
+            description: from.description.clone(),
+            refundable: from.refundable,
+            grace_period: from.grace_period.clone(),
+            delayed: matches!(from.applied, Applied::Delayed),
+        }
+    }
+}
+
+impl From<&Credit> for FeeComponent {
+    fn from(from: &Credit) -> Self {
+        // Credits are meant to be negative in every draft, but not all registries agree
+        let amount = from.value.trim();
+        FeeComponent {
+            amount: match amount.starts_with('-') {
+                true => amount.to_string(),
+                false => format!("-{}", amount),
+            },
+            description: from.description.clone(),
+            refundable: None,
+            grace_period: None,
+            delayed: false,
+        }
+    }
+}
+
+impl NormalisedCommandFee {
+    fn new(
+        command: Command,
+        period: Option<super::Period>,
+        fees: &[Fee],
+        credits: &[Credit],
+    ) -> Self {
+        let components = fees
+            .iter()
+            .map(FeeComponent::from)
+            .chain(credits.iter().map(FeeComponent::from))
+            .collect::<Vec<_>>();
+        NormalisedCommandFee {
+            command,
+            command_name: None,
+            period,
+            total: sum_amounts(components.iter().map(|c| c.amount.as_str())),
+            components,
+            reason: None,
+        }
+    }
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    match s.trim() {
+        "" => None,
+        s => Some(s.to_string()),
+    }
+}
+
+impl From<&FeeCheckData> for NormalisedFees {
+    fn from(from: &FeeCheckData) -> Self {
+        let mut fees = NormalisedFees::default();
+        for c in &from.commands {
+            if fees.currency.is_none() {
+                fees.currency = non_empty(&c.currency);
+            }
+            if fees.class.is_none() {
+                fees.class = c.class.as_deref().and_then(non_empty);
+            }
+            // Commands without a fee, such as ones only giving a reason, say nothing about price
+            if c.standard == Some(false) && !c.fees.is_empty() {
+                fees.premium = true;
+            }
+            let mut command =
+                NormalisedCommandFee::new(c.command, c.period.clone(), &c.fees, &c.credits);
+            command.reason = c.reason.clone();
+            fees.commands.push(command);
+        }
+        if let Some(class) = &fees.class {
+            if class.to_lowercase().contains("premium") {
+                fees.premium = true;
+            }
+        }
+        fees
+    }
+}
+
+impl From<&DonutsFeeData> for NormalisedFees {
+    fn from(from: &DonutsFeeData) -> Self {
+        let mut fees = NormalisedFees::default();
+        for set in &from.sets {
+            if !set.category.category.eq_ignore_ascii_case("standard") {
+                fees.premium = true;
+                if fees.class.is_none() {
+                    fees.class = Some(set.category.category.clone());
+                }
+            }
+            let description = set.fee_type.name.clone().or_else(|| {
+                Some(
+                    match set.fee_type.fee_type {
+                        DonutsFeeTypes::Fee => "fee",
+                        DonutsFeeTypes::Price => "price",
+                        DonutsFeeTypes::Custom => "custom",
+                    }
+                    .to_string(),
+                )
+            });
+            for amount in &set.fees {
+                // There's no restore command in the charge extension, it's sent as a named update
+                let (command, command_name) = match (amount.command, amount.command_name.as_deref())
+                {
+                    (Command::Update, Some(n)) if n.eq_ignore_ascii_case("restore") => {
+                        (Command::Restore, None)
+                    }
+                    (c, _) => (c, amount.command_name.clone()),
+                };
+                let component = FeeComponent {
+                    amount: amount.value.trim().to_string(),
+                    description: description.clone(),
+                    refundable: None,
+                    grace_period: None,
+                    delayed: false,
+                };
+                match fees
+                    .commands
+                    .iter_mut()
+                    .find(|c| c.command == command && c.command_name == command_name)
+                {
+                    Some(c) => c.components.push(component),
+                    None => fees.commands.push(NormalisedCommandFee {
+                        command,
+                        command_name,
+                        period: None,
+                        total: None,
+                        components: vec![component],
+                        reason: None,
+                    }),
+                }
+            }
+        }
+        for c in &mut fees.commands {
+            c.total = sum_amounts(c.components.iter().map(|c| c.amount.as_str()));
+        }
+        fees
+    }
+}
+
+impl NormalisedFees {
+    /// Normalises fee data from a transform command response
+    ///
+    /// # Arguments
+    /// * `command` - Command the response is to
+    /// * `from` - Fee data returned in the response
+    pub fn from_fee_data(command: Command, from: &FeeData) -> Self {
+        NormalisedFees {
+            currency: non_empty(&from.currency),
+            premium: false,
+            class: None,
+            commands: vec![NormalisedCommandFee::new(
+                command,
+                from.period.clone(),
+                &from.fees,
+                &from.credits,
+            )],
+            balance: from.balance.clone(),
+            credit_limit: from.credit_limit.clone(),
+        }
+    }
+
+    /// Normalises the fee data of a check, preferring IETF fee data over Donuts charge data if
+    /// the registry returned both
+    ///
+    /// # Arguments
+    /// * `fee_check` - IETF fee extension data, of any draft version
+    /// * `donuts_fee_check` - Donuts charge extension data
+    pub fn from_check(
+        fee_check: Option<&FeeCheckData>,
+        donuts_fee_check: Option<&DonutsFeeData>,
+    ) -> Option<Self> {
+        fee_check
+            .map(Into::into)
+            .or_else(|| donuts_fee_check.map(Into::into))
+    }
+
+    /// Normalises the fee data of a transform command response, preferring IETF fee data over
+    /// Donuts charge data if the registry returned both
+    ///
+    /// # Arguments
+    /// * `command` - Command the response is to
+    /// * `fee_data` - IETF fee extension data, of any draft version
+    /// * `donuts_fee_data` - Donuts charge extension data
+    pub fn from_data(
+        command: Command,
+        fee_data: Option<&FeeData>,
+        donuts_fee_data: Option<&DonutsFeeData>,
+    ) -> Option<Self> {
+        fee_data
+            .map(|f| Self::from_fee_data(command, f))
+            .or_else(|| donuts_fee_data.map(Into::into))
+    }
+}
+
+/// Prices of the common billable commands on a domain, whichever fee extension they came from
+#[derive(Debug, Default, PartialEq)]
+pub struct DomainPrices {
+    /// Currency all prices are in, if the registry gave any
+    pub currency: Option<String>,
+    /// Is the domain priced differently to the standard for its TLD
+    pub premium: bool,
+    /// Registry assigned fee class, such as `premium-1`
+    pub class: Option<String>,
+    pub create: Option<String>,
+    pub renew: Option<String>,
+    pub transfer: Option<String>,
+    pub restore: Option<String>,
+}
+
+impl DomainPrices {
+    /// Extracts prices from the fee data of a domain check, preferring IETF fee data over Donuts
+    /// charge data if the registry returned both
+    ///
+    /// # Arguments
+    /// * `fee_check` - IETF fee extension data, of any draft version
+    /// * `donuts_fee_check` - Donuts charge extension data
+    pub fn from_check(
+        fee_check: Option<&FeeCheckData>,
+        donuts_fee_check: Option<&DonutsFeeData>,
+    ) -> Option<Self> {
+        let fees = NormalisedFees::from_check(fee_check, donuts_fee_check)?;
+        let mut prices = DomainPrices {
+            currency: fees.currency,
+            premium: fees.premium,
+            class: fees.class,
+            ..Default::default()
+        };
+        for c in fees.commands {
+            let price = match c.command {
+                Command::Create => &mut prices.create,
+                Command::Renew => &mut prices.renew,
+                Command::Transfer => &mut prices.transfer,
+                Command::Restore => &mut prices.restore,
+                _ => continue,
+            };
+            if price.is_none() {
+                *price = c.total;
+            }
+        }
+        Some(prices)
+    }
+}
+
+/// Adds up decimal amounts without going through floating point
+///
+/// Returns `None` if there are no amounts or any isn't a plain decimal number.
+fn sum_amounts<'a, I: IntoIterator<Item = &'a str>>(amounts: I) -> Option<String> {
+    let mut total: i128 = 0;
+    let mut scale: u32 = 0;
+    let mut any = false;
+    for amount in amounts {
+        let amount = amount.trim();
+        let (negative, amount) = match amount.strip_prefix('-') {
+            Some(a) => (true, a),
+            None => (false, amount),
+        };
+        let (whole, frac) = match amount.split_once('.') {
+            Some((w, f)) => (w, f),
+            None => (amount, ""),
+        };
+        if whole.is_empty() && frac.is_empty() {
+            return None;
+        }
+        if !whole
+            .chars()
+            .chain(frac.chars())
+            .all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+        let frac_scale = frac.len() as u32;
+        if frac_scale > scale {
+            total = total.checked_mul(10i128.checked_pow(frac_scale - scale)?)?;
+            scale = frac_scale;
+        }
+        let value: i128 = format!("{}{}", whole, frac).parse().ok()?;
+        let value = value.checked_mul(10i128.checked_pow(scale - frac_scale)?)?;
+        total = match negative {
+            true => total.checked_sub(value)?,
+            false => total.checked_add(value)?,
+        };
+        any = true;
+    }
+    if !any {
+        return None;
+    }
+
+    let sign = if total < 0 { "-" } else { "" };
+    let total = total.unsigned_abs();
+    Some(match scale {
+        0 => format!("{}{}", sign, total),
+        s => {
+            let divisor = 10u128.pow(s);
+            format!(
+                "{}{}.{:0width$}",
+                sign,
+                total / divisor,
+                total % divisor,
+                width = s as usize
+            )
+        }
+    })
+}
+
+/// Compares decimal amounts without going through floating point
+///
+/// Returns `None` if either amount isn't a plain decimal number.
+fn compare_amounts(a: &str, b: &str) -> Option<std::cmp::Ordering> {
+    let b = b.trim();
+    let negated_b = match b.strip_prefix('-') {
+        Some(b) => b.to_string(),
+        None => format!("-{}", b),
+    };
+    let difference = sum_amounts([a, negated_b.as_str()])?;
+    Some(if difference.starts_with('-') {
+        std::cmp::Ordering::Less
+    } else if difference.chars().all(|c| c == '0' || c == '.') {
+        std::cmp::Ordering::Equal
+    } else {
+        std::cmp::Ordering::Greater
+    })
+}
+
+#[cfg(test)]
+mod fee_tests {
+    #[test]
+    fn sum_amounts() {
+        assert_eq!(super::sum_amounts(["10.00", "2.5"]).unwrap(), "12.50");
+        assert_eq!(super::sum_amounts(["5"]).unwrap(), "5");
+        assert_eq!(super::sum_amounts(["1.00", "-1.50"]).unwrap(), "-0.50");
+        assert!(super::sum_amounts(std::iter::empty()).is_none());
+        assert!(super::sum_amounts(["ten"]).is_none());
+    }
+
+    #[test]
+    fn prices_from_fee_check() {
+        let fee_check = super::FeeCheckData {
+            available: true,
+            commands: vec![super::FeeCommand {
+                command: super::Command::Create,
+                period: None,
+                standard: Some(false),
+                currency: "USD".to_string(),
+                fees: vec![
+                    super::Fee {
+                        value: "100.00".to_string(),
+                        description: Some("Registration Fee".to_string()),
+                        refundable: Some(true),
+                        grace_period: None,
+                        applied: super::Applied::Immediate,
+                    },
+                    super::Fee {
+                        value: "0.18".to_string(),
+                        description: Some("ICANN Fee".to_string()),
+                        refundable: Some(false),
+                        grace_period: None,
+                        applied: super::Applied::Immediate,
+                    },
+                ],
+                credits: vec![],
+                reason: None,
+                class: Some("premium-tier-1".to_string()),
+            }],
+            reason: None,
+        };
+        let prices = super::DomainPrices::from_check(Some(&fee_check), None).unwrap();
+        assert!(prices.premium);
+        assert_eq!(prices.currency.as_deref(), Some("USD"));
+        assert_eq!(prices.create.as_deref(), Some("100.18"));
+        assert!(prices.renew.is_none());
+    }
+
+    #[test]
+    fn compare_amounts() {
+        use std::cmp::Ordering;
+        assert_eq!(
+            super::compare_amounts("10.00", "9.99"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(super::compare_amounts("10", "10.00"), Some(Ordering::Equal));
+        assert_eq!(super::compare_amounts("-1", "0"), Some(Ordering::Less));
+        assert_eq!(super::compare_amounts("5", "-5"), Some(Ordering::Greater));
+        assert!(super::compare_amounts("5", "five").is_none());
+    }
+
+    fn create_fees(currency: &str, total: &str) -> super::NormalisedFees {
+        super::NormalisedFees {
+            currency: Some(currency.to_string()),
+            commands: vec![super::NormalisedCommandFee {
+                command: super::Command::Create,
+                command_name: None,
+                period: None,
+                total: Some(total.to_string()),
+                components: vec![],
+                reason: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fee_ceiling() {
+        let policy = super::FeePolicy::default();
+        let ceiling = super::FeeCeiling {
+            max_price: "50.00".to_string(),
+            currency: Some("USD".to_string()),
+        };
+        let create = super::Command::Create;
+        assert!(ceiling
+            .check(create, Some(&create_fees("USD", "50.00")), &policy)
+            .is_ok());
+        assert_eq!(
+            ceiling.check(create, Some(&create_fees("USD", "50.01")), &policy),
+            Err(super::FeeCeilingError::Exceeded {
+                max_price: "50.00".to_string(),
+                price: "50.01".to_string(),
+            })
+        );
+        assert_eq!(
+            ceiling.check(create, Some(&create_fees("EUR", "10.00")), &policy),
+            Err(super::FeeCeilingError::CurrencyMismatch {
+                expected: "USD".to_string(),
+                quoted: "EUR".to_string(),
+            })
+        );
+        assert_eq!(
+            ceiling.check(
+                super::Command::Renew,
+                Some(&create_fees("USD", "10.00")),
+                &policy
+            ),
+            Err(super::FeeCeilingError::Unpriced)
+        );
+        let lenient = super::FeePolicy {
+            require_max_price: false,
+            allow_unpriced: true,
+        };
+        assert!(ceiling.check(create, None, &lenient).is_ok());
+    }
+
+    #[test]
+    fn donuts_agreement() {
+        let amount = |value: &str, command, command_name: Option<&str>| super::DonutsAmount {
+            value: value.to_string(),
+            command,
+            command_name: command_name.map(Into::into),
+        };
+        let fee_data = super::DonutsFeeData {
+            sets: vec![super::DonutsFeeSet {
+                fees: vec![
+                    amount("20.00", super::Command::Create, None),
+                    amount("20.00", super::Command::Renew, None),
+                    amount("30.00", super::Command::Update, Some("restore")),
+                ],
+                fee_type: super::DonutsFeeType {
+                    fee_type: super::DonutsFeeTypes::Price,
+                    name: None,
+                },
+                category: super::DonutsCategory {
+                    category: "premium".to_string(),
+                    name: None,
+                },
+            }],
+        };
+        let agreement = fee_data.agreement(super::Command::Restore).unwrap();
+        assert_eq!(agreement.sets.len(), 1);
+        assert_eq!(agreement.sets[0].fees.len(), 1);
+        assert_eq!(agreement.sets[0].fees[0].value, "30.00");
+        assert!(fee_data.agreement(super::Command::Transfer).is_none());
+    }
+}
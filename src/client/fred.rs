@@ -0,0 +1,51 @@
+//! Poll messages specific to FRED registries
+
+use chrono::prelude::*;
+
+/// Kind of FRED object a notification is about
+#[derive(Debug, PartialEq)]
+pub enum ObjectType {
+    NSSet,
+    KeySet,
+}
+
+/// An object has been transferred away from this registrar
+#[derive(Debug)]
+pub struct TransferData {
+    pub object_type: ObjectType,
+    pub id: String,
+    pub transfer_date: DateTime<Utc>,
+    /// Registrar the object was transferred to
+    pub client_id: String,
+}
+
+/// An object has been deleted by the registry after being unused for too long
+#[derive(Debug)]
+pub struct IdleDeleteData {
+    pub object_type: ObjectType,
+    pub id: String,
+}
+
+/// Results of the registry's technical check of an NSSET
+#[derive(Debug)]
+pub struct NSSetTestData {
+    pub id: String,
+    /// Domains the check was run for
+    pub names: Vec<String>,
+    pub results: Vec<NSSetTestResult>,
+}
+
+#[derive(Debug)]
+pub struct NSSetTestResult {
+    pub test_name: String,
+    pub passed: bool,
+    pub note: Option<String>,
+}
+
+/// Credit for a zone has fallen below the limit set for it
+#[derive(Debug)]
+pub struct LowCreditData {
+    pub zone: String,
+    pub limit: String,
+    pub credit: String,
+}
@@ -15,6 +15,24 @@ pub struct CheckResponse {
     pub reason: Option<String>,
 }
 
+#[derive(Debug)]
+pub struct BatchCheckRequest {
+    pub(crate) names: Vec<String>,
+    pub return_path: Sender<BatchCheckResponse>,
+}
+
+#[derive(Debug)]
+pub struct BatchCheckResponse {
+    pub results: Vec<BatchCheckResult>,
+}
+
+#[derive(Debug)]
+pub struct BatchCheckResult {
+    pub name: String,
+    pub avail: bool,
+    pub reason: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct InfoRequest {
     pub(super) name: String,
@@ -113,7 +131,7 @@ pub enum Status {
 
 pub async fn check(
     host: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<CheckResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -127,9 +145,30 @@ pub async fn check(
     .await
 }
 
+/// Checks if several host names are available in one command
+///
+/// # Arguments
+/// * `hosts` - The host names in question
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn batch_check(
+    hosts: &[String],
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<BatchCheckResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::HostBatchCheck(Box::new(BatchCheckRequest {
+            names: hosts.to_vec(),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
 pub async fn info(
     host: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<InfoResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -147,7 +186,7 @@ pub async fn create(
     host: &str,
     addresses: Vec<Address>,
     isnic_info: Option<super::isnic::HostInfo>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<CreateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -165,7 +204,7 @@ pub async fn create(
 
 pub async fn delete(
     host: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<DeleteResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -185,7 +224,7 @@ pub async fn update<N: Into<Option<String>>>(
     remove: Vec<UpdateObject>,
     new_name: N,
     isnic_info: Option<super::isnic::HostInfo>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<UpdateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
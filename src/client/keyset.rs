@@ -0,0 +1,259 @@
+//! EPP commands relating to FRED KEYSET (DNSSEC key set) objects
+
+use super::{CommandResponse, RequestMessage, Sender};
+use chrono::prelude::*;
+
+#[derive(Debug)]
+pub struct CheckRequest {
+    pub(super) id: String,
+    pub return_path: Sender<CheckResponse>,
+}
+
+#[derive(Debug)]
+pub struct CheckResponse {
+    pub avail: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct InfoRequest {
+    pub(super) id: String,
+    pub return_path: Sender<InfoResponse>,
+}
+
+#[derive(Debug)]
+pub struct InfoResponse {
+    pub id: String,
+    pub registry_id: String,
+    pub statuses: Vec<String>,
+    pub client_id: String,
+    pub client_created_id: Option<String>,
+    pub creation_date: Option<DateTime<Utc>>,
+    pub last_updated_client: Option<String>,
+    pub last_updated_date: Option<DateTime<Utc>>,
+    pub last_transfer_date: Option<DateTime<Utc>>,
+    pub auth_info: Option<String>,
+    pub keys: Vec<DNSKey>,
+    pub tech_contacts: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct DNSKey {
+    pub flags: u16,
+    pub protocol: u8,
+    pub algorithm: u8,
+    /// Base64 encoded public key
+    pub public_key: String,
+}
+
+#[derive(Debug)]
+pub struct CreateRequest {
+    pub(super) id: String,
+    pub(super) keys: Vec<DNSKey>,
+    pub(super) tech_contacts: Vec<String>,
+    pub(super) auth_info: Option<String>,
+    pub return_path: Sender<CreateResponse>,
+}
+
+#[derive(Debug)]
+pub struct CreateResponse {
+    pub id: String,
+    pub pending: bool,
+    pub transaction_id: String,
+    pub creation_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+pub struct DeleteRequest {
+    pub(super) id: String,
+    pub return_path: Sender<DeleteResponse>,
+}
+
+#[derive(Debug)]
+pub struct DeleteResponse {
+    pub pending: bool,
+    pub transaction_id: String,
+}
+
+#[derive(Debug)]
+pub struct UpdateRequest {
+    pub(super) id: String,
+    pub(super) add_keys: Vec<DNSKey>,
+    pub(super) remove_keys: Vec<DNSKey>,
+    pub(super) add_tech_contacts: Vec<String>,
+    pub(super) remove_tech_contacts: Vec<String>,
+    pub(super) new_auth_info: Option<String>,
+    pub return_path: Sender<UpdateResponse>,
+}
+
+#[derive(Debug)]
+pub struct UpdateResponse {
+    pub pending: bool,
+    pub transaction_id: String,
+}
+
+#[derive(Debug)]
+pub struct TransferRequest {
+    pub(super) id: String,
+    pub(super) auth_info: String,
+    pub return_path: Sender<TransferResponse>,
+}
+
+#[derive(Debug)]
+pub struct TransferResponse {
+    pub pending: bool,
+    pub transaction_id: String,
+}
+
+/// Checks if a KEYSET ID is available
+///
+/// # Arguments
+/// * `id` - The KEYSET ID to check
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn check(
+    id: &str,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<CheckResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::KeySetCheck(Box::new(CheckRequest {
+            id: id.to_string(),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Fetches information about a specific KEYSET
+///
+/// # Arguments
+/// * `id` - The ID of the KEYSET
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn info(
+    id: &str,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<InfoResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::KeySetInfo(Box::new(InfoRequest {
+            id: id.to_string(),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Creates a new KEYSET
+///
+/// # Arguments
+/// * `id` - The ID of the new KEYSET
+/// * `keys` - DNSKEY records in the set
+/// * `tech_contacts` - Contact IDs of the technical contacts
+/// * `auth_info` - Optional auth info, one will be generated by the registry if not given
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn create(
+    id: &str,
+    keys: Vec<DNSKey>,
+    tech_contacts: Vec<String>,
+    auth_info: Option<&str>,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<CreateResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::KeySetCreate(Box::new(CreateRequest {
+            id: id.to_string(),
+            keys,
+            tech_contacts,
+            auth_info: auth_info.map(Into::into),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Deletes a KEYSET
+///
+/// # Arguments
+/// * `id` - The ID of the KEYSET
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn delete(
+    id: &str,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<DeleteResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::KeySetDelete(Box::new(DeleteRequest {
+            id: id.to_string(),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Updates the properties of a KEYSET
+///
+/// # Arguments
+/// * `id` - The ID of the KEYSET
+/// * `add_keys` - DNSKEY records to add to the set
+/// * `remove_keys` - DNSKEY records to remove from the set
+/// * `add_tech_contacts` - Technical contacts to add
+/// * `remove_tech_contacts` - Technical contacts to remove
+/// * `new_auth_info` - New auth info
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn update(
+    id: &str,
+    add_keys: Vec<DNSKey>,
+    remove_keys: Vec<DNSKey>,
+    add_tech_contacts: Vec<String>,
+    remove_tech_contacts: Vec<String>,
+    new_auth_info: Option<&str>,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<UpdateResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::KeySetUpdate(Box::new(UpdateRequest {
+            id: id.to_string(),
+            add_keys,
+            remove_keys,
+            add_tech_contacts,
+            remove_tech_contacts,
+            new_auth_info: new_auth_info.map(Into::into),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Transfers a KEYSET to this registrar
+///
+/// # Arguments
+/// * `id` - The ID of the KEYSET
+/// * `auth_info` - Auth info of the KEYSET
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn transfer(
+    id: &str,
+    auth_info: &str,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<TransferResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::KeySetTransfer(Box::new(TransferRequest {
+            id: id.to_string(),
+            auth_info: auth_info.to_string(),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
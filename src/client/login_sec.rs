@@ -0,0 +1,474 @@
+//! Login security events reported by the server, and rotating the login password before it expires
+//!
+//! Servers supporting RFC 8807 may attach events to the login response, such as a warning that
+//! the password is about to expire. When rotation is configured the first session of a registry
+//! generates a new password once such a warning is received, and at its next login stores it in a
+//! [`SecretSink`] as pending before setting it on the server, so a password the server accepted
+//! is never only held in memory. Once the server accepts it the sink stores it as current. Other
+//! sessions of the registry pick up the new password on their next login.
+
+use chrono::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Type of a login security event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginSecurityEventType {
+    /// The password is expiring or has expired
+    Password,
+    /// The client certificate is expiring or has expired
+    Certificate,
+    /// The TLS cipher is insecure
+    Cipher,
+    /// The TLS protocol version is insecure
+    TLSProtocol,
+    /// The new password didn't meet the server's policy
+    NewPassword,
+    /// Statistics about the client's logins, such as failed attempts
+    Statistical,
+    /// Server defined event, see the event name
+    Custom,
+}
+
+impl LoginSecurityEventType {
+    /// Name of the event type, as used in metric labels
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoginSecurityEventType::Password => "password",
+            LoginSecurityEventType::Certificate => "certificate",
+            LoginSecurityEventType::Cipher => "cipher",
+            LoginSecurityEventType::TLSProtocol => "tls_protocol",
+            LoginSecurityEventType::NewPassword => "new_password",
+            LoginSecurityEventType::Statistical => "statistical",
+            LoginSecurityEventType::Custom => "custom",
+        }
+    }
+}
+
+/// Severity of a login security event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginSecurityEventLevel {
+    /// Something will need action soon
+    Warning,
+    /// Something needs action now
+    Error,
+}
+
+impl LoginSecurityEventLevel {
+    /// Name of the level, as used in metric labels
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoginSecurityEventLevel::Warning => "warning",
+            LoginSecurityEventLevel::Error => "error",
+        }
+    }
+}
+
+/// Login security event returned by the server at login
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoginSecurityEvent {
+    pub event_type: LoginSecurityEventType,
+    /// Name of a custom or statistical event
+    pub name: Option<String>,
+    pub level: LoginSecurityEventLevel,
+    /// When the password or certificate expires
+    pub expiration_date: Option<DateTime<Utc>>,
+    /// Value that caused the event, such as the cipher or statistic
+    pub value: Option<String>,
+    /// Duration the event applies to, such as the period of a statistic
+    pub duration: Option<String>,
+    /// Human readable description from the server
+    pub message: Option<String>,
+    /// When the event was received
+    pub received: DateTime<Utc>,
+}
+
+/// Somewhere to keep a new password, before and after it has been set on the server
+#[tonic::async_trait]
+pub trait SecretSink: std::fmt::Debug + Send + Sync {
+    /// Keeps a password about to be set on the server, without replacing the current one
+    async fn store_pending(&self, secret: &str) -> std::io::Result<()>;
+    /// Stores a password the server has accepted as the current one
+    async fn store(&self, secret: &str) -> std::io::Result<()>;
+    /// Drops a pending password the server rejected
+    async fn discard_pending(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Overwrites a file with the secret, keeping a pending secret in the file with `.pending` added
+/// to its name
+#[derive(Debug, Clone)]
+pub struct FileSecretSink {
+    path: std::path::PathBuf,
+}
+
+impl FileSecretSink {
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn with_suffix(&self, suffix: &str) -> std::path::PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(suffix);
+        std::path::PathBuf::from(name)
+    }
+
+    fn pending_path(&self) -> std::path::PathBuf {
+        self.with_suffix(".pending")
+    }
+
+    /// Writes next to the file and moves it into place, so the old secret survives a failed write
+    async fn write(&self, path: &std::path::Path, secret: &str) -> std::io::Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let tmp_path = self.with_suffix(".tmp");
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(&tmp_path).await?;
+        file.write_all(secret.as_bytes()).await?;
+        file.sync_all().await?;
+        tokio::fs::rename(&tmp_path, path).await
+    }
+}
+
+#[tonic::async_trait]
+impl SecretSink for FileSecretSink {
+    async fn store_pending(&self, secret: &str) -> std::io::Result<()> {
+        self.write(&self.pending_path(), secret).await
+    }
+
+    async fn store(&self, secret: &str) -> std::io::Result<()> {
+        self.write(&self.path, secret).await?;
+        self.discard_pending().await
+    }
+
+    async fn discard_pending(&self) -> std::io::Result<()> {
+        match tokio::fs::remove_file(self.pending_path()).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Runs a command with the secret on its standard input, such as a secret manager's CLI
+///
+/// The `EPP_SECRET_STATE` environment variable of the command is `pending` for a password about
+/// to be set on the server, and `current` once the server has accepted it.
+#[derive(Debug, Clone)]
+pub struct CommandSecretSink {
+    command: Vec<String>,
+}
+
+impl CommandSecretSink {
+    pub fn new(command: Vec<String>) -> Self {
+        Self { command }
+    }
+
+    async fn run(&self, secret: &str, state: &'static str) -> std::io::Result<()> {
+        let command = self.command.clone();
+        let secret = secret.to_string();
+        tokio::task::spawn_blocking(move || -> std::io::Result<()> {
+            use std::io::Write;
+
+            let (program, args) = match command.split_first() {
+                Some(c) => c,
+                None => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "empty secret sink command",
+                    ))
+                }
+            };
+            let mut child = std::process::Command::new(program)
+                .args(args)
+                .env("EPP_SECRET_STATE", state)
+                .stdin(std::process::Stdio::piped())
+                .spawn()?;
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(secret.as_bytes())?;
+            }
+            let status = child.wait()?;
+            if status.success() {
+                Ok(())
+            } else {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("secret sink command exited with {}", status),
+                ))
+            }
+        })
+        .await?
+    }
+}
+
+#[tonic::async_trait]
+impl SecretSink for CommandSecretSink {
+    async fn store_pending(&self, secret: &str) -> std::io::Result<()> {
+        self.run(secret, "pending").await
+    }
+
+    async fn store(&self, secret: &str) -> std::io::Result<()> {
+        self.run(secret, "current").await
+    }
+}
+
+/// Longest password allowed by the EPP core schema, when login security isn't supported
+pub const MAX_BASE_PASSWORD_LENGTH: usize = 16;
+/// Shortest password that will be generated
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+const LOWER: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPER: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &[u8] = b"0123456789";
+// Leaves out whitespace, quotes, and anything that needs escaping in XML
+const SYMBOLS: &[u8] = b"!#$%()*+,-./:;=?@[]^_{|}~";
+
+/// Generates a random password with at least one lower case letter, upper case letter, digit,
+/// and symbol, as most registry password policies require
+///
+/// # Arguments
+/// * `length` - Number of characters, raised to [`MIN_PASSWORD_LENGTH`] if shorter
+pub fn generate_password(length: usize) -> String {
+    let mut rng = rand::thread_rng();
+    let length = length.max(MIN_PASSWORD_LENGTH);
+    let all = [LOWER, UPPER, DIGITS, SYMBOLS].concat();
+
+    let mut password = [LOWER, UPPER, DIGITS, SYMBOLS]
+        .iter()
+        .map(|class| *class.choose(&mut rng).unwrap())
+        .collect::<Vec<_>>();
+    while password.len() < length {
+        password.push(all[rng.gen_range(0..all.len())]);
+    }
+    password.shuffle(&mut rng);
+    String::from_utf8(password).unwrap()
+}
+
+/// Password rotation policy for a registry, shared between its sessions
+#[derive(Debug, Clone)]
+pub struct PasswordRotation {
+    sink: std::sync::Arc<dyn SecretSink>,
+    length: usize,
+    rotate_before: chrono::Duration,
+    /// Only the first session changes the password
+    owner: bool,
+    /// Password most recently set by rotation
+    current: std::sync::Arc<std::sync::RwLock<Option<String>>>,
+}
+
+impl PasswordRotation {
+    /// Creates a rotation policy for the first session of a registry
+    ///
+    /// # Arguments
+    /// * `sink` - Where to store new passwords
+    /// * `length` - Length of generated passwords
+    /// * `rotate_before` - How long before the password expires to change it
+    pub fn new(
+        sink: std::sync::Arc<dyn SecretSink>,
+        length: usize,
+        rotate_before: chrono::Duration,
+    ) -> Self {
+        Self {
+            sink,
+            length,
+            rotate_before,
+            owner: true,
+            current: Default::default(),
+        }
+    }
+
+    /// Handle for another session of the same registry, which uses rotated passwords but doesn't
+    /// change them itself
+    pub fn follower(&self) -> Self {
+        Self {
+            owner: false,
+            ..self.clone()
+        }
+    }
+
+    /// Do the events received at login call for a new password
+    ///
+    /// # Arguments
+    /// * `events` - Events from the last login
+    /// * `now` - Current time
+    pub fn is_due(&self, events: &[LoginSecurityEvent], now: DateTime<Utc>) -> bool {
+        self.owner
+            && events.iter().any(|e| {
+                e.event_type == LoginSecurityEventType::Password
+                    && match e.expiration_date {
+                        Some(d) => d - now <= self.rotate_before,
+                        None => true,
+                    }
+            })
+    }
+
+    /// Generates a new password for this registry
+    ///
+    /// # Arguments
+    /// * `max_length` - Longest password the server accepts
+    pub fn generate(&self, max_length: usize) -> String {
+        generate_password(self.length.min(max_length))
+    }
+
+    /// Stores a password in the sink as pending, before it is set on the server
+    pub async fn prepare(&self, password: &str) -> std::io::Result<()> {
+        self.sink.store_pending(password).await
+    }
+
+    /// Drops the pending password after the server rejected it
+    pub async fn rejected(&self) -> std::io::Result<()> {
+        self.sink.discard_pending().await
+    }
+
+    /// Records a password that has been set on the server, and stores it in the sink as current
+    pub async fn rotated(&self, password: &str) -> std::io::Result<()> {
+        match self.current.write() {
+            Ok(mut c) => *c = Some(password.to_string()),
+            Err(e) => *e.into_inner() = Some(password.to_string()),
+        }
+        self.sink.store(password).await
+    }
+
+    /// The password most recently set by rotation, if it has happened
+    pub fn current(&self) -> Option<String> {
+        match self.current.read() {
+            Ok(c) => c.clone(),
+            Err(e) => e.into_inner().clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod login_sec_tests {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MemorySink(std::sync::Mutex<Vec<String>>);
+
+    #[tonic::async_trait]
+    impl SecretSink for MemorySink {
+        async fn store_pending(&self, secret: &str) -> std::io::Result<()> {
+            self.0.lock().unwrap().push(format!("pending {}", secret));
+            Ok(())
+        }
+
+        async fn store(&self, secret: &str) -> std::io::Result<()> {
+            self.0.lock().unwrap().push(secret.to_string());
+            Ok(())
+        }
+    }
+
+    fn event(
+        event_type: LoginSecurityEventType,
+        expiration_date: Option<DateTime<Utc>>,
+    ) -> LoginSecurityEvent {
+        LoginSecurityEvent {
+            event_type,
+            name: None,
+            level: LoginSecurityEventLevel::Warning,
+            expiration_date,
+            value: None,
+            duration: None,
+            message: None,
+            received: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn generate_password() {
+        for length in &[0, 8, 16, 64] {
+            let password = super::generate_password(*length);
+            assert_eq!(password.len(), (*length).max(MIN_PASSWORD_LENGTH));
+            for class in &[LOWER, UPPER, DIGITS, SYMBOLS] {
+                assert!(password.bytes().any(|c| class.contains(&c)));
+            }
+            assert!(!password.contains(|c: char| "<>&'\" ".contains(c)));
+        }
+    }
+
+    #[test]
+    fn rotation_due() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let rotation = PasswordRotation::new(
+            std::sync::Arc::new(MemorySink::default()),
+            16,
+            chrono::Duration::days(14),
+        );
+
+        assert!(!rotation.is_due(&[], now));
+        assert!(!rotation.is_due(
+            &[event(
+                LoginSecurityEventType::Password,
+                Some(now + chrono::Duration::days(30))
+            )],
+            now
+        ));
+        assert!(rotation.is_due(
+            &[event(
+                LoginSecurityEventType::Password,
+                Some(now + chrono::Duration::days(7))
+            )],
+            now
+        ));
+        assert!(rotation.is_due(&[event(LoginSecurityEventType::Password, None)], now));
+        assert!(!rotation.is_due(
+            &[event(
+                LoginSecurityEventType::Certificate,
+                Some(now + chrono::Duration::days(7))
+            )],
+            now
+        ));
+        assert!(!rotation
+            .follower()
+            .is_due(&[event(LoginSecurityEventType::Password, None)], now));
+    }
+
+    #[tokio::test]
+    async fn rotation_shared() {
+        let sink = std::sync::Arc::new(MemorySink::default());
+        let rotation = PasswordRotation::new(sink.clone(), 16, chrono::Duration::days(14));
+        let follower = rotation.follower();
+
+        rotation.prepare("n3w-Password").await.unwrap();
+        assert_eq!(follower.current(), None);
+        rotation.rotated("n3w-Password").await.unwrap();
+        assert_eq!(follower.current().as_deref(), Some("n3w-Password"));
+        assert_eq!(
+            *sink.0.lock().unwrap(),
+            vec![
+                "pending n3w-Password".to_string(),
+                "n3w-Password".to_string()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn file_sink_pending() {
+        let dir = std::env::temp_dir().join(format!("epp-proxy-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir(&dir).unwrap();
+        let path = dir.join("password");
+        let pending_path = dir.join("password.pending");
+        let sink = FileSecretSink::new(&path);
+        std::fs::write(&path, "old-Password").unwrap();
+
+        sink.store_pending("n3w-Password").await.unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "old-Password");
+        assert_eq!(
+            std::fs::read_to_string(&pending_path).unwrap(),
+            "n3w-Password"
+        );
+        sink.discard_pending().await.unwrap();
+        assert!(!pending_path.exists());
+        sink.discard_pending().await.unwrap();
+
+        sink.store_pending("n3w-Password").await.unwrap();
+        sink.store("n3w-Password").await.unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "n3w-Password");
+        assert!(!pending_path.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -103,7 +103,7 @@ pub enum Description {
 /// # Arguments
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn list(
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<ListResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -123,7 +123,7 @@ pub async fn list(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn info(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<InfoResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -18,22 +18,31 @@ pub mod domain;
 pub mod email_forward;
 pub mod eurid;
 pub mod fee;
+pub mod fred;
 pub mod host;
 pub mod isnic;
+pub mod keyset;
 pub mod keysys;
 pub mod launch;
+pub mod login_sec;
 pub mod maintenance;
 pub mod mark;
 pub mod nominet;
+pub mod nsset;
 pub mod personal_registration;
 pub mod poll;
+pub mod pool;
 pub mod rgp;
+pub mod queue;
+pub mod reconnect;
 pub mod router;
+pub mod status;
 pub mod tmch;
 pub mod traficom;
 pub mod verisign;
 
-pub use router::{CommandResponse, RequestMessage, RequestSender, Response, Sender};
+pub use queue::RequestSender;
+pub use router::{CommandResponse, RequestMessage, Response, Sender};
 
 pub enum ClientCertConf<'a> {
     /// PCKS#12 file path for client identity
@@ -77,19 +86,77 @@ pub struct ClientConf<'a, C: Into<Option<&'a str>>, M: crate::metrics::Metrics>
     pub nominet_dac: Option<NominetDACConf<'a>>,
     /// Should the client send keepalive commands automatically
     pub keepalive: bool,
+    /// Waiting queue for requests made while the client is reconnecting
+    pub queue: queue::QueueConf,
+    pub timeouts: Timeouts,
+    /// Backoff and circuit breaker policy for reconnecting
+    pub reconnect: reconnect::ReconnectConf,
+    /// Change the password automatically when the server warns it is expiring
+    pub password_rotation: Option<login_sec::PasswordRotation>,
+}
+
+/// Timeouts used by a client
+#[derive(Debug, Clone, Copy)]
+pub struct Timeouts {
+    /// How long a caller will wait for a response to a request
+    pub request: std::time::Duration,
+    /// How long to wait for the server to respond to a command before reconnecting
+    pub response: std::time::Duration,
+    /// How often to send a keepalive command
+    pub keepalive_interval: std::time::Duration,
+    /// How long to wait before reconnecting after a connection failure
+    pub reconnect_delay: std::time::Duration,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            request: std::time::Duration::from_secs(60),
+            response: std::time::Duration::from_secs(15),
+            keepalive_interval: std::time::Duration::from_secs(120),
+            reconnect_delay: std::time::Duration::from_secs(5),
+        }
+    }
+}
+
+tokio::task_local! {
+    static REQUEST_TIMEOUT: std::time::Duration;
+}
+
+/// Runs a future with all client requests made from it using the given timeout, instead of the
+/// default for the registry
+///
+/// # Arguments
+/// * `timeout` - How long to wait for a response to each request
+/// * `f` - Future to run
+pub async fn with_timeout<F: std::future::Future>(timeout: std::time::Duration, f: F) -> F::Output {
+    REQUEST_TIMEOUT.scope(timeout, f).await
 }
 
 async fn send_epp_client_request<R>(
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut RequestSender,
     req: RequestMessage,
     receiver: futures::channel::oneshot::Receiver<Response<R>>,
 ) -> Result<R, Error> {
-    match client_sender.try_send(req) {
-        Ok(_) => {}
-        Err(_) => return Err(Error::ServerInternal),
+    let timeout = REQUEST_TIMEOUT
+        .try_with(|t| *t)
+        .unwrap_or_else(|_| client_sender.timeout());
+    let mut delay = Box::pin(tokio::time::sleep(timeout).fuse());
+    {
+        let send_fut = client_sender.send(req).fuse();
+        futures::pin_mut!(send_fut);
+        futures::select! {
+            r = send_fut => {
+                if r.is_err() {
+                    return Err(Error::ServerInternal);
+                }
+            }
+            _ = delay => {
+                return Err(Error::Timeout);
+            }
+        };
     }
     let mut receiver = receiver.fuse();
-    let mut delay = Box::pin(tokio::time::sleep(tokio::time::Duration::new(60, 0)).fuse());
     let resp = futures::select! {
         r = receiver => r,
         _ = delay => {
@@ -115,6 +182,48 @@ pub enum Error {
     Timeout,
     /// The EPP server returned an error message (probably invalid parameters)
     Err(String),
+    /// The EPP server returned an error result for the command
+    EPP(EPPError),
+}
+
+/// Error result returned by the EPP server for a command
+#[derive(Debug)]
+pub struct EPPError {
+    /// EPP result code, such as 2302 if the object already exists
+    pub code: u16,
+    /// Message from the server describing the result
+    pub message: String,
+    /// Values from the command the server objected to, and why
+    pub reasons: Vec<router::CommandExtraValue>,
+    pub transaction_id: router::CommandTransactionID,
+}
+
+impl EPPError {
+    /// Did the command fail because of a problem on the server, rather than with the command
+    pub fn is_server_error(&self) -> bool {
+        self.code >= 2400
+    }
+}
+
+impl std::fmt::Display for EPPError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_server_error() {
+            write!(f, "Server error: ")?;
+        }
+        write!(f, "{}", self.message)?;
+        if !self.reasons.is_empty() {
+            write!(
+                f,
+                ": {}",
+                self.reasons
+                    .iter()
+                    .map(|e| format!("({}) {}", e.value, e.reason))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -127,7 +236,7 @@ pub enum TransferStatus {
     ServerCancelled,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Period {
     /// Unit of time
     pub unit: PeriodUnit,
@@ -135,7 +244,7 @@ pub struct Period {
     pub value: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum PeriodUnit {
     Years,
     Months,
@@ -159,7 +268,7 @@ pub struct BlankRequest {
 /// # Arguments
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn logout(
-    mut client_sender: futures::channel::mpsc::Sender<RequestMessage>,
+    mut client_sender: RequestSender,
 ) -> Result<CommandResponse<()>, Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     send_epp_client_request(
@@ -172,11 +281,14 @@ pub async fn logout(
     .await
 }
 
-pub trait Client {
+pub trait Client: Send {
+    /// Handle to the status published by this client, to be taken before it is started
+    fn status(&self) -> status::StatusHandle;
+
     fn start(
         self: Box<Self>,
     ) -> (
-        futures::channel::mpsc::Sender<RequestMessage>,
+        RequestSender,
         futures::channel::mpsc::UnboundedReceiver<router::CommandTransactionID>,
     );
 }
@@ -112,6 +112,68 @@ pub struct LockRequest {
 #[derive(Debug)]
 pub struct LockResponse {}
 
+#[derive(Debug)]
+pub struct UnrenewRequest {
+    pub(super) domains: Vec<String>,
+    pub return_path: Sender<UnrenewResponse>,
+}
+
+/// Response to an unrenew request
+#[derive(Debug)]
+pub struct UnrenewResponse {
+    /// Domains unrenewed
+    pub domains: Vec<UnrenewedDomain>,
+}
+
+#[derive(Debug)]
+pub struct UnrenewedDomain {
+    /// Domain name
+    pub name: String,
+    /// New expiry date of the domain
+    pub expiry_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+pub struct ListRequest {
+    pub(super) list_type: ListType,
+    pub(super) year: i32,
+    pub(super) month: u32,
+    pub return_path: Sender<ListResponse>,
+}
+
+/// Which date domains are listed by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListType {
+    /// Month the domain was registered in
+    Registration,
+    /// Month the domain expires in
+    Expiry,
+}
+
+/// Response to a domain list request
+#[derive(Debug)]
+pub struct ListResponse {
+    /// Domains registered or expiring in the requested month
+    pub domains: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct ForkRequest {
+    pub(super) contact_id: String,
+    pub(super) new_contact_id: Option<String>,
+    pub(super) domains: Vec<String>,
+    pub return_path: Sender<ForkResponse>,
+}
+
+/// Response to a contact fork request
+#[derive(Debug)]
+pub struct ForkResponse {
+    /// ID of the newly created contact
+    pub new_contact_id: Option<String>,
+    /// Creation date of the new contact
+    pub creation_date: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug)]
 pub struct TagListRequest {
     pub return_path: Sender<TagListResponse>,
@@ -205,6 +267,21 @@ pub struct RegistrantTransferData {
     pub contact: super::contact::InfoResponse,
 }
 
+#[derive(Debug)]
+pub struct AbuseFeedData {
+    pub key: String,
+    pub activity: String,
+    pub source: String,
+    pub hostname: String,
+    pub url: Option<String>,
+    pub date: DateTime<Utc>,
+    pub ip: Option<String>,
+    pub nameserver: Option<String>,
+    pub dns_admin: Option<String>,
+    pub target: Option<String>,
+    pub whole_domain: Option<String>,
+}
+
 #[derive(Debug)]
 pub enum DataQualityStatus {
     Valid,
@@ -230,7 +307,7 @@ pub struct DataQualityData {
 pub async fn handshake_accept(
     case_id: &str,
     registrant: Option<&str>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<HandshakeResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -252,7 +329,7 @@ pub async fn handshake_accept(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn handshake_reject(
     case_id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<HandshakeResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -275,7 +352,7 @@ pub async fn handshake_reject(
 pub async fn release(
     registrar_tag: &str,
     object: Object,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<ReleaseResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -295,7 +372,7 @@ pub async fn release(
 /// # Arguments
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn tag_list(
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TagListResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -315,7 +392,7 @@ pub async fn tag_list(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn contact_validate(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<ContactValidateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -338,7 +415,7 @@ pub async fn contact_validate(
 pub async fn lock(
     object: Object,
     lock_type: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<LockResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -362,7 +439,7 @@ pub async fn lock(
 pub async fn unlock(
     object: Object,
     lock_type: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<LockResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -376,3 +453,78 @@ pub async fn unlock(
     )
     .await
 }
+
+/// Reverses the renewal of domains, returning them to their previous expiry date
+///
+/// # Arguments
+/// * `domains` - Domain names to unrenew
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn unrenew(
+    domains: Vec<String>,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<UnrenewResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::NominetUnrenew(Box::new(UnrenewRequest {
+            domains,
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Lists domains registered or expiring in a given month
+///
+/// # Arguments
+/// * `list_type` - Whether to list by registration or expiry date
+/// * `year` - Year to list domains for
+/// * `month` - Month to list domains for, from 1 to 12
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn list_domains(
+    list_type: ListType,
+    year: i32,
+    month: u32,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<ListResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::NominetList(Box::new(ListRequest {
+            list_type,
+            year,
+            month,
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Moves domains off a contact shared between domains onto a new copy of the contact
+///
+/// # Arguments
+/// * `contact_id` - ID of the existing contact
+/// * `new_contact_id` - Optional ID for the new contact, one will be assigned if not given
+/// * `domains` - Domains to move to the new contact
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn fork(
+    contact_id: &str,
+    new_contact_id: Option<&str>,
+    domains: &[&str],
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<ForkResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::NominetFork(Box::new(ForkRequest {
+            contact_id: contact_id.to_owned(),
+            new_contact_id: new_contact_id.map(Into::into),
+            domains: domains.iter().map(|d| d.to_string()).collect(),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
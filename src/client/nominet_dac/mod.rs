@@ -1,4 +1,4 @@
-use super::{router as outer_router, RequestMessage};
+use super::router as outer_router;
 use futures::future::FutureExt;
 use futures::stream::StreamExt;
 use futures::SinkExt;
@@ -11,20 +11,27 @@ mod router;
 #[derive(Debug)]
 pub struct DACClient<M: crate::metrics::Metrics> {
     metrics_registry: M,
+    status: super::status::StatusHandle,
     router: outer_router::Router<router::Router, (), M>,
     is_closing: bool,
+    timeouts: super::Timeouts,
+    backoff: super::reconnect::Backoff,
     source_addr: Option<std::net::IpAddr>,
     rt_host: String,
     td_host: String,
 }
 
 impl<M: crate::metrics::Metrics + 'static> super::Client for DACClient<M> {
+    fn status(&self) -> super::status::StatusHandle {
+        self.status.clone()
+    }
+
     // Starts up the DAC client and returns the sending end of a tokio channel to inject
     // commands into the client to be processed
     fn start(
         mut self: Box<Self>,
     ) -> (
-        futures::channel::mpsc::Sender<RequestMessage>,
+        super::RequestSender,
         futures::channel::mpsc::UnboundedReceiver<outer_router::CommandTransactionID>,
     ) {
         info!(
@@ -32,7 +39,7 @@ impl<M: crate::metrics::Metrics + 'static> super::Client for DACClient<M> {
             &self.rt_host, &self.td_host
         );
         self.metrics_registry.connection_status(false);
-        let (sender, receiver) = futures::channel::mpsc::channel::<RequestMessage>(16);
+        let (sender, receiver) = super::queue::channel(16, self.timeouts.request);
         let (ready_sender, ready_receiver) = futures::channel::mpsc::unbounded();
         tokio::spawn(async move {
             self._main_loop(receiver, ready_sender).await;
@@ -47,16 +54,23 @@ impl<M: crate::metrics::Metrics + 'static> DACClient<M> {
     /// # Arguments
     /// * `rt_host` - Hostname and port of the real time server
     /// * `td_host` - Hostname and port of the time delay server
+    /// * `timeouts` - Timeouts to use
+    /// * `reconnect` - Reconnect policy to follow
     pub async fn new(
         rt_host: &str,
         td_host: &str,
         source_addr: Option<&std::net::IpAddr>,
+        timeouts: super::Timeouts,
+        reconnect: super::reconnect::ReconnectConf,
         metrics_registry: M,
     ) -> std::io::Result<Self> {
         Ok(Self {
             router: outer_router::Router::new(&metrics_registry),
             metrics_registry,
+            status: Default::default(),
             is_closing: false,
+            timeouts,
+            backoff: super::reconnect::Backoff::new(timeouts.reconnect_delay, reconnect),
             rt_host: rt_host.to_string(),
             td_host: td_host.to_string(),
             source_addr: source_addr.map(|a| a.to_owned()),
@@ -65,12 +79,15 @@ impl<M: crate::metrics::Metrics + 'static> DACClient<M> {
 
     async fn _main_loop(
         &mut self,
-        receiver: futures::channel::mpsc::Receiver<RequestMessage>,
+        receiver: super::queue::RequestReceiver,
         mut ready_sender: futures::channel::mpsc::UnboundedSender<
             outer_router::CommandTransactionID,
         >,
     ) {
         let mut receiver = receiver.fuse();
+        let mut waiting = super::queue::WaitingQueue::new(Default::default());
+        let mut expire_interval = tokio::time::interval(tokio::time::Duration::new(1, 0));
+        let mut retry_delay = None;
         loop {
             self.is_closing = false;
 
@@ -80,20 +97,39 @@ impl<M: crate::metrics::Metrics + 'static> DACClient<M> {
                     self.rt_host,
                     self.td_host
                 );
-                let connect_fut = self._connect().fuse();
+                // The wait after a failure is part of connecting, so that requests keep being
+                // queued, or rejected while the breaker is open
+                let delay = retry_delay.take();
+                let connect_fut = async {
+                    if let Some(delay) = delay {
+                        self.backoff.wait(delay, &self.metrics_registry).await;
+                    }
+                    self._connect().await
+                }
+                .fuse();
                 futures::pin_mut!(connect_fut);
 
                 loop {
                     futures::select! {
                         x = receiver.next() => {
                             match x {
-                                Some(x) => outer_router::Router::<router::Router, (), M>::reject_request(x),
+                                Some((_, x)) if self.backoff.state() == super::reconnect::BreakerState::Open => {
+                                    x.reject(super::Error::NotReady)
+                                }
+                                Some((ctx, x)) => waiting.push(ctx, x),
                                 None => {
                                     info!("All senders for {}/{} dropped, exiting...", self.rt_host, self.td_host);
                                     return
                                 }
                             };
                         }
+                        _ = expire_interval.tick().fuse() => {
+                            if self.backoff.state() == super::reconnect::BreakerState::Open {
+                                waiting.reject_all();
+                            } else {
+                                waiting.expire();
+                            }
+                        }
                         s = connect_fut => {
                             break s;
                         }
@@ -101,7 +137,9 @@ impl<M: crate::metrics::Metrics + 'static> DACClient<M> {
                 }
             };
             trace!("Got connection for {} and {}", self.rt_host, self.td_host);
+            self.backoff.success(&self.metrics_registry);
             self.metrics_registry.connection_status(true);
+            self.status.set_connected(true);
             let _ = ready_sender
                 .send(outer_router::CommandTransactionID {
                     client: "".to_string(),
@@ -128,15 +166,19 @@ impl<M: crate::metrics::Metrics + 'static> DACClient<M> {
 
             trace!("Entering event loop for {}/{}", self.rt_host, self.td_host);
             loop {
+                if let Some((ctx, r)) = waiting.pop() {
+                    match self._handle_request(ctx, r, &mut rt_sock_write, &mut td_sock_write).await {
+                        Ok(_) => {}
+                        Err(_) => break,
+                    }
+                    continue;
+                }
                 futures::select! {
                     r = receiver.next() => {
                         match r {
-                            Some(r) => match self._handle_request(r, &mut rt_sock_write, &mut td_sock_write).await {
+                            Some((ctx, r)) => match self._handle_request(ctx, r, &mut rt_sock_write, &mut td_sock_write).await {
                                 Ok(_) => {},
-                                Err(_) => {
-                                    tokio::time::sleep(tokio::time::Duration::new(5, 0)).await;
-                                    break;
-                                }
+                                Err(_) => break,
                             },
                             None => {
                                 info!("All senders for {}/{} dropped, exiting...", self.rt_host, self.td_host);
@@ -187,17 +229,22 @@ impl<M: crate::metrics::Metrics + 'static> DACClient<M> {
                 }
             }
             self.metrics_registry.connection_status(false);
-            tokio::time::sleep(tokio::time::Duration::new(5, 0)).await;
+            self.status.set_connected(false);
+            retry_delay = Some(self.backoff.failure(&self.metrics_registry));
         }
     }
 
     async fn _handle_request<W: Unpin + tokio::io::AsyncWrite>(
         &mut self,
+        ctx: super::queue::RequestContext,
         req: outer_router::RequestMessage,
         rt_sock_write: &mut W,
         td_sock_write: &mut W,
     ) -> Result<(), ()> {
-        match self.router.handle_request(&(), req) {
+        match self
+            .router
+            .handle_request(&(), req, ctx.client_transaction_id.as_ref())
+        {
             Some(((command, env), _)) => {
                 if env == router::DACEnv::RealTime || env == router::DACEnv::Both {
                     match self._send_command(command.clone(), rt_sock_write).await {
@@ -290,7 +337,8 @@ impl<M: crate::metrics::Metrics + 'static> DACClient<M> {
                     return s;
                 }
                 Err(_) => {
-                    tokio::time::sleep(tokio::time::Duration::new(5, 0)).await;
+                    let delay = self.backoff.failure(&self.metrics_registry);
+                    self.backoff.wait(delay, &self.metrics_registry).await;
                 }
             }
         }
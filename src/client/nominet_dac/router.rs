@@ -30,14 +30,14 @@ macro_rules! router {
     ($($n:ident);*) => {
         #[derive(Default, Debug)]
         pub struct Router {
-            pub(super) command_map: std::collections::HashMap<DACKey, uuid::Uuid>
+            pub(super) command_map: std::collections::HashMap<DACKey, String>
         }
 
         impl<M: crate::metrics::Metrics> router::InnerRouter<(), M> for Router {
             type Request = (super::proto::DACRequest, DACEnv);
             type Response = super::proto::DACResponse;
 
-            fn Logout_request(&mut self, _client: &(), _req: &router::LogoutRequest, command_id: uuid::Uuid) -> HandleReqReturn<router::LogoutResponse> {
+            fn Logout_request(&mut self, _client: &(), _req: &router::LogoutRequest, command_id: String) -> HandleReqReturn<router::LogoutResponse> {
                 self.command_map.insert(DACKey {
                     env: DACEnv::Both,
                     cmd: "#exit".to_string(),
@@ -52,11 +52,12 @@ macro_rules! router {
                 let _ = return_path.send(Ok(router::CommandResponse {
                     response: (),
                     extra_values: vec![],
+                    unhandled_namespaces: vec![],
                     transaction_id: None,
                 }));
             }
 
-            fn DomainCheck_request(&mut self, _client: &(), req: &router::DomainCheckRequest, command_id: uuid::Uuid) -> HandleReqReturn<router::DomainCheckResponse> {
+            fn DomainCheck_request(&mut self, _client: &(), req: &router::DomainCheckRequest, command_id: String) -> HandleReqReturn<router::DomainCheckResponse> {
                 if req.fee_check.is_some() {
                     return Err(Err(Error::Unsupported));
                 }
@@ -87,6 +88,7 @@ macro_rules! router {
                                 eurid_idn: None,
                             },
                             extra_values: vec![],
+                            unhandled_namespaces: vec![],
                             transaction_id: None,
                         }));
                     },
@@ -111,6 +113,7 @@ macro_rules! router {
                                 eurid_idn: None,
                             },
                             extra_values: vec![],
+                            unhandled_namespaces: vec![],
                             transaction_id: None,
                         }));
                     },
@@ -123,7 +126,7 @@ macro_rules! router {
                 }
             }
 
-            fn DACDomain_request(&mut self, _client: &(), req: &router::DACDomainRequest, command_id: uuid::Uuid) -> HandleReqReturn<router::DACDomainResponse> {
+            fn DACDomain_request(&mut self, _client: &(), req: &router::DACDomainRequest, command_id: String) -> HandleReqReturn<router::DACDomainResponse> {
                 self.command_map.insert(DACKey {
                     env: req.env.into(),
                     cmd: req.domain.clone()
@@ -152,6 +155,7 @@ macro_rules! router {
                                 tag: d.tag,
                             },
                             extra_values: vec![],
+                            unhandled_namespaces: vec![],
                             transaction_id: None,
                         }));
                     },
@@ -177,6 +181,7 @@ macro_rules! router {
                                 tag: d.tag,
                             },
                             extra_values: vec![],
+                            unhandled_namespaces: vec![],
                             transaction_id: None,
                         }));
                     },
@@ -189,7 +194,7 @@ macro_rules! router {
                 }
             }
 
-            fn DACUsage_request(&mut self, _client: &(), req: &router::DACUsageRequest, command_id: uuid::Uuid) -> HandleReqReturn<router::DACUsageResponse> {
+            fn DACUsage_request(&mut self, _client: &(), req: &router::DACUsageRequest, command_id: String) -> HandleReqReturn<router::DACUsageResponse> {
                 self.command_map.insert(DACKey {
                     env: req.env.into(),
                     cmd: "#usage".to_string()
@@ -209,6 +214,7 @@ macro_rules! router {
                                 usage_24: u.usage_24,
                             },
                             extra_values: vec![],
+                            unhandled_namespaces: vec![],
                             transaction_id: None,
                         }));
                     },
@@ -221,7 +227,7 @@ macro_rules! router {
                 }
             }
 
-            fn DACLimits_request(&mut self, _client: &(), req: &router::DACLimitsRequest, command_id: uuid::Uuid) -> HandleReqReturn<router::DACLimitsResponse> {
+            fn DACLimits_request(&mut self, _client: &(), req: &router::DACLimitsRequest, command_id: String) -> HandleReqReturn<router::DACLimitsResponse> {
                 self.command_map.insert(DACKey {
                     env: req.env.into(),
                     cmd: "#limits".to_string()
@@ -241,6 +247,7 @@ macro_rules! router {
                                 usage_24: u.usage_24,
                             },
                             extra_values: vec![],
+                            unhandled_namespaces: vec![],
                             transaction_id: None,
                         }));
                     },
@@ -254,7 +261,7 @@ macro_rules! router {
             }
 
             paste! {
-                $(fn [<$n _request>](&mut self, _client: &(), _req: &router::[<$n Request>], _command_id: uuid::Uuid) -> HandleReqReturn<router::[<$n Response>]> {
+                $(fn [<$n _request>](&mut self, _client: &(), _req: &router::[<$n Request>], _command_id: String) -> HandleReqReturn<router::[<$n Response>]> {
                     Err(Response::Err(Error::Unsupported))
                 })*
 
@@ -272,6 +279,7 @@ macro_rules! router {
 router!(
     Poll;
     PollAck;
+    DomainBatchCheck;
     DomainClaimsCheck;
     DomainTrademarkCheck;
     DomainInfo;
@@ -299,11 +307,25 @@ router!(
     RestoreRequest;
     RestoreReport;
     HostCheck;
+    HostBatchCheck;
     HostInfo;
     HostCreate;
     HostDelete;
     HostUpdate;
+    NSSetCheck;
+    NSSetInfo;
+    NSSetCreate;
+    NSSetDelete;
+    NSSetUpdate;
+    NSSetTransfer;
+    KeySetCheck;
+    KeySetInfo;
+    KeySetCreate;
+    KeySetDelete;
+    KeySetUpdate;
+    KeySetTransfer;
     ContactCheck;
+    ContactBatchCheck;
     ContactInfo;
     ContactCreate;
     ContactDelete;
@@ -319,6 +341,9 @@ router!(
     NominetContactValidate;
     NominetLock;
     NominetUnlock;
+    NominetUnrenew;
+    NominetList;
+    NominetFork;
     Balance;
     MaintenanceList;
     MaintenanceInfo;
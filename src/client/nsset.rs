@@ -0,0 +1,268 @@
+//! EPP commands relating to FRED NSSET (nameserver set) objects
+
+use super::{CommandResponse, RequestMessage, Sender};
+use chrono::prelude::*;
+
+#[derive(Debug)]
+pub struct CheckRequest {
+    pub(super) id: String,
+    pub return_path: Sender<CheckResponse>,
+}
+
+#[derive(Debug)]
+pub struct CheckResponse {
+    pub avail: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct InfoRequest {
+    pub(super) id: String,
+    pub return_path: Sender<InfoResponse>,
+}
+
+#[derive(Debug)]
+pub struct InfoResponse {
+    pub id: String,
+    pub registry_id: String,
+    pub statuses: Vec<String>,
+    pub client_id: String,
+    pub client_created_id: Option<String>,
+    pub creation_date: Option<DateTime<Utc>>,
+    pub last_updated_client: Option<String>,
+    pub last_updated_date: Option<DateTime<Utc>>,
+    pub last_transfer_date: Option<DateTime<Utc>>,
+    pub auth_info: Option<String>,
+    pub nameservers: Vec<Nameserver>,
+    pub tech_contacts: Vec<String>,
+    pub report_level: Option<u8>,
+}
+
+#[derive(Debug)]
+pub struct Nameserver {
+    /// Host name of the nameserver
+    pub name: String,
+    /// Glue addresses, only needed for nameservers under the domain they serve
+    pub addresses: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct CreateRequest {
+    pub(super) id: String,
+    pub(super) nameservers: Vec<Nameserver>,
+    pub(super) tech_contacts: Vec<String>,
+    pub(super) auth_info: Option<String>,
+    pub(super) report_level: Option<u8>,
+    pub return_path: Sender<CreateResponse>,
+}
+
+#[derive(Debug)]
+pub struct CreateResponse {
+    pub id: String,
+    pub pending: bool,
+    pub transaction_id: String,
+    pub creation_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+pub struct DeleteRequest {
+    pub(super) id: String,
+    pub return_path: Sender<DeleteResponse>,
+}
+
+#[derive(Debug)]
+pub struct DeleteResponse {
+    pub pending: bool,
+    pub transaction_id: String,
+}
+
+#[derive(Debug)]
+pub struct UpdateRequest {
+    pub(super) id: String,
+    pub(super) add_nameservers: Vec<Nameserver>,
+    pub(super) remove_nameservers: Vec<String>,
+    pub(super) add_tech_contacts: Vec<String>,
+    pub(super) remove_tech_contacts: Vec<String>,
+    pub(super) new_auth_info: Option<String>,
+    pub(super) new_report_level: Option<u8>,
+    pub return_path: Sender<UpdateResponse>,
+}
+
+#[derive(Debug)]
+pub struct UpdateResponse {
+    pub pending: bool,
+    pub transaction_id: String,
+}
+
+#[derive(Debug)]
+pub struct TransferRequest {
+    pub(super) id: String,
+    pub(super) auth_info: String,
+    pub return_path: Sender<TransferResponse>,
+}
+
+#[derive(Debug)]
+pub struct TransferResponse {
+    pub pending: bool,
+    pub transaction_id: String,
+}
+
+/// Checks if an NSSET ID is available
+///
+/// # Arguments
+/// * `id` - The NSSET ID to check
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn check(
+    id: &str,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<CheckResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::NSSetCheck(Box::new(CheckRequest {
+            id: id.to_string(),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Fetches information about a specific NSSET
+///
+/// # Arguments
+/// * `id` - The ID of the NSSET
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn info(
+    id: &str,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<InfoResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::NSSetInfo(Box::new(InfoRequest {
+            id: id.to_string(),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Creates a new NSSET
+///
+/// # Arguments
+/// * `id` - The ID of the new NSSET
+/// * `nameservers` - Nameservers in the set
+/// * `tech_contacts` - Contact IDs of the technical contacts
+/// * `auth_info` - Optional auth info, one will be generated by the registry if not given
+/// * `report_level` - Level of technical check results to report, from 0 to 10
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn create(
+    id: &str,
+    nameservers: Vec<Nameserver>,
+    tech_contacts: Vec<String>,
+    auth_info: Option<&str>,
+    report_level: Option<u8>,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<CreateResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::NSSetCreate(Box::new(CreateRequest {
+            id: id.to_string(),
+            nameservers,
+            tech_contacts,
+            auth_info: auth_info.map(Into::into),
+            report_level,
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Deletes an NSSET
+///
+/// # Arguments
+/// * `id` - The ID of the NSSET
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn delete(
+    id: &str,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<DeleteResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::NSSetDelete(Box::new(DeleteRequest {
+            id: id.to_string(),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Updates the properties of an NSSET
+///
+/// # Arguments
+/// * `id` - The ID of the NSSET
+/// * `add_nameservers` - Nameservers to add to the set
+/// * `remove_nameservers` - Host names of nameservers to remove from the set
+/// * `add_tech_contacts` - Technical contacts to add
+/// * `remove_tech_contacts` - Technical contacts to remove
+/// * `new_auth_info` - New auth info
+/// * `new_report_level` - New technical check report level
+/// * `client_sender` - Reference to the tokio channel into the client
+#[allow(clippy::too_many_arguments)]
+pub async fn update(
+    id: &str,
+    add_nameservers: Vec<Nameserver>,
+    remove_nameservers: Vec<String>,
+    add_tech_contacts: Vec<String>,
+    remove_tech_contacts: Vec<String>,
+    new_auth_info: Option<&str>,
+    new_report_level: Option<u8>,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<UpdateResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::NSSetUpdate(Box::new(UpdateRequest {
+            id: id.to_string(),
+            add_nameservers,
+            remove_nameservers,
+            add_tech_contacts,
+            remove_tech_contacts,
+            new_auth_info: new_auth_info.map(Into::into),
+            new_report_level,
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Transfers an NSSET to this registrar
+///
+/// # Arguments
+/// * `id` - The ID of the NSSET
+/// * `auth_info` - Auth info of the NSSET
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn transfer(
+    id: &str,
+    auth_info: &str,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<TransferResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::NSSetTransfer(Box::new(TransferRequest {
+            id: id.to_string(),
+            auth_info: auth_info.to_string(),
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
@@ -93,10 +93,18 @@ pub enum PollData {
         data: super::nominet::RegistrantTransferData,
         change_data: Option<ChangeData>,
     },
+    NominetAbuseFeedData(super::nominet::AbuseFeedData),
+    FredTransferData(super::fred::TransferData),
+    FredIdleDeleteData(super::fred::IdleDeleteData),
+    FredNSSetTestData(super::fred::NSSetTestData),
+    FredLowCreditData(super::fred::LowCreditData),
     VerisignLowBalanceData(super::verisign::LowBalanceData),
     TraficomTrnData(super::traficom::TrnData),
     MaintenanceData(super::maintenance::InfoResponse),
     EURIDPoll(super::eurid::PollResponse),
+    /// Message of a type not understood by the client, with the `<resData>` and `<extension>`
+    /// elements as sent by the server
+    Raw(String),
     None,
 }
 
@@ -173,7 +181,7 @@ pub struct PollAckResponse {
 /// # Arguments
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn poll(
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<Option<PollResponse>>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -193,7 +201,7 @@ pub async fn poll(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn poll_ack(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<PollAckResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
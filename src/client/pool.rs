@@ -0,0 +1,230 @@
+//! Pool of sessions to the same registry behind a single [`RequestSender`]
+//!
+//! Sessions are logged in one after the other, each once the previous one is ready, so the pool
+//! never holds more sessions than configured and a password change made by the first session is
+//! in place before the rest log in. Commands go to the connected session with the fewest commands
+//! in flight, except for poll traffic which always goes to the first session so that messages are
+//! acknowledged in the order they were dequeued.
+
+use super::router::CommandTransactionID;
+use super::{queue, Client, Error, RequestMessage, RequestSender};
+use futures::stream::StreamExt;
+use futures::SinkExt;
+
+/// Client spreading requests over several sessions to the same registry
+pub struct ClientPool {
+    sessions: Vec<Box<dyn Client>>,
+    timeout: std::time::Duration,
+}
+
+struct Session {
+    sender: RequestSender,
+    status: super::status::StatusHandle,
+}
+
+impl ClientPool {
+    /// Creates a new pool ready to be started
+    ///
+    /// # Arguments
+    /// * `sessions` - Clients for each session, the first of which will handle poll traffic
+    /// * `timeout` - Default time callers will wait for a response
+    pub fn new(sessions: Vec<Box<dyn Client>>, timeout: std::time::Duration) -> Self {
+        Self { sessions, timeout }
+    }
+}
+
+impl Client for ClientPool {
+    fn status(&self) -> super::status::StatusHandle {
+        super::status::StatusHandle::Pool(self.sessions.iter().map(|s| s.status()).collect())
+    }
+
+    fn start(
+        self: Box<Self>,
+    ) -> (
+        RequestSender,
+        futures::channel::mpsc::UnboundedReceiver<CommandTransactionID>,
+    ) {
+        info!("Starting pool of {} sessions", self.sessions.len());
+        let (sender, receiver) = queue::channel(16, self.timeout);
+        let (ready_sender, ready_receiver) = futures::channel::mpsc::unbounded();
+        tokio::spawn(Self::_main_loop(self.sessions, receiver, ready_sender));
+        (sender, ready_receiver)
+    }
+}
+
+impl ClientPool {
+    async fn _main_loop(
+        sessions: Vec<Box<dyn Client>>,
+        receiver: queue::RequestReceiver,
+        mut ready_sender: futures::channel::mpsc::UnboundedSender<CommandTransactionID>,
+    ) {
+        let mut receiver = receiver.fuse();
+        let mut pending = sessions.into_iter();
+        let mut started = vec![];
+        let mut ready = futures::stream::SelectAll::new();
+        let mut next_session = 0;
+
+        if let Some(session) = pending.next() {
+            let status = session.status();
+            let (sender, ready_receiver) = session.start();
+            started.push(Session { sender, status });
+            ready.push(ready_receiver);
+        } else {
+            return;
+        }
+
+        loop {
+            futures::select! {
+                r = receiver.next() => {
+                    match r {
+                        Some((ctx, RequestMessage::Logout(req))) => {
+                            // Sessions that never logged in are dropped rather than started
+                            pending = Vec::new().into_iter();
+                            let senders = started.iter().map(|s| s.sender.clone()).collect::<Vec<_>>();
+                            tokio::spawn(ctx.scope(async move {
+                                let res = futures::future::join_all(senders.into_iter().map(super::logout))
+                                    .await
+                                    .into_iter()
+                                    .reduce(|a, b| a.and(b))
+                                    .unwrap_or(Err(Error::NotReady));
+                                let _ = req.return_path.send(res);
+                            }));
+                        }
+                        Some((ctx, r)) => {
+                            let i = Self::_session_for(&r, &started, &mut next_session);
+                            // Sent from its own task so that a session with a full lane doesn't
+                            // hold up requests to the others
+                            let mut sender = started[i].sender.clone();
+                            tokio::spawn(ctx.scope(async move {
+                                if sender.send(r).await.is_err() {
+                                    warn!("Session {} of pool has exited", i);
+                                }
+                            }));
+                        }
+                        None => {
+                            info!("All senders for pool dropped, exiting...");
+                            return
+                        }
+                    }
+                }
+                t = ready.next() => {
+                    if let Some(t) = t {
+                        let _ = ready_sender.send(t).await;
+                        if let Some(session) = pending.next() {
+                            let status = session.status();
+                            let (sender, ready_receiver) = session.start();
+                            started.push(Session { sender, status });
+                            ready.push(ready_receiver);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Picks the session to send a request to, keeping poll traffic on the first session
+    fn _session_for(req: &RequestMessage, sessions: &[Session], next_session: &mut usize) -> usize {
+        match req {
+            RequestMessage::Poll(_) | RequestMessage::PollAck(_) => 0,
+            _ => Self::_pick_session(sessions, next_session),
+        }
+    }
+
+    /// Picks the connected session with the fewest commands in flight, round robin on a tie
+    fn _pick_session(sessions: &[Session], next_session: &mut usize) -> usize {
+        let statuses = sessions.iter().map(|s| s.status.get()).collect::<Vec<_>>();
+        let any_connected = statuses.iter().any(|s| s.connected);
+        let n = sessions.len();
+        let i = (0..n)
+            .map(|o| (*next_session + o) % n)
+            .filter(|&i| statuses[i].connected || !any_connected)
+            .min_by_key(|&i| statuses[i].pending_commands)
+            .unwrap_or(0);
+        *next_session = (i + 1) % n;
+        i
+    }
+}
+
+#[cfg(test)]
+mod pool_tests {
+    use super::*;
+    use crate::client::{poll, status, BlankRequest};
+
+    fn session(connected: bool, pending_commands: usize) -> Session {
+        let (sender, _) = queue::channel(1, std::time::Duration::from_secs(1));
+        Session {
+            sender,
+            status: status::StatusHandle::new(status::ClientStatus {
+                connected,
+                pending_commands,
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn pick_session() {
+        let mut next_session = 0;
+        let sessions = vec![session(true, 3), session(true, 1), session(false, 0)];
+        assert_eq!(ClientPool::_pick_session(&sessions, &mut next_session), 1);
+        assert_eq!(next_session, 2);
+
+        let sessions = vec![session(true, 0), session(true, 0), session(true, 0)];
+        let mut next_session = 0;
+        let picked = (0..4)
+            .map(|_| ClientPool::_pick_session(&sessions, &mut next_session))
+            .collect::<Vec<_>>();
+        assert_eq!(picked, vec![0, 1, 2, 0]);
+
+        let sessions = vec![session(false, 2), session(false, 0)];
+        let mut next_session = 0;
+        assert_eq!(ClientPool::_pick_session(&sessions, &mut next_session), 1);
+    }
+
+    #[test]
+    fn pool_status() {
+        let sessions = vec![session(false, 2), session(true, 3), session(false, 0)];
+        sessions[0].status.set_error("first");
+        sessions[2].status.set_error("last");
+        let status =
+            status::StatusHandle::Pool(sessions.iter().map(|s| s.status.clone()).collect());
+        let s = status.get();
+        assert!(s.connected);
+        assert_eq!(s.pending_commands, 5);
+        assert_eq!(s.last_error.as_deref(), Some("last"));
+
+        sessions[1].status.set_connected(false);
+        sessions[2].status.set_pending_commands(1);
+        let s = status.get();
+        assert!(!s.connected);
+        assert_eq!(s.pending_commands, 6);
+
+        let s = status::StatusHandle::Pool(vec![]).get();
+        assert!(!s.connected);
+        assert_eq!(s.pending_commands, 0);
+    }
+
+    #[test]
+    fn poll_on_first_session() {
+        let sessions = vec![session(true, 5), session(true, 0)];
+        let mut next_session = 1;
+        let (sender, _) = futures::channel::oneshot::channel();
+        let poll = RequestMessage::Poll(Box::new(poll::PollRequest {
+            return_path: sender,
+        }));
+        assert_eq!(
+            ClientPool::_session_for(&poll, &sessions, &mut next_session),
+            0
+        );
+        assert_eq!(next_session, 1);
+
+        let (sender, _) = futures::channel::oneshot::channel();
+        let hello = RequestMessage::Hello(Box::new(BlankRequest {
+            return_path: sender,
+        }));
+        assert_eq!(
+            ClientPool::_session_for(&hello, &sessions, &mut next_session),
+            1
+        );
+    }
+}
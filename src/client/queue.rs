@@ -0,0 +1,399 @@
+//! Request lanes into a client, and the waiting queue used to hold requests while it reconnects
+//!
+//! Each client has two lanes, interactive and batch. Requests are sent in the lane given by the
+//! priority of the calling task (see [`with_priority`]), and the client always services the
+//! interactive lane first. A client transaction ID set on the calling task (see
+//! [`with_client_transaction_id`]) travels with the request to the client, numbered by how many
+//! commands the task has sent with it (see [`ClientTransactionID::for_command`]).
+
+use super::router::ClientTransactionID;
+use super::{Error, RequestMessage};
+use futures::stream::{FusedStream, Stream, StreamExt};
+use futures::SinkExt;
+use std::collections::VecDeque;
+
+/// Priority lane a request is sent in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Priority {
+    /// Requests with a user waiting on them
+    #[default]
+    Interactive,
+    /// Bulk jobs that can wait behind interactive requests
+    Batch,
+}
+
+tokio::task_local! {
+    static PRIORITY: Priority;
+    static CLIENT_TRANSACTION_ID: Option<ClientTransactionID>;
+    static COMMANDS_SENT: std::cell::Cell<usize>;
+}
+
+impl Priority {
+    /// Priority of the current task, as set by [`with_priority`]
+    pub fn current() -> Self {
+        PRIORITY.try_with(|p| *p).unwrap_or_default()
+    }
+}
+
+/// Runs a future with all client requests made from it sent in the given priority lane
+///
+/// # Arguments
+/// * `priority` - Lane to send requests in
+/// * `f` - Future to run
+pub async fn with_priority<F: std::future::Future>(priority: Priority, f: F) -> F::Output {
+    PRIORITY.scope(priority, f).await
+}
+
+/// Runs a future with all client requests made from it sent with the given client transaction ID
+///
+/// # Arguments
+/// * `client_transaction_id` - ID, or prefix of the IDs, to send commands with
+/// * `f` - Future to run
+pub async fn with_client_transaction_id<F: std::future::Future>(
+    client_transaction_id: ClientTransactionID,
+    f: F,
+) -> F::Output {
+    CLIENT_TRANSACTION_ID
+        .scope(
+            Some(client_transaction_id),
+            COMMANDS_SENT.scope(Default::default(), f),
+        )
+        .await
+}
+
+/// Settings of the task that sent a request
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    /// Lane the request was sent in
+    pub priority: Priority,
+    /// Client transaction ID the caller asked for
+    pub client_transaction_id: Option<ClientTransactionID>,
+}
+
+impl RequestContext {
+    /// Runs a future with the same settings as the task that sent the request, for passing it on
+    ///
+    /// The request keeps its client transaction ID when sent again from the future.
+    pub async fn scope<F: std::future::Future>(self, f: F) -> F::Output {
+        CLIENT_TRANSACTION_ID
+            .scope(
+                self.client_transaction_id,
+                COMMANDS_SENT.scope(Default::default(), with_priority(self.priority, f)),
+            )
+            .await
+    }
+}
+
+/// Configuration of the waiting queue
+#[derive(Debug, Clone, Copy)]
+pub struct QueueConf {
+    /// Maximum number of requests to hold while the client is not connected
+    pub size: usize,
+    /// How long a request will be held before being rejected
+    pub max_wait: std::time::Duration,
+}
+
+impl Default for QueueConf {
+    fn default() -> Self {
+        Self {
+            size: 64,
+            max_wait: std::time::Duration::from_secs(30),
+        }
+    }
+}
+
+/// Sending end of a client's request lanes
+#[derive(Debug, Clone)]
+pub struct RequestSender {
+    interactive: futures::channel::mpsc::Sender<(Option<ClientTransactionID>, RequestMessage)>,
+    batch: futures::channel::mpsc::Sender<(Option<ClientTransactionID>, RequestMessage)>,
+    timeout: std::time::Duration,
+}
+
+/// Receiving end of a client's request lanes, yielding interactive requests first
+#[derive(Debug)]
+pub struct RequestReceiver {
+    interactive: futures::stream::Fuse<
+        futures::channel::mpsc::Receiver<(Option<ClientTransactionID>, RequestMessage)>,
+    >,
+    batch: futures::stream::Fuse<
+        futures::channel::mpsc::Receiver<(Option<ClientTransactionID>, RequestMessage)>,
+    >,
+}
+
+/// Creates a new pair of request lanes
+///
+/// # Arguments
+/// * `buffer` - Number of requests each lane can buffer before senders must wait
+/// * `timeout` - Default time callers will wait for a response
+pub fn channel(buffer: usize, timeout: std::time::Duration) -> (RequestSender, RequestReceiver) {
+    let (interactive_sender, interactive_receiver) = futures::channel::mpsc::channel(buffer);
+    let (batch_sender, batch_receiver) = futures::channel::mpsc::channel(buffer);
+    (
+        RequestSender {
+            interactive: interactive_sender,
+            batch: batch_sender,
+            timeout,
+        },
+        RequestReceiver {
+            interactive: interactive_receiver.fuse(),
+            batch: batch_receiver.fuse(),
+        },
+    )
+}
+
+impl RequestSender {
+    /// Default time callers will wait for a response from this client
+    pub fn timeout(&self) -> std::time::Duration {
+        self.timeout
+    }
+
+    /// Sends a request in the lane of the current task, along with its client transaction ID,
+    /// waiting for space if the lane is full
+    pub async fn send(
+        &mut self,
+        req: RequestMessage,
+    ) -> Result<(), futures::channel::mpsc::SendError> {
+        let client_transaction_id = CLIENT_TRANSACTION_ID
+            .try_with(|id| {
+                id.as_ref().map(|id| {
+                    let n = COMMANDS_SENT
+                        .try_with(|n| n.replace(n.get() + 1))
+                        .unwrap_or_default();
+                    id.for_command(n)
+                })
+            })
+            .unwrap_or_default();
+        let req = (client_transaction_id, req);
+        match Priority::current() {
+            Priority::Interactive => self.interactive.send(req).await,
+            Priority::Batch => self.batch.send(req).await,
+        }
+    }
+}
+
+impl Stream for RequestReceiver {
+    type Item = (RequestContext, RequestMessage);
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        if let std::task::Poll::Ready(Some((client_transaction_id, r))) =
+            self.interactive.poll_next_unpin(cx)
+        {
+            let ctx = RequestContext {
+                priority: Priority::Interactive,
+                client_transaction_id,
+            };
+            return std::task::Poll::Ready(Some((ctx, r)));
+        }
+        if let std::task::Poll::Ready(Some((client_transaction_id, r))) =
+            self.batch.poll_next_unpin(cx)
+        {
+            let ctx = RequestContext {
+                priority: Priority::Batch,
+                client_transaction_id,
+            };
+            return std::task::Poll::Ready(Some((ctx, r)));
+        }
+        if self.is_terminated() {
+            std::task::Poll::Ready(None)
+        } else {
+            std::task::Poll::Pending
+        }
+    }
+}
+
+impl FusedStream for RequestReceiver {
+    fn is_terminated(&self) -> bool {
+        self.interactive.is_terminated() && self.batch.is_terminated()
+    }
+}
+
+/// Requests held by a client while it is not able to send them to the server
+#[derive(Debug)]
+pub struct WaitingQueue {
+    conf: QueueConf,
+    interactive: VecDeque<(tokio::time::Instant, RequestContext, RequestMessage)>,
+    batch: VecDeque<(tokio::time::Instant, RequestContext, RequestMessage)>,
+}
+
+impl WaitingQueue {
+    pub fn new(conf: QueueConf) -> Self {
+        Self {
+            conf,
+            interactive: VecDeque::new(),
+            batch: VecDeque::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.interactive.len() + self.batch.len()
+    }
+
+    /// Holds a request until the client is ready, rejecting it if the queue is full
+    pub fn push(&mut self, ctx: RequestContext, req: RequestMessage) {
+        if self.len() >= self.conf.size {
+            req.reject(Error::NotReady);
+            return;
+        }
+        let deadline = tokio::time::Instant::now() + self.conf.max_wait;
+        match ctx.priority {
+            Priority::Interactive => self.interactive.push_back((deadline, ctx, req)),
+            Priority::Batch => self.batch.push_back((deadline, ctx, req)),
+        }
+    }
+
+    /// Rejects requests that have been waiting too long, and drops those the caller gave up on
+    pub fn expire(&mut self) {
+        let now = tokio::time::Instant::now();
+        for lane in [&mut self.interactive, &mut self.batch] {
+            for (deadline, ctx, req) in std::mem::take(lane) {
+                if req.is_canceled() {
+                    continue;
+                } else if deadline <= now {
+                    req.reject(Error::NotReady);
+                } else {
+                    lane.push_back((deadline, ctx, req));
+                }
+            }
+        }
+    }
+
+    /// Rejects every waiting request
+    pub fn reject_all(&mut self) {
+        for (_, _, req) in self.interactive.drain(..).chain(self.batch.drain(..)) {
+            req.reject(Error::NotReady);
+        }
+    }
+
+    /// Takes the next request to send, interactive requests first
+    pub fn pop(&mut self) -> Option<(RequestContext, RequestMessage)> {
+        self.expire();
+        match self.interactive.pop_front() {
+            Some((_, ctx, r)) => Some((ctx, r)),
+            None => self.batch.pop_front().map(|(_, ctx, r)| (ctx, r)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod queue_tests {
+    use super::*;
+    use crate::client::{BlankRequest, CommandResponse, Response};
+
+    type Receiver = futures::channel::oneshot::Receiver<Response<CommandResponse<()>>>;
+
+    fn request() -> (RequestMessage, Receiver) {
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        (
+            RequestMessage::Hello(Box::new(BlankRequest {
+                return_path: sender,
+            })),
+            receiver,
+        )
+    }
+
+    fn ctx(priority: Priority, id: &str) -> RequestContext {
+        RequestContext {
+            priority,
+            client_transaction_id: Some(ClientTransactionID::Exact(id.to_string())),
+        }
+    }
+
+    fn is_rejected(receiver: &mut Receiver) -> bool {
+        matches!(receiver.try_recv(), Ok(Some(Err(Error::NotReady))))
+    }
+
+    #[test]
+    fn lane_order() {
+        let mut queue = WaitingQueue::new(QueueConf::default());
+        let (req_1, _rx_1) = request();
+        let (req_2, _rx_2) = request();
+        let (req_3, _rx_3) = request();
+        queue.push(ctx(Priority::Batch, "batch-1"), req_1);
+        queue.push(ctx(Priority::Interactive, "interactive-1"), req_2);
+        queue.push(ctx(Priority::Batch, "batch-2"), req_3);
+
+        let order = std::iter::from_fn(|| queue.pop())
+            .map(|(c, _)| c.client_transaction_id.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            vec![
+                ClientTransactionID::Exact("interactive-1".to_string()),
+                ClientTransactionID::Exact("batch-1".to_string()),
+                ClientTransactionID::Exact("batch-2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn capacity() {
+        let mut queue = WaitingQueue::new(QueueConf {
+            size: 1,
+            ..Default::default()
+        });
+        let (req_1, mut rx_1) = request();
+        let (req_2, mut rx_2) = request();
+        queue.push(RequestContext::default(), req_1);
+        queue.push(RequestContext::default(), req_2);
+
+        assert!(!is_rejected(&mut rx_1));
+        assert!(is_rejected(&mut rx_2));
+        assert!(queue.pop().is_some());
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn expiry() {
+        let mut queue = WaitingQueue::new(QueueConf {
+            size: 8,
+            max_wait: std::time::Duration::from_secs(0),
+        });
+        let (req_1, mut rx_1) = request();
+        let (req_2, rx_2) = request();
+        queue.push(RequestContext::default(), req_1);
+        queue.push(RequestContext::default(), req_2);
+        drop(rx_2);
+
+        queue.expire();
+        assert!(is_rejected(&mut rx_1));
+        assert!(queue.pop().is_none());
+
+        let mut queue = WaitingQueue::new(QueueConf::default());
+        let (req, mut rx) = request();
+        queue.push(RequestContext::default(), req);
+        queue.reject_all();
+        assert!(is_rejected(&mut rx));
+        assert!(queue.pop().is_none());
+    }
+
+    #[tokio::test]
+    async fn client_transaction_id_per_command() {
+        let (mut sender, mut receiver) = channel(8, std::time::Duration::from_secs(1));
+        with_client_transaction_id(ClientTransactionID::Exact("order-1".to_string()), async {
+            for _ in 0..3 {
+                sender.send(request().0).await.unwrap();
+            }
+        })
+        .await;
+        sender.send(request().0).await.unwrap();
+
+        let mut ids = vec![];
+        for _ in 0..4 {
+            let (c, _) = receiver.next().await.unwrap();
+            ids.push(c.client_transaction_id);
+        }
+        assert_eq!(
+            ids,
+            vec![
+                Some(ClientTransactionID::Exact("order-1".to_string())),
+                Some(ClientTransactionID::Exact("order-1-2".to_string())),
+                Some(ClientTransactionID::Exact("order-1-3".to_string())),
+                None,
+            ]
+        );
+    }
+}
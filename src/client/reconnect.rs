@@ -0,0 +1,229 @@
+//! Reconnect policy shared by the clients, with exponential backoff and a circuit breaker
+//!
+//! Each failed connection attempt doubles (by default) the delay before the next one, up to a
+//! maximum, with random jitter so that clients sharing a source IP don't retry in lockstep.
+//! After enough consecutive failures the circuit breaker opens, and the client stops trying for
+//! a while and rejects requests immediately rather than queueing them.
+
+use std::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+
+/// Configuration of the reconnect policy
+#[derive(Debug, Clone, Copy)]
+pub struct ReconnectConf {
+    /// Longest delay between attempts
+    pub max_delay: std::time::Duration,
+    /// Factor the delay is multiplied by after each failure
+    pub multiplier: f64,
+    /// Fraction of the delay to randomly subtract, between 0 and 1
+    pub jitter: f64,
+    /// Number of consecutive failures before the circuit breaker opens
+    pub breaker_threshold: u32,
+    /// How long the circuit breaker stays open before trying again
+    pub breaker_reset: std::time::Duration,
+}
+
+impl Default for ReconnectConf {
+    fn default() -> Self {
+        Self {
+            max_delay: std::time::Duration::from_secs(300),
+            multiplier: 2.0,
+            jitter: 0.5,
+            breaker_threshold: 10,
+            breaker_reset: std::time::Duration::from_secs(600),
+        }
+    }
+}
+
+/// State of the circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Connecting normally
+    Closed,
+    /// Too many failures, not trying to connect
+    Open,
+    /// Trying to connect again after being open
+    HalfOpen,
+}
+
+impl BreakerState {
+    fn from_u8(v: u8) -> Self {
+        match v {
+            1 => BreakerState::Open,
+            2 => BreakerState::HalfOpen,
+            _ => BreakerState::Closed,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            BreakerState::Closed => 0,
+            BreakerState::Open => 1,
+            BreakerState::HalfOpen => 2,
+        }
+    }
+}
+
+/// Tracks consecutive connection failures for a client
+#[derive(Debug)]
+pub struct Backoff {
+    initial_delay: std::time::Duration,
+    conf: ReconnectConf,
+    failures: AtomicU32,
+    state: AtomicU8,
+}
+
+impl Backoff {
+    /// Creates a new reconnect tracker
+    ///
+    /// # Arguments
+    /// * `initial_delay` - Delay after the first failure
+    /// * `conf` - Reconnect policy to follow
+    pub fn new(initial_delay: std::time::Duration, conf: ReconnectConf) -> Self {
+        Self {
+            initial_delay,
+            conf,
+            failures: AtomicU32::new(0),
+            state: AtomicU8::new(BreakerState::Closed.to_u8()),
+        }
+    }
+
+    pub fn state(&self) -> BreakerState {
+        BreakerState::from_u8(self.state.load(Ordering::Relaxed))
+    }
+
+    fn set_state<M: crate::metrics::Metrics>(&self, state: BreakerState, metrics: &M) {
+        let old_state = BreakerState::from_u8(self.state.swap(state.to_u8(), Ordering::Relaxed));
+        if old_state != state {
+            metrics.circuit_breaker(state);
+        }
+    }
+
+    /// Records a successful connection, resetting the delay and closing the breaker
+    pub fn success<M: crate::metrics::Metrics>(&self, metrics: &M) {
+        self.failures.store(0, Ordering::Relaxed);
+        self.set_state(BreakerState::Closed, metrics);
+    }
+
+    /// Delay before the next attempt after the given number of consecutive failures
+    fn delay(&self, failures: u32) -> std::time::Duration {
+        let base = self.initial_delay.as_secs_f64()
+            * self
+                .conf
+                .multiplier
+                .powi(failures.saturating_sub(1).min(i32::MAX as u32) as i32);
+        let base = base.min(self.conf.max_delay.as_secs_f64()).max(0.0);
+        let jitter = self.conf.jitter.clamp(0.0, 1.0) * rand::random::<f64>();
+        std::time::Duration::from_secs_f64(base * (1.0 - jitter))
+    }
+
+    /// Records a failed connection, returning how long to wait before the next attempt
+    ///
+    /// The breaker stays open until the wait is over, so the caller should keep rejecting
+    /// requests while it waits, and then call [`Backoff::wait`] or [`Backoff::retry`].
+    pub fn failure<M: crate::metrics::Metrics>(&self, metrics: &M) -> std::time::Duration {
+        let failures = self
+            .failures
+            .fetch_add(1, Ordering::Relaxed)
+            .saturating_add(1);
+        metrics.reconnect_attempt();
+        if failures >= self.conf.breaker_threshold || self.state() == BreakerState::HalfOpen {
+            warn!(
+                "{} consecutive connection failures, not trying again for {}s",
+                failures,
+                self.conf.breaker_reset.as_secs()
+            );
+            self.set_state(BreakerState::Open, metrics);
+            self.conf.breaker_reset
+        } else {
+            self.delay(failures)
+        }
+    }
+
+    /// Marks the wait after a failure as over, letting an open breaker try one more connection
+    pub fn retry<M: crate::metrics::Metrics>(&self, metrics: &M) {
+        if self.state() == BreakerState::Open {
+            self.failures.store(0, Ordering::Relaxed);
+            self.set_state(BreakerState::HalfOpen, metrics);
+        }
+    }
+
+    /// Waits out a delay returned by [`Backoff::failure`], then calls [`Backoff::retry`]
+    pub async fn wait<M: crate::metrics::Metrics>(&self, delay: std::time::Duration, metrics: &M) {
+        tokio::time::sleep(delay).await;
+        self.retry(metrics);
+    }
+}
+
+#[cfg(test)]
+mod reconnect_tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn test_backoff(jitter: f64) -> Backoff {
+        Backoff::new(
+            Duration::from_secs(5),
+            ReconnectConf {
+                max_delay: Duration::from_secs(60),
+                multiplier: 2.0,
+                jitter,
+                breaker_threshold: 4,
+                breaker_reset: Duration::from_secs(600),
+            },
+        )
+    }
+
+    #[test]
+    fn delay() {
+        let backoff = test_backoff(0.0);
+        assert_eq!(backoff.delay(1), Duration::from_secs(5));
+        assert_eq!(backoff.delay(2), Duration::from_secs(10));
+        assert_eq!(backoff.delay(3), Duration::from_secs(20));
+        assert_eq!(backoff.delay(5), Duration::from_secs(60));
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(60));
+
+        let backoff = test_backoff(0.5);
+        for failures in 1..8 {
+            let delay = backoff.delay(failures);
+            assert!(delay <= Duration::from_secs(60));
+            assert!(delay >= Duration::from_secs(5) / 2);
+        }
+    }
+
+    #[test]
+    fn negative_multiplier() {
+        let backoff = Backoff::new(
+            Duration::from_secs(5),
+            ReconnectConf {
+                multiplier: -2.0,
+                ..Default::default()
+            },
+        );
+        assert_eq!(backoff.delay(2), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn breaker() {
+        let metrics = crate::metrics::DummyMetrics {};
+        let backoff = test_backoff(0.0);
+
+        assert_eq!(backoff.failure(&metrics), Duration::from_secs(5));
+        assert_eq!(backoff.failure(&metrics), Duration::from_secs(10));
+        assert_eq!(backoff.failure(&metrics), Duration::from_secs(20));
+        assert_eq!(backoff.state(), BreakerState::Closed);
+
+        assert_eq!(backoff.failure(&metrics), Duration::from_secs(600));
+        assert_eq!(backoff.state(), BreakerState::Open);
+
+        backoff.retry(&metrics);
+        assert_eq!(backoff.state(), BreakerState::HalfOpen);
+        assert_eq!(backoff.failure(&metrics), Duration::from_secs(600));
+        assert_eq!(backoff.state(), BreakerState::Open);
+
+        backoff.retry(&metrics);
+        backoff.success(&metrics);
+        assert_eq!(backoff.state(), BreakerState::Closed);
+        assert_eq!(backoff.failure(&metrics), Duration::from_secs(5));
+        backoff.retry(&metrics);
+        assert_eq!(backoff.state(), BreakerState::Closed);
+    }
+}
@@ -6,6 +6,7 @@ use chrono::prelude::*;
 #[derive(Debug)]
 pub struct RestoreRequest {
     pub(super) name: String,
+    pub(super) fee_agreement: Option<fee::FeeAgreement>,
     pub(super) donuts_fee_agreement: Option<fee::DonutsFeeData>,
     pub return_path: Sender<RestoreResponse>,
 }
@@ -60,18 +61,21 @@ pub struct RestoreReportResponse {
 ///
 /// # Arguments
 /// * `domain` - The domain to restore
+/// * `fee_agreement` - Agreement to fees charged for the command
 /// * `donuts_fee_agreement` - Donuts fee information
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn request(
     domain: &str,
+    fee_agreement: Option<fee::FeeAgreement>,
     donuts_fee_agreement: Option<fee::DonutsFeeData>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<RestoreResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
         client_sender,
         RequestMessage::RestoreRequest(Box::new(RestoreRequest {
             name: domain.to_string(),
+            fee_agreement,
             donuts_fee_agreement,
             return_path: sender,
         })),
@@ -99,7 +103,7 @@ pub struct RestoreReportInfo<'a> {
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn report(
     info: RestoreReportInfo<'_>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<RestoreReportResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -5,7 +5,6 @@ use std::collections::HashMap;
 
 pub type Response<T> = Result<T, Error>;
 pub type Sender<T> = futures::channel::oneshot::Sender<Result<CommandResponse<T>, Error>>;
-pub type RequestSender = futures::channel::mpsc::Sender<RequestMessage>;
 
 #[derive(Debug)]
 pub struct CommandExtraValue {
@@ -13,16 +12,107 @@ pub struct CommandExtraValue {
     pub reason: String,
 }
 
+/// Response data for an extension not included at login, as returned under RFC 9038
+#[derive(Debug)]
+pub struct UnhandledNamespace {
+    /// URI of the extension namespace
+    pub namespace: String,
+    /// The extension XML as sent by the server
+    pub xml: String,
+}
+
 #[derive(Debug)]
 pub struct CommandTransactionID {
     pub client: String,
     pub server: String,
 }
 
+/// Shortest client transaction ID allowed by the EPP core schema
+pub const MIN_CLIENT_TRANSACTION_ID_LENGTH: usize = 3;
+/// Longest client transaction ID allowed by the EPP core schema
+pub const MAX_CLIENT_TRANSACTION_ID_LENGTH: usize = 64;
+/// Length of the UUID following a client transaction ID prefix
+const GENERATED_ID_LENGTH: usize = 36;
+
+/// Client transaction ID asked for by the caller, instead of a random one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientTransactionID {
+    /// Send commands with exactly this ID
+    Exact(String),
+    /// Send commands with this prefix followed by a random UUID
+    Prefix(String),
+}
+
+fn check_client_transaction_id(id: &str, min: usize, max: usize) -> Result<(), String> {
+    if id.len() < min || id.len() > max {
+        return Err(format!(
+            "client transaction ID must be between {} and {} characters",
+            min, max
+        ));
+    }
+    if !id.chars().all(|c| c.is_ascii_graphic()) {
+        return Err(
+            "client transaction ID must only contain printable ASCII characters without spaces"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+impl ClientTransactionID {
+    /// Validates an exact client transaction ID against the EPP length limits
+    pub fn exact(id: &str) -> Result<Self, String> {
+        check_client_transaction_id(
+            id,
+            MIN_CLIENT_TRANSACTION_ID_LENGTH,
+            MAX_CLIENT_TRANSACTION_ID_LENGTH,
+        )?;
+        Ok(ClientTransactionID::Exact(id.to_string()))
+    }
+
+    /// Validates a client transaction ID prefix, leaving room for the UUID after it
+    pub fn prefix(prefix: &str) -> Result<Self, String> {
+        check_client_transaction_id(
+            prefix,
+            1,
+            MAX_CLIENT_TRANSACTION_ID_LENGTH - GENERATED_ID_LENGTH,
+        )?;
+        Ok(ClientTransactionID::Prefix(prefix.to_string()))
+    }
+
+    /// ID to send the `n`th command of a call with, counting from 0
+    ///
+    /// An exact ID is kept for the first command, and has `-2`, `-3`... added for the rest, cut
+    /// short if needed to stay within the length limit. A prefix is kept as is.
+    pub fn for_command(&self, n: usize) -> Self {
+        match self {
+            ClientTransactionID::Exact(id) if n > 0 => {
+                let suffix = format!("-{}", n + 1);
+                let len = id
+                    .len()
+                    .min(MAX_CLIENT_TRANSACTION_ID_LENGTH - suffix.len());
+                ClientTransactionID::Exact(format!("{}{}", &id[..len], suffix))
+            }
+            id => id.clone(),
+        }
+    }
+
+    /// ID to send with the next command
+    pub fn generate(&self) -> String {
+        match self {
+            ClientTransactionID::Exact(id) => id.clone(),
+            ClientTransactionID::Prefix(prefix) => {
+                format!("{}{}", prefix, uuid::Uuid::new_v4().hyphenated())
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct CommandResponse<T> {
     pub response: T,
     pub extra_values: Vec<CommandExtraValue>,
+    pub unhandled_namespaces: Vec<UnhandledNamespace>,
     pub transaction_id: Option<CommandTransactionID>,
 }
 
@@ -33,13 +123,29 @@ macro_rules! router {
             $($n(Box<$req>),)*
         }
 
+        impl RequestMessage {
+            /// Returns an error to the caller without processing the request
+            pub fn reject(self, err: Error) {
+                match self {
+                    $(RequestMessage::$n(req) => {let _ = req.return_path.send(Err(err));},)*
+                };
+            }
+
+            /// Has the caller stopped waiting for a response
+            pub fn is_canceled(&self) -> bool {
+                match self {
+                    $(RequestMessage::$n(req) => req.return_path.is_canceled(),)*
+                }
+            }
+        }
+
         #[allow(non_snake_case)]
         #[derive(Debug)]
         pub struct Router<I: InnerRouter<T, M>, T, M: crate::metrics::Metrics> {
             _marker: std::marker::PhantomData<T>,
             pub inner: Box<I>,
             metrics_registry: M,
-            $($n: HashMap<uuid::Uuid, (Sender<$res>, Option<prometheus::HistogramTimer>)>,)*
+            $($n: HashMap<String, (Sender<$res>, Option<prometheus::HistogramTimer>)>,)*
         }
 
         paste! {
@@ -53,7 +159,7 @@ macro_rules! router {
                 type Request;
                 type Response;
 
-                $(fn [<$n _request>](&mut self, client: &T, req: &$req, command_id: uuid::Uuid) -> Result<Self::Request, Response<$res>>;)*
+                $(fn [<$n _request>](&mut self, client: &T, req: &$req, command_id: String) -> Result<Self::Request, Response<$res>>;)*
                 $(fn [<$n _response>](&mut self, return_path: Sender<$res>, response: Self::Response, metrics: &M);)*
             }
         }
@@ -74,17 +180,44 @@ macro_rules! router {
                 };
             }
 
+            /// Number of commands sent awaiting a response
+            pub fn pending(&self) -> usize {
+                0 $(+ self.$n.len())*
+            }
+
+            fn is_pending(&self, command_id: &str) -> bool {
+                false $(|| self.$n.contains_key(command_id))*
+            }
+
+            /// Picks the client transaction ID for a command, refusing the caller's if a command
+            /// with the same ID is still awaiting a response
+            fn command_id(&self, client_transaction_id: Option<&ClientTransactionID>) -> Result<String, Error> {
+                match client_transaction_id.map(ClientTransactionID::generate) {
+                    Some(id) if self.is_pending(&id) => Err(Error::Err(format!(
+                        "client transaction ID {} is already awaiting a response", id
+                    ))),
+                    Some(id) => Ok(id),
+                    None => Ok(uuid::Uuid::new_v4().hyphenated().to_string()),
+                }
+            }
+
             pub fn drain(&mut self) {
                 $(for r in self.$n.drain() {
                     let _ = r.1.0.send(Err(Error::NotReady));
                 })*
             }
 
-            pub fn handle_request(&mut self, client: &T, req: RequestMessage) ->
-             Option<(I::Request, uuid::Uuid)> {
+            pub fn handle_request(&mut self, client: &T, req: RequestMessage, client_transaction_id: Option<&ClientTransactionID>) ->
+             Option<(I::Request, String)> {
                 match req {
                     $(RequestMessage::$n(req) => {
-                        let command_id = uuid::Uuid::new_v4();
+                        let command_id = match self.command_id(client_transaction_id) {
+                            Ok(c) => c,
+                            Err(e) => {
+                                let _ = req.return_path.send(Err(e));
+                                return None
+                            }
+                        };
                         let timer =  self.metrics_registry.record_response_time(stringify!($n));
                         paste! {
                             let res = match I::[<$n _request>](&mut self.inner, client, &req, command_id.clone()) {
@@ -94,6 +227,7 @@ macro_rules! router {
                                         Ok(r) => Ok(CommandResponse {
                                             response: r,
                                             extra_values: vec![],
+                                            unhandled_namespaces: vec![],
                                             transaction_id: None
                                         }),
                                         Err(e) => Err(e)
@@ -108,7 +242,7 @@ macro_rules! router {
                 }
             }
 
-            pub fn handle_response(&mut self, transaction_id: &uuid::Uuid, response: I::Response) {
+            pub fn handle_response(&mut self, transaction_id: &str, response: I::Response) {
                 $(if let Some((return_path, timer)) = self.$n.remove(transaction_id) {
                     paste! {
                         if let Some(timer) = timer {
@@ -128,6 +262,7 @@ router!(
     Poll,                        super::poll::PollRequest,                          Option<super::poll::PollResponse>;
     PollAck,                     super::poll::PollAckRequest,                       super::poll::PollAckResponse;
     DomainCheck,                 super::domain::CheckRequest,                       super::domain::CheckResponse;
+    DomainBatchCheck,            super::domain::BatchCheckRequest,                  super::domain::BatchCheckResponse;
     DomainClaimsCheck,           super::domain::ClaimsCheckRequest,                 super::domain::ClaimsCheckResponse;
     DomainTrademarkCheck,        super::domain::TrademarkCheckRequest,              super::domain::ClaimsCheckResponse;
     DomainInfo,                  super::domain::InfoRequest,                        super::domain::InfoResponse;
@@ -155,11 +290,25 @@ router!(
     RestoreRequest,              super::rgp::RestoreRequest,                        super::rgp::RestoreResponse;
     RestoreReport,               super::rgp::RestoreReportRequest,                  super::rgp::RestoreReportResponse;
     HostCheck,                   super::host::CheckRequest,                         super::host::CheckResponse;
+    HostBatchCheck,              super::host::BatchCheckRequest,                    super::host::BatchCheckResponse;
     HostInfo,                    super::host::InfoRequest,                          super::host::InfoResponse;
     HostCreate,                  super::host::CreateRequest,                        super::host::CreateResponse;
     HostDelete,                  super::host::DeleteRequest,                        super::host::DeleteResponse;
     HostUpdate,                  super::host::UpdateRequest,                        super::host::UpdateResponse;
+    NSSetCheck,                  super::nsset::CheckRequest,                        super::nsset::CheckResponse;
+    NSSetInfo,                   super::nsset::InfoRequest,                         super::nsset::InfoResponse;
+    NSSetCreate,                 super::nsset::CreateRequest,                       super::nsset::CreateResponse;
+    NSSetDelete,                 super::nsset::DeleteRequest,                       super::nsset::DeleteResponse;
+    NSSetUpdate,                 super::nsset::UpdateRequest,                       super::nsset::UpdateResponse;
+    NSSetTransfer,               super::nsset::TransferRequest,                     super::nsset::TransferResponse;
+    KeySetCheck,                 super::keyset::CheckRequest,                       super::keyset::CheckResponse;
+    KeySetInfo,                  super::keyset::InfoRequest,                        super::keyset::InfoResponse;
+    KeySetCreate,                super::keyset::CreateRequest,                      super::keyset::CreateResponse;
+    KeySetDelete,                super::keyset::DeleteRequest,                      super::keyset::DeleteResponse;
+    KeySetUpdate,                super::keyset::UpdateRequest,                      super::keyset::UpdateResponse;
+    KeySetTransfer,              super::keyset::TransferRequest,                    super::keyset::TransferResponse;
     ContactCheck,                super::contact::CheckRequest,                      super::contact::CheckResponse;
+    ContactBatchCheck,           super::contact::BatchCheckRequest,                 super::contact::BatchCheckResponse;
     ContactInfo,                 super::contact::InfoRequest,                       super::contact::InfoResponse;
     ContactCreate,               super::contact::CreateRequest,                     super::contact::CreateResponse;
     ContactDelete,               super::contact::DeleteRequest,                     super::contact::DeleteResponse;
@@ -175,6 +324,9 @@ router!(
     NominetContactValidate,      super::nominet::ContactValidateRequest,            super::nominet::ContactValidateResponse;
     NominetLock,                 super::nominet::LockRequest,                       super::nominet::LockResponse;
     NominetUnlock,               super::nominet::LockRequest,                       super::nominet::LockResponse;
+    NominetUnrenew,              super::nominet::UnrenewRequest,                    super::nominet::UnrenewResponse;
+    NominetList,                 super::nominet::ListRequest,                       super::nominet::ListResponse;
+    NominetFork,                 super::nominet::ForkRequest,                       super::nominet::ForkResponse;
     Balance,                     super::balance::BalanceRequest,                    super::balance::BalanceResponse;
     MaintenanceList,             super::maintenance::ListRequest,                   super::maintenance::ListResponse;
     MaintenanceInfo,             super::maintenance::InfoRequest,                   super::maintenance::InfoResponse;
@@ -198,3 +350,48 @@ router!(
     DACUsage,                    super::dac::DACUsageRequest,                       super::dac::DACUsageResponse;
     DACLimits,                   super::dac::DACUsageRequest,                       super::dac::DACUsageResponse
 );
+
+#[cfg(test)]
+mod router_tests {
+    use super::*;
+
+    #[test]
+    fn client_transaction_id() {
+        assert_eq!(
+            ClientTransactionID::exact("order-1234").unwrap().generate(),
+            "order-1234"
+        );
+        assert!(ClientTransactionID::exact("ab").is_err());
+        assert!(ClientTransactionID::exact(&"a".repeat(65)).is_err());
+        assert!(ClientTransactionID::exact("order 1234").is_err());
+
+        let id = ClientTransactionID::prefix("job-42-").unwrap().generate();
+        assert!(id.starts_with("job-42-"));
+        assert!(id.len() <= MAX_CLIENT_TRANSACTION_ID_LENGTH);
+        assert!(ClientTransactionID::prefix(&"a".repeat(28)).is_ok());
+        assert!(ClientTransactionID::prefix(&"a".repeat(29)).is_err());
+        assert!(ClientTransactionID::prefix("").is_err());
+    }
+
+    #[test]
+    fn client_transaction_id_for_command() {
+        let id = ClientTransactionID::exact("order-1234").unwrap();
+        assert_eq!(id.for_command(0).generate(), "order-1234");
+        assert_eq!(id.for_command(1).generate(), "order-1234-2");
+        assert_eq!(id.for_command(10).generate(), "order-1234-11");
+
+        let id = ClientTransactionID::exact(&"a".repeat(64)).unwrap();
+        assert_eq!(id.for_command(0).generate(), "a".repeat(64));
+        assert_eq!(
+            id.for_command(1).generate(),
+            format!("{}-2", "a".repeat(62))
+        );
+        assert_eq!(
+            id.for_command(99).generate(),
+            format!("{}-100", "a".repeat(60))
+        );
+
+        let prefix = ClientTransactionID::prefix("job-42-").unwrap();
+        assert_eq!(prefix.for_command(3), prefix);
+    }
+}
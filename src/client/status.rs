@@ -0,0 +1,109 @@
+//! Session state published by the client actors for the admin service
+
+use chrono::prelude::*;
+
+/// Snapshot of a client's session state
+#[derive(Debug, Clone, Default)]
+pub struct ClientStatus {
+    /// Is the session currently connected and logged in
+    pub connected: bool,
+    /// Server ID from the last greeting received
+    pub server_id: Option<String>,
+    /// When the last greeting was received
+    pub last_greeting: Option<DateTime<Utc>>,
+    /// Object URIs sent at login
+    pub objects: Vec<String>,
+    /// Extension URIs sent at login
+    pub extensions: Vec<String>,
+    /// Configured server errata
+    pub errata: Option<String>,
+    /// Is pipelining enabled for this session
+    pub pipelining: bool,
+    /// Number of commands sent to the server awaiting a response
+    pub pending_commands: usize,
+    /// The last error encountered on the session
+    pub last_error: Option<String>,
+    /// When the last error was encountered
+    pub last_error_date: Option<DateTime<Utc>>,
+    /// Login security events received at the last login
+    pub login_security_events: Vec<super::login_sec::LoginSecurityEvent>,
+    /// Is the password to be changed at the next login
+    pub password_rotation_due: bool,
+    /// When the password was last changed by rotation
+    pub password_rotated: Option<DateTime<Utc>>,
+}
+
+/// Shared handle to a client's status, written by the client and read by the admin service
+#[derive(Debug, Clone)]
+pub enum StatusHandle {
+    /// Status of a single session
+    Session(std::sync::Arc<std::sync::RwLock<ClientStatus>>),
+    /// Statuses of the sessions of a pool, merged when read
+    Pool(Vec<StatusHandle>),
+}
+
+impl Default for StatusHandle {
+    fn default() -> Self {
+        Self::new(ClientStatus::default())
+    }
+}
+
+impl StatusHandle {
+    pub fn new(status: ClientStatus) -> Self {
+        Self::Session(std::sync::Arc::new(std::sync::RwLock::new(status)))
+    }
+
+    /// Fetches a copy of the current status
+    ///
+    /// A pool is connected if any of its sessions are, and has the commands pending on all of
+    /// them; the rest of its status is that of the first session, with the latest error of any.
+    pub fn get(&self) -> ClientStatus {
+        match self {
+            Self::Session(s) => match s.read() {
+                Ok(s) => s.clone(),
+                Err(e) => e.into_inner().clone(),
+            },
+            Self::Pool(sessions) => {
+                let statuses = sessions.iter().map(Self::get).collect::<Vec<_>>();
+                let mut status = statuses.first().cloned().unwrap_or_default();
+                status.connected = statuses.iter().any(|s| s.connected);
+                status.pending_commands = statuses.iter().map(|s| s.pending_commands).sum();
+                if let Some(s) = statuses
+                    .iter()
+                    .filter(|s| s.last_error_date.is_some())
+                    .max_by_key(|s| s.last_error_date)
+                {
+                    status.last_error = s.last_error.clone();
+                    status.last_error_date = s.last_error_date;
+                }
+                status
+            }
+        }
+    }
+
+    pub(crate) fn update<F: FnOnce(&mut ClientStatus)>(&self, f: F) {
+        match self {
+            Self::Session(s) => match s.write() {
+                Ok(mut s) => f(&mut s),
+                Err(e) => f(&mut e.into_inner()),
+            },
+            // Each session of a pool writes its own status
+            Self::Pool(_) => {}
+        }
+    }
+
+    pub(crate) fn set_connected(&self, connected: bool) {
+        self.update(|s| s.connected = connected);
+    }
+
+    pub(crate) fn set_pending_commands(&self, pending_commands: usize) {
+        self.update(|s| s.pending_commands = pending_commands);
+    }
+
+    pub(crate) fn set_error<E: ToString>(&self, error: E) {
+        self.update(|s| {
+            s.last_error = Some(error.to_string());
+            s.last_error_date = Some(Utc::now());
+        });
+    }
+}
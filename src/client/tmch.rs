@@ -341,7 +341,7 @@ pub struct BalanceData {
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn check(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<CheckResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -370,7 +370,7 @@ pub async fn create(
     documents: Vec<Document>,
     labels: Vec<CreateLabel>,
     variations: Vec<String>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<CreateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -395,7 +395,7 @@ pub async fn create(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn mark_info(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<MarkInfoResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -416,7 +416,7 @@ pub async fn mark_info(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn mark_smd_info(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<MarkSMDInfoResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -437,7 +437,7 @@ pub async fn mark_smd_info(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn mark_encoded_smd_info(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<MarkSMDInfoResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -458,7 +458,7 @@ pub async fn mark_encoded_smd_info(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn mark_file_info(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<MarkSMDInfoResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -484,7 +484,7 @@ pub async fn update(
     new_mark: Option<super::mark::Mark>,
     update_labels: Vec<CreateLabel>,
     update_cases: Vec<CaseUpdate>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<UpdateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -514,7 +514,7 @@ pub async fn renew(
     id: &str,
     cur_expiry_date: DateTime<Utc>,
     add_period: Option<super::Period>,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<RenewResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -537,7 +537,7 @@ pub async fn renew(
 /// * `client_sender` - Reference to the tokio channel into the client
 pub async fn transfer_initiate(
     id: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferInitiateResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -560,7 +560,7 @@ pub async fn transfer_initiate(
 pub async fn transfer(
     id: &str,
     auth_code: &str,
-    client_sender: &mut futures::channel::mpsc::Sender<RequestMessage>,
+    client_sender: &mut super::RequestSender,
 ) -> Result<CommandResponse<TransferResponse>, super::Error> {
     let (sender, receiver) = futures::channel::oneshot::channel();
     super::send_epp_client_request(
@@ -574,3 +574,51 @@ pub async fn transfer(
     )
     .await
 }
+
+/// Activates TREX protection on labels of a mark
+///
+/// # Arguments
+/// * `id` - The ID of the mark
+/// * `labels` - Labels to activate TREX on
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn trex_activate(
+    id: &str,
+    labels: Vec<TrexActivateLabel>,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<TrexActivateResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::TMCHTrexActivate(Box::new(TrexActivateRequest {
+            id: id.to_string(),
+            labels,
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
+
+/// Renews TREX protection on labels of a mark
+///
+/// # Arguments
+/// * `id` - The ID of the mark
+/// * `labels` - Labels to renew TREX on
+/// * `client_sender` - Reference to the tokio channel into the client
+pub async fn trex_renew(
+    id: &str,
+    labels: Vec<TrexRenewLabel>,
+    client_sender: &mut super::RequestSender,
+) -> Result<CommandResponse<TrexRenewResponse>, super::Error> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    super::send_epp_client_request(
+        client_sender,
+        RequestMessage::TMCHTrexRenew(Box::new(TrexRenewRequest {
+            id: id.to_string(),
+            labels,
+            return_path: sender,
+        })),
+        receiver,
+    )
+    .await
+}
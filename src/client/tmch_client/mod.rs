@@ -1,5 +1,5 @@
 use super::proto::tmch as tmch_proto;
-use super::{router as outer_router, BlankRequest, RequestMessage};
+use super::{router as outer_router, BlankRequest};
 use chrono::prelude::*;
 use futures::future::FutureExt;
 use futures::stream::StreamExt;
@@ -38,11 +38,15 @@ fn send_msg(data: &tmch_proto::TMCHMessage, host: &str) -> Result<String, ()> {
 pub struct TMCHClient<M: crate::metrics::Metrics> {
     log_storage: crate::StorageScoped,
     metrics_registry: M,
+    status: super::status::StatusHandle,
     host: String,
     client_id: String,
     password: String,
     server_id: String,
     pipelining: bool,
+    queue_conf: super::queue::QueueConf,
+    timeouts: super::Timeouts,
+    backoff: super::reconnect::Backoff,
     is_awaiting_response: bool,
     is_closing: bool,
     router: outer_router::Router<router::Router, (), M>,
@@ -50,17 +54,21 @@ pub struct TMCHClient<M: crate::metrics::Metrics> {
 }
 
 impl<M: crate::metrics::Metrics + 'static> super::Client for TMCHClient<M> {
+    fn status(&self) -> super::status::StatusHandle {
+        self.status.clone()
+    }
+
     // Starts up the TMCH client and returns the sending end of a tokio channel to inject
     // commands into the client to be processed
     fn start(
         mut self: Box<Self>,
     ) -> (
-        futures::channel::mpsc::Sender<RequestMessage>,
+        super::RequestSender,
         futures::channel::mpsc::UnboundedReceiver<outer_router::CommandTransactionID>,
     ) {
         info!("TMCH Client for {} starting...", &self.host);
         self.metrics_registry.connection_status(false);
-        let (sender, receiver) = futures::channel::mpsc::channel::<RequestMessage>(16);
+        let (sender, receiver) = super::queue::channel(16, self.timeouts.request);
         let (ready_sender, ready_receiver) = futures::channel::mpsc::unbounded();
         tokio::spawn(async move {
             self._main_loop(receiver, ready_sender).await;
@@ -85,10 +93,18 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
             log_storage: conf.log_storage,
             router: outer_router::Router::new(&conf.metrics_registry),
             metrics_registry: conf.metrics_registry,
+            status: super::status::StatusHandle::new(super::status::ClientStatus {
+                errata: conf.errata.clone(),
+                pipelining: conf.pipelining,
+                ..Default::default()
+            }),
             host: conf.host.to_string(),
             client_id: conf.tag.to_string(),
             password: conf.password.to_string(),
             pipelining: conf.pipelining,
+            queue_conf: conf.queue,
+            timeouts: conf.timeouts,
+            backoff: super::reconnect::Backoff::new(conf.timeouts.reconnect_delay, conf.reconnect),
             server_id: String::new(),
             is_awaiting_response: false,
             is_closing: false,
@@ -98,32 +114,56 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
 
     async fn _main_loop(
         &mut self,
-        receiver: futures::channel::mpsc::Receiver<RequestMessage>,
+        receiver: super::queue::RequestReceiver,
         mut ready_sender: futures::channel::mpsc::UnboundedSender<
             outer_router::CommandTransactionID,
         >,
     ) {
         let mut receiver = receiver.fuse();
+        let mut waiting = super::queue::WaitingQueue::new(self.queue_conf);
+        let mut expire_interval = tokio::time::interval(tokio::time::Duration::new(1, 0));
+        let mut retry_delay = None;
         loop {
             self.is_closing = false;
             self.is_awaiting_response = false;
 
             let mut sock = {
                 trace!("Getting connection for {}", self.host);
-                let connect_fut = self.tls_client.connect().fuse();
+                // The wait after a failure is part of connecting, so that requests keep being
+                // queued, or rejected while the breaker is open
+                let delay = retry_delay.take();
+                let connect_fut = async {
+                    if let Some(delay) = delay {
+                        self.backoff.wait(delay, &self.metrics_registry).await;
+                    }
+                    self.tls_client
+                        .connect(&self.backoff, &self.metrics_registry)
+                        .await
+                }
+                .fuse();
                 futures::pin_mut!(connect_fut);
 
                 loop {
                     futures::select! {
                         x = receiver.next() => {
                             match x {
-                                Some(x) => outer_router::Router::<router::Router, (), M>::reject_request(x),
+                                Some((_, x)) if self.backoff.state() == super::reconnect::BreakerState::Open => {
+                                    x.reject(super::Error::NotReady)
+                                }
+                                Some((ctx, x)) => waiting.push(ctx, x),
                                 None => {
                                     info!("All senders for {} dropped, exiting...", self.host);
                                     return
                                 }
                             };
                         }
+                        _ = expire_interval.tick().fuse() => {
+                            if self.backoff.state() == super::reconnect::BreakerState::Open {
+                                waiting.reject_all();
+                            } else {
+                                waiting.expire();
+                            }
+                        }
                         s = connect_fut => {
                             break s;
                         }
@@ -141,13 +181,14 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
                     futures::select! {
                         x = receiver.next() => {
                             match x {
-                                Some(x) => outer_router::Router::<router::Router, (), M>::reject_request(x),
+                                Some((ctx, x)) => waiting.push(ctx, x),
                                 None => {
                                     info!("{}", exit_str);
                                     return
                                 }
                             };
                         }
+                        _ = expire_interval.tick().fuse() => waiting.expire(),
                         s = setup_fut => {
                             break s;
                         }
@@ -158,14 +199,16 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
                         if r {
                             break;
                         } else {
-                            tokio::time::sleep(tokio::time::Duration::new(5, 0)).await;
+                            retry_delay = Some(self.backoff.failure(&self.metrics_registry));
                             continue;
                         }
                     }
                 }
             };
             trace!("Connection setup to {}", self.host);
+            self.backoff.success(&self.metrics_registry);
             self.metrics_registry.connection_status(true);
+            self.status.set_connected(true);
             let _ = ready_sender.send(setup_res).await;
 
             let (sock_read, mut sock_write) = tokio::io::split(sock);
@@ -177,20 +220,24 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
                 decode_fn: recv_msg,
             };
             let mut message_channel = msg_receiver.run().fuse();
-            let mut keepalive_interval = tokio::time::interval(tokio::time::Duration::new(120, 0));
+            let mut keepalive_interval = tokio::time::interval(self.timeouts.keepalive_interval);
 
             trace!("Entering event loop for {}", self.host);
             loop {
                 if self.pipelining || !self.is_awaiting_response {
+                    if let Some((ctx, r)) = waiting.pop() {
+                        match self._handle_request(ctx, r, &mut sock_write).await {
+                            Ok(_) => self.status.set_pending_commands(self.router.pending()),
+                            Err(_) => break,
+                        }
+                        continue;
+                    }
                     futures::select! {
                         r = receiver.next() => {
                             match r {
-                                Some(r) => match self._handle_request(r, &mut sock_write).await {
-                                    Ok(_) => {},
-                                    Err(_) => {
-                                        tokio::time::sleep(tokio::time::Duration::new(5, 0)).await;
-                                        break;
-                                    }
+                                Some((ctx, r)) => match self._handle_request(ctx, r, &mut sock_write).await {
+                                    Ok(_) => self.status.set_pending_commands(self.router.pending()),
+                                    Err(_) => break,
                                 },
                                 None => {
                                     info!("All senders for {} dropped, exiting...", self.host);
@@ -229,11 +276,12 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
                     }
                 } else {
                     let mut delay =
-                        Box::pin(tokio::time::sleep(tokio::time::Duration::new(15, 0)).fuse());
+                        Box::pin(tokio::time::sleep(self.timeouts.response).fuse());
                     let resp = futures::select! {
                         r = message_channel.next() => r,
                         _ = delay => {
                             warn!("Timeout awaiting response from {}", self.host);
+                            self.status.set_error("Timeout awaiting response");
                             break;
                         }
                     };
@@ -262,7 +310,8 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
                 }
             }
             self.metrics_registry.connection_status(false);
-            tokio::time::sleep(tokio::time::Duration::new(5, 0)).await;
+            self.status.set_connected(false);
+            retry_delay = Some(self.backoff.failure(&self.metrics_registry));
         }
     }
 
@@ -283,7 +332,7 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
         )
         .fuse();
         self.metrics_registry.request_sent();
-        let mut delay = Box::pin(tokio::time::sleep(tokio::time::Duration::new(15, 0)).fuse());
+        let mut delay = Box::pin(tokio::time::sleep(self.timeouts.response).fuse());
         futures::pin_mut!(receiver);
         let resp = futures::select! {
             r = receiver => r,
@@ -302,13 +351,17 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
 
     async fn _handle_request<W: std::marker::Unpin + tokio::io::AsyncWrite>(
         &mut self,
+        ctx: super::queue::RequestContext,
         req: outer_router::RequestMessage,
         sock_write: &mut W,
     ) -> Result<(), ()> {
         if let outer_router::RequestMessage::Logout(_) = req {
             self.is_closing = true;
         }
-        match self.router.handle_request(&(), req) {
+        match self
+            .router
+            .handle_request(&(), req, ctx.client_transaction_id.as_ref())
+        {
             Some((command, command_id)) => {
                 self.is_awaiting_response = true;
                 match self._send_command(command, sock_write, command_id).await {
@@ -330,6 +383,7 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
                         self.server_id,
                         response.response_msg()
                     );
+                    self.status.set_error(response.response_msg());
                 }
                 let transaction_id = match &response.transaction_id.client_transaction_id {
                     Some(i) => i,
@@ -342,20 +396,13 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
                     }
                 };
                 let is_closing = response.is_closing();
-                let transaction_id = match uuid::Uuid::parse_str(transaction_id) {
-                    Ok(i) => i,
-                    Err(e) => {
-                        error!(
-                            "Received response with invalid transaction UUID from {}: {}",
-                            self.server_id, e
-                        );
-                        return Err(());
-                    }
-                };
+                let transaction_id = transaction_id.clone();
                 self.router.handle_response(&transaction_id, *response);
+                self.status.set_pending_commands(self.router.pending());
                 Ok(is_closing)
             }
             tmch_proto::TMCHMessageType::Greeting(greeting) => {
+                self.status.update(|s| s.last_greeting = Some(Utc::now()));
                 self._process_greeting(greeting).await.map(|_| false)
             }
             o => {
@@ -387,6 +434,10 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
 
         if let tmch_proto::TMCHMessageType::Greeting(greeting) = msg.message {
             self.server_id = greeting.server_id.clone();
+            self.status.update(|s| {
+                s.server_id = Some(greeting.server_id.clone());
+                s.last_greeting = Some(Utc::now());
+            });
             info!("Connection open with: {}", self.server_id);
             match self._process_greeting(greeting).await {
                 Ok(_) => {}
@@ -404,6 +455,7 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
                 }),
                 Err(_) => {
                     info!("Restarting connection...");
+                    self.status.set_error("Login failed");
                     self._close(sock).await;
                     Err(false)
                 }
@@ -492,20 +544,20 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
 
     async fn _send_command<
         W: std::marker::Unpin + tokio::io::AsyncWrite,
-        I: Into<Option<uuid::Uuid>>,
+        I: Into<Option<String>>,
     >(
         &self,
         command: tmch_proto::TMCHCommandType,
         sock: &mut W,
         message_id: I,
-    ) -> Result<uuid::Uuid, ()> {
+    ) -> Result<String, ()> {
         let message_id = match message_id.into() {
             Some(m) => m,
-            None => uuid::Uuid::new_v4(),
+            None => uuid::Uuid::new_v4().hyphenated().to_string(),
         };
         let command = tmch_proto::TMCHCommand {
             command,
-            client_transaction_id: Some(message_id.hyphenated().to_string()),
+            client_transaction_id: Some(message_id.clone()),
             extension: None,
         };
         let message = tmch_proto::TMCHMessage {
@@ -529,6 +581,7 @@ impl<M: crate::metrics::Metrics + 'static> TMCHClient<M> {
 
     async fn _close(&mut self, sock: &mut super::epp_like::tls_client::TLSConnection) {
         self.router.drain();
+        self.status.set_pending_commands(0);
         sock.close().await
     }
 }
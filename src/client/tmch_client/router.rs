@@ -1,6 +1,6 @@
 use paste::paste;
 
-pub use super::super::{router, Error, Response};
+pub use super::super::{router, EPPError, Error, Response};
 
 pub type HandleReqReturn<T> = Result<super::tmch_proto::TMCHCommandType, Response<T>>;
 
@@ -14,7 +14,7 @@ macro_rules! router {
             type Response = super::tmch_proto::TMCHResponse;
 
             paste! {
-                $(fn [<$n _request>](&mut self, client: &(), req: &router::[<$n Request>], _command_id: uuid::Uuid) -> HandleReqReturn<router::[<$n Response>]> {
+                $(fn [<$n _request>](&mut self, client: &(), req: &router::[<$n Request>], _command_id: String) -> HandleReqReturn<router::[<$n Response>]> {
                     $req_handle(client, &req)
                 })*
 
@@ -22,21 +22,29 @@ macro_rules! router {
                     &mut self, return_path: router::Sender<router::[<$n Response>]>,
                     response: Self::Response, metrics: &M
                 ) {
+                    let trans_id = router::CommandTransactionID {
+                        client: response.transaction_id.client_transaction_id.as_deref().unwrap_or_default().to_owned(),
+                        server: response.transaction_id.server_transaction_id.to_owned(),
+                    };
                     let _ = if !response.is_success() {
-                        if response.is_server_error() {
-                            return_path.send(Err(Error::Err(format!("Server error: {}", response.response_msg()))))
-                        } else {
-                            return_path.send(Err(Error::Err(response.response_msg())))
-                        }
+                        return_path.send(Err(Error::EPP(EPPError {
+                            code: response.results.first().map(|r| (&r.code).into()).unwrap_or_default(),
+                            message: response.result_msg(),
+                            reasons: response.results.iter()
+                                .flat_map(|r| r.extra_values.iter().flatten())
+                                .map(|e| router::CommandExtraValue {
+                                    value: e.value.clone(),
+                                    reason: e.reason.clone(),
+                                })
+                                .collect(),
+                            transaction_id: trans_id,
+                        })))
                     } else {
-                        let trans_id = router::CommandTransactionID {
-                            client: response.transaction_id.client_transaction_id.as_deref().unwrap_or_default().to_owned(),
-                            server: response.transaction_id.server_transaction_id.to_owned(),
-                        };
                         match $res_handle(response, metrics) {
                             Ok(r) => return_path.send(Ok(router::CommandResponse {
                                 response: r,
                                 extra_values: vec![],
+                                unhandled_namespaces: vec![],
                                 transaction_id: Some(trans_id),
                             })),
                             Err(e) => return_path.send(Err(e))
@@ -61,6 +69,7 @@ router!(
     Poll,                        super::poll::handle_poll,                  super::poll::handle_poll_response;
     PollAck,                     super::poll::handle_poll_ack,              super::poll::handle_poll_ack_response;
     DomainCheck,                 request_nop,                               response_nop;
+    DomainBatchCheck,            request_nop,                               response_nop;
     DomainClaimsCheck,           request_nop,                               response_nop;
     DomainTrademarkCheck,        request_nop,                               response_nop;
     DomainInfo,                  request_nop,                               response_nop;
@@ -88,11 +97,25 @@ router!(
     RestoreRequest,              request_nop,                               response_nop;
     RestoreReport,               request_nop,                               response_nop;
     HostCheck,                   request_nop,                               response_nop;
+    HostBatchCheck,              request_nop,                               response_nop;
     HostInfo,                    request_nop,                               response_nop;
     HostCreate,                  request_nop,                               response_nop;
     HostDelete,                  request_nop,                               response_nop;
     HostUpdate,                  request_nop,                               response_nop;
+    NSSetCheck,                  request_nop,                               response_nop;
+    NSSetInfo,                   request_nop,                               response_nop;
+    NSSetCreate,                 request_nop,                               response_nop;
+    NSSetDelete,                 request_nop,                               response_nop;
+    NSSetUpdate,                 request_nop,                               response_nop;
+    NSSetTransfer,               request_nop,                               response_nop;
+    KeySetCheck,                 request_nop,                               response_nop;
+    KeySetInfo,                  request_nop,                               response_nop;
+    KeySetCreate,                request_nop,                               response_nop;
+    KeySetDelete,                request_nop,                               response_nop;
+    KeySetUpdate,                request_nop,                               response_nop;
+    KeySetTransfer,              request_nop,                               response_nop;
     ContactCheck,                request_nop,                               response_nop;
+    ContactBatchCheck,           request_nop,                               response_nop;
     ContactInfo,                 request_nop,                               response_nop;
     ContactCreate,               request_nop,                               response_nop;
     ContactDelete,               request_nop,                               response_nop;
@@ -108,6 +131,9 @@ router!(
     NominetContactValidate,      request_nop,                               response_nop;
     NominetLock,                 request_nop,                               response_nop;
     NominetUnlock,               request_nop,                               response_nop;
+    NominetUnrenew,              request_nop,                               response_nop;
+    NominetList,                 request_nop,                               response_nop;
+    NominetFork,                 request_nop,                               response_nop;
     Balance,                     request_nop,                               response_nop;
     MaintenanceList,             request_nop,                               response_nop;
     MaintenanceInfo,             request_nop,                               response_nop;
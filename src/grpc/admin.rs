@@ -0,0 +1,129 @@
+use super::super::client;
+use super::epp_proto;
+
+/// Admin service for inspecting the state of registry sessions
+#[derive(Debug)]
+pub struct EPPProxyAdmin {
+    pub client_router: std::sync::Arc<std::sync::RwLock<super::super::Router>>,
+}
+
+fn status_to_reply(
+    registry_name: String,
+    from: client::status::ClientStatus,
+) -> epp_proto::admin::RegistryStatusReply {
+    epp_proto::admin::RegistryStatusReply {
+        registry_name,
+        connected: from.connected,
+        server_id: from.server_id,
+        last_greeting: super::utils::chrono_to_proto(from.last_greeting),
+        objects: from.objects,
+        extensions: from.extensions,
+        errata: from.errata,
+        pipelining: from.pipelining,
+        pending_commands: from.pending_commands as u32,
+        last_error: from.last_error,
+        last_error_date: super::utils::chrono_to_proto(from.last_error_date),
+    }
+}
+
+fn login_security_to_reply(
+    registry_name: String,
+    from: client::status::ClientStatus,
+) -> epp_proto::admin::LoginSecurityReply {
+    use client::login_sec::{LoginSecurityEventLevel, LoginSecurityEventType};
+    use epp_proto::admin::login_security_event::{EventType, Level};
+
+    epp_proto::admin::LoginSecurityReply {
+        registry_name,
+        events: from
+            .login_security_events
+            .into_iter()
+            .map(|e| epp_proto::admin::LoginSecurityEvent {
+                r#type: match e.event_type {
+                    LoginSecurityEventType::Password => EventType::Password,
+                    LoginSecurityEventType::Certificate => EventType::Certificate,
+                    LoginSecurityEventType::Cipher => EventType::Cipher,
+                    LoginSecurityEventType::TLSProtocol => EventType::TlsProtocol,
+                    LoginSecurityEventType::NewPassword => EventType::NewPassword,
+                    LoginSecurityEventType::Statistical => EventType::Statistical,
+                    LoginSecurityEventType::Custom => EventType::Custom,
+                }
+                .into(),
+                name: e.name,
+                level: match e.level {
+                    LoginSecurityEventLevel::Warning => Level::Warning,
+                    LoginSecurityEventLevel::Error => Level::Error,
+                }
+                .into(),
+                expiration_date: super::utils::chrono_to_proto(e.expiration_date),
+                value: e.value,
+                duration: e.duration,
+                message: e.message,
+                received: super::utils::chrono_to_proto(Some(e.received)),
+            })
+            .collect(),
+        password_rotation_due: from.password_rotation_due,
+        password_rotated: super::utils::chrono_to_proto(from.password_rotated),
+    }
+}
+
+#[tonic::async_trait]
+impl epp_proto::admin::epp_proxy_admin_server::EppProxyAdmin for EPPProxyAdmin {
+    async fn registry_list(
+        &self,
+        _request: tonic::Request<()>,
+    ) -> Result<tonic::Response<epp_proto::admin::RegistryListReply>, tonic::Status> {
+        let statuses = self
+            .client_router
+            .read()
+            .map_err(|_| tonic::Status::internal("router lock poisoned"))?
+            .statuses();
+
+        Ok(tonic::Response::new(epp_proto::admin::RegistryListReply {
+            registries: statuses
+                .into_iter()
+                .map(|(id, s)| status_to_reply(id, s))
+                .collect(),
+        }))
+    }
+
+    async fn registry_status(
+        &self,
+        request: tonic::Request<epp_proto::admin::RegistryStatusRequest>,
+    ) -> Result<tonic::Response<epp_proto::admin::RegistryStatusReply>, tonic::Status> {
+        let request = request.into_inner();
+        let status = self
+            .client_router
+            .read()
+            .map_err(|_| tonic::Status::internal("router lock poisoned"))?
+            .status_by_id(&request.registry_name);
+
+        match status {
+            Some(s) => Ok(tonic::Response::new(status_to_reply(
+                request.registry_name,
+                s,
+            ))),
+            None => Err(tonic::Status::not_found("unknown registry")),
+        }
+    }
+
+    async fn registry_login_security(
+        &self,
+        request: tonic::Request<epp_proto::admin::RegistryStatusRequest>,
+    ) -> Result<tonic::Response<epp_proto::admin::LoginSecurityReply>, tonic::Status> {
+        let request = request.into_inner();
+        let status = self
+            .client_router
+            .read()
+            .map_err(|_| tonic::Status::internal("router lock poisoned"))?
+            .status_by_id(&request.registry_name);
+
+        match status {
+            Some(s) => Ok(tonic::Response::new(login_security_to_reply(
+                request.registry_name,
+                s,
+            ))),
+            None => Err(tonic::Status::not_found("unknown registry")),
+        }
+    }
+}
@@ -50,7 +50,7 @@ pub fn domain_status_from_i32(from: i32) -> Option<client::domain::Status> {
     })
 }
 
-fn i32_from_domain_status(from: client::domain::Status) -> i32 {
+pub fn i32_from_domain_status(from: client::domain::Status) -> i32 {
     match from {
         client::domain::Status::ClientDeleteProhibited => {
             epp_proto::domain_common::DomainStatus::ClientDeleteProhibited.into()
@@ -107,6 +107,10 @@ impl From<client::domain::CheckResponse> for epp_proto::domain::DomainCheckReply
         epp_proto::domain::DomainCheckReply {
             available: res.avail,
             reason: res.reason,
+            normalised_fees: super::fee::normalised_check_fees(
+                &res.fee_check,
+                &res.donuts_fee_check,
+            ),
             fee_check: res.fee_check.map(Into::into),
             donuts_fee_check: res.donuts_fee_check.map(Into::into),
             registry_name: String::new(),
@@ -120,6 +124,77 @@ impl From<client::domain::CheckResponse> for epp_proto::domain::DomainCheckReply
     }
 }
 
+pub fn batch_check_result(
+    registry_name: &str,
+    res: client::domain::BatchCheckResult,
+) -> epp_proto::domain::domain_batch_check_reply::CheckResult {
+    epp_proto::domain::domain_batch_check_reply::CheckResult {
+        name: res.name,
+        registry_name: registry_name.to_string(),
+        available: res.avail,
+        reason: res.reason,
+        normalised_fees: super::fee::normalised_check_fees(&res.fee_check, &res.donuts_fee_check),
+        fee_check: res.fee_check.map(Into::into),
+        donuts_fee_check: res.donuts_fee_check.map(Into::into),
+        error: None,
+    }
+}
+
+pub fn batch_check_error(
+    name: String,
+    registry_name: &str,
+    error: &str,
+) -> epp_proto::domain::domain_batch_check_reply::CheckResult {
+    epp_proto::domain::domain_batch_check_reply::CheckResult {
+        name,
+        registry_name: registry_name.to_string(),
+        available: false,
+        reason: None,
+        fee_check: None,
+        donuts_fee_check: None,
+        normalised_fees: None,
+        error: Some(error.to_string()),
+    }
+}
+
+pub fn search_result(
+    registry_name: &str,
+    res: client::domain::BatchCheckResult,
+) -> epp_proto::domain::domain_search_reply::SearchResult {
+    let prices = client::fee::DomainPrices::from_check(
+        res.fee_check.as_ref(),
+        res.donuts_fee_check.as_ref(),
+    )
+    .unwrap_or_default();
+    epp_proto::domain::domain_search_reply::SearchResult {
+        name: res.name,
+        registry_name: registry_name.to_string(),
+        available: res.avail,
+        reason: res.reason,
+        premium: prices.premium,
+        fee_class: prices.class,
+        currency: prices.currency,
+        create_price: prices.create,
+        renew_price: prices.renew,
+        transfer_price: prices.transfer,
+        restore_price: prices.restore,
+        error: None,
+    }
+}
+
+pub fn search_error(
+    name: String,
+    registry_name: &str,
+    error: &str,
+) -> epp_proto::domain::domain_search_reply::SearchResult {
+    epp_proto::domain::domain_search_reply::SearchResult {
+        name,
+        registry_name: registry_name.to_string(),
+        error: Some(error.to_string()),
+        ..Default::default()
+    }
+}
+
 impl From<client::domain::InfoResponse> for epp_proto::domain::DomainInfoReply {
     fn from(res: client::domain::InfoResponse) -> Self {
         epp_proto::domain::DomainInfoReply {
@@ -231,6 +306,11 @@ impl From<client::domain::InfoResponse> for epp_proto::domain::DomainInfoReply {
                 }),
             }),
             launch_info: res.launch_info.map(Into::into),
+            normalised_fees: super::fee::normalised_fees(
+                client::fee::Command::Info,
+                &None,
+                &res.donuts_fee_data,
+            ),
             donuts_fee_data: res.donuts_fee_data.map(Into::into),
             verisign_whois_info: res
                 .whois_info
@@ -270,6 +350,7 @@ impl From<client::domain::InfoResponse> for epp_proto::domain::DomainInfoReply {
             }),
             keysys: res.keysys.map(Into::into),
             nominet_ext: res.nominet_ext.map(Into::into),
+            allocation_token: res.allocation_token,
         }
     }
 }
@@ -281,6 +362,11 @@ impl From<client::domain::CreateResponse> for epp_proto::domain::DomainCreateRep
             pending: res.pending,
             creation_date: super::utils::chrono_to_proto(res.data.creation_date),
             expiry_date: super::utils::chrono_to_proto(res.data.expiration_date),
+            normalised_fees: super::fee::normalised_fees(
+                client::fee::Command::Create,
+                &res.fee_data,
+                &res.donuts_fee_data,
+            ),
             fee_data: res.fee_data.map(Into::into),
             donuts_fee_data: res.donuts_fee_data.map(Into::into),
             registry_name: String::new(),
@@ -302,6 +388,11 @@ impl From<client::domain::RenewResponse> for epp_proto::domain::DomainRenewReply
             name: res.data.name,
             pending: res.pending,
             expiry_date: super::utils::chrono_to_proto(res.data.new_expiry_date),
+            normalised_fees: super::fee::normalised_fees(
+                client::fee::Command::Renew,
+                &res.fee_data,
+                &res.donuts_fee_data,
+            ),
             fee_data: res.fee_data.map(Into::into),
             donuts_fee_data: res.donuts_fee_data.map(Into::into),
             registry_name: String::new(),
@@ -333,6 +424,11 @@ impl From<client::domain::TransferResponse> for epp_proto::domain::DomainTransfe
             act_client_id: res.data.act_client_id,
             act_date: super::utils::chrono_to_proto(Some(res.data.act_date)),
             expiry_date: super::utils::chrono_to_proto(res.data.expiry_date),
+            normalised_fees: super::fee::normalised_fees(
+                client::fee::Command::Transfer,
+                &res.fee_data,
+                &res.donuts_fee_data,
+            ),
             fee_data: res.fee_data.map(Into::into),
             donuts_fee_data: res.donuts_fee_data.map(Into::into),
             registry_name: String::new(),
@@ -379,6 +475,11 @@ impl From<client::domain::UpdateResponse> for epp_proto::domain::DomainUpdateRep
     fn from(res: client::domain::UpdateResponse) -> Self {
         epp_proto::domain::DomainUpdateReply {
             pending: res.pending,
+            normalised_fees: super::fee::normalised_fees(
+                client::fee::Command::Update,
+                &res.fee_data,
+                &res.donuts_fee_data,
+            ),
             fee_data: res.fee_data.map(Into::into),
             donuts_fee_data: res.donuts_fee_data.map(Into::into),
             registry_name: String::new(),
@@ -0,0 +1,186 @@
+use super::super::client;
+use super::epp_proto;
+
+impl From<client::email_forward::CheckResponse>
+    for epp_proto::email_forward::EmailForwardCheckReply
+{
+    fn from(res: client::email_forward::CheckResponse) -> Self {
+        epp_proto::email_forward::EmailForwardCheckReply {
+            available: res.avail,
+            reason: res.reason,
+            normalised_fees: super::fee::normalised_check_fees(&res.fee_check, &None),
+            fee_check: res.fee_check.map(Into::into),
+            registry_name: String::new(),
+            cmd_resp: None,
+        }
+    }
+}
+
+impl From<client::email_forward::InfoResponse> for epp_proto::email_forward::EmailForwardInfoReply {
+    fn from(res: client::email_forward::InfoResponse) -> Self {
+        epp_proto::email_forward::EmailForwardInfoReply {
+            name: res.name,
+            registry_id: res.registry_id,
+            statuses: res
+                .statuses
+                .into_iter()
+                .map(super::domain::i32_from_domain_status)
+                .collect(),
+            registrant: res.registrant,
+            contacts: res
+                .contacts
+                .into_iter()
+                .map(|c| epp_proto::domain::Contact {
+                    id: c.contact_id,
+                    r#type: c.contact_type,
+                })
+                .collect(),
+            forward_to: res.forward_to,
+            client_id: res.client_id,
+            client_created_id: res.client_created_id,
+            creation_date: super::utils::chrono_to_proto(res.creation_date),
+            expiry_date: super::utils::chrono_to_proto(res.expiry_date),
+            last_updated_client: res.last_updated_client,
+            last_updated_date: super::utils::chrono_to_proto(res.last_updated_date),
+            last_transfer_date: super::utils::chrono_to_proto(res.last_transfer_date),
+            registry_name: String::new(),
+            rgp_state: res
+                .rgp_state
+                .into_iter()
+                .map(super::rgp::i32_from_restore_status)
+                .collect(),
+            auth_info: res.auth_info,
+            verisign_whois_info: res
+                .whois_info
+                .map(|i| epp_proto::domain::VerisignWhoisInfo {
+                    registrar: i.registrar,
+                    whois_server: i.whois_server,
+                    url: i.url,
+                    iris_server: i.iris_server,
+                }),
+            personal_registration: res.personal_registration.map(|p| {
+                epp_proto::personal_registration::PersonalRegistrationInfo {
+                    consent_id: p.consent_id,
+                }
+            }),
+            cmd_resp: None,
+        }
+    }
+}
+
+impl From<client::email_forward::CreateResponse>
+    for epp_proto::email_forward::EmailForwardCreateReply
+{
+    fn from(res: client::email_forward::CreateResponse) -> Self {
+        epp_proto::email_forward::EmailForwardCreateReply {
+            name: res.data.name,
+            pending: res.pending,
+            creation_date: super::utils::chrono_to_proto(res.data.creation_date),
+            expiry_date: super::utils::chrono_to_proto(res.data.expiration_date),
+            normalised_fees: super::fee::normalised_fees(
+                client::fee::Command::Create,
+                &res.fee_data,
+                &None,
+            ),
+            fee_data: res.fee_data.map(Into::into),
+            registry_name: String::new(),
+            cmd_resp: None,
+            personal_registration: res.data.personal_registration.map(|p| {
+                epp_proto::personal_registration::PersonalRegistrationCreate {
+                    bundled_rate: p.bundled_rate,
+                }
+            }),
+        }
+    }
+}
+
+impl From<client::email_forward::DeleteResponse>
+    for epp_proto::email_forward::EmailForwardDeleteReply
+{
+    fn from(res: client::email_forward::DeleteResponse) -> Self {
+        epp_proto::email_forward::EmailForwardDeleteReply {
+            pending: res.pending,
+            normalised_fees: super::fee::normalised_fees(
+                client::fee::Command::Delete,
+                &res.fee_data,
+                &None,
+            ),
+            fee_data: res.fee_data.map(Into::into),
+            registry_name: String::new(),
+            cmd_resp: None,
+        }
+    }
+}
+
+impl From<client::email_forward::UpdateResponse>
+    for epp_proto::email_forward::EmailForwardUpdateReply
+{
+    fn from(res: client::email_forward::UpdateResponse) -> Self {
+        epp_proto::email_forward::EmailForwardUpdateReply {
+            pending: res.pending,
+            normalised_fees: super::fee::normalised_fees(
+                client::fee::Command::Update,
+                &res.fee_data,
+                &None,
+            ),
+            fee_data: res.fee_data.map(Into::into),
+            registry_name: String::new(),
+            cmd_resp: None,
+        }
+    }
+}
+
+impl From<client::email_forward::RenewResponse>
+    for epp_proto::email_forward::EmailForwardRenewReply
+{
+    fn from(res: client::email_forward::RenewResponse) -> Self {
+        epp_proto::email_forward::EmailForwardRenewReply {
+            name: res.data.name,
+            pending: res.pending,
+            expiry_date: super::utils::chrono_to_proto(res.data.new_expiry_date),
+            normalised_fees: super::fee::normalised_fees(
+                client::fee::Command::Renew,
+                &res.fee_data,
+                &None,
+            ),
+            fee_data: res.fee_data.map(Into::into),
+            registry_name: String::new(),
+            cmd_resp: None,
+            personal_registration: res.data.personal_registration.map(|p| {
+                epp_proto::personal_registration::PersonalRegistrationCreate {
+                    bundled_rate: p.bundled_rate,
+                }
+            }),
+        }
+    }
+}
+
+impl From<client::email_forward::TransferResponse>
+    for epp_proto::email_forward::EmailForwardTransferReply
+{
+    fn from(res: client::email_forward::TransferResponse) -> Self {
+        epp_proto::email_forward::EmailForwardTransferReply {
+            pending: res.pending,
+            name: res.data.name,
+            status: super::utils::i32_from_transfer_status(res.data.status),
+            requested_client_id: res.data.requested_client_id,
+            requested_date: super::utils::chrono_to_proto(Some(res.data.requested_date)),
+            act_client_id: res.data.act_client_id,
+            act_date: super::utils::chrono_to_proto(Some(res.data.act_date)),
+            expiry_date: super::utils::chrono_to_proto(res.data.expiry_date),
+            normalised_fees: super::fee::normalised_fees(
+                client::fee::Command::Transfer,
+                &res.fee_data,
+                &None,
+            ),
+            fee_data: res.fee_data.map(Into::into),
+            registry_name: String::new(),
+            cmd_resp: None,
+            personal_registration: res.data.personal_registration.map(|p| {
+                epp_proto::personal_registration::PersonalRegistrationCreate {
+                    bundled_rate: p.bundled_rate,
+                }
+            }),
+        }
+    }
+}
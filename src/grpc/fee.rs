@@ -46,6 +46,7 @@ impl From<epp_proto::fee::FeeCheck> for client::fee::FeeCheck {
                         unit: super::utils::period_unit_from_i32(p.unit),
                         value: p.value,
                     }),
+                    custom_name: c.custom_name,
                     phase: c.phase,
                     sub_phase: c.sub_phase,
                 })
@@ -226,6 +227,15 @@ impl TryFrom<epp_proto::fee::DonutsFeeData> for client::fee::DonutsFeeData {
     }
 }
 
+impl From<epp_proto::fee::FeeCeiling> for client::fee::FeeCeiling {
+    fn from(from: epp_proto::fee::FeeCeiling) -> Self {
+        client::fee::FeeCeiling {
+            max_price: from.max_price,
+            currency: from.currency,
+        }
+    }
+}
+
 impl From<epp_proto::fee::FeeAgreement> for client::fee::FeeAgreement {
     fn from(from: epp_proto::fee::FeeAgreement) -> Self {
         client::fee::FeeAgreement {
@@ -253,3 +263,68 @@ impl From<epp_proto::fee::FeeAgreement> for client::fee::FeeAgreement {
         }
     }
 }
+
+impl From<client::fee::NormalisedFees> for epp_proto::fee::NormalisedFees {
+    fn from(from: client::fee::NormalisedFees) -> Self {
+        epp_proto::fee::NormalisedFees {
+            currency: from.currency,
+            premium: from.premium,
+            class: from.class,
+            commands: from
+                .commands
+                .into_iter()
+                .map(|c| epp_proto::fee::normalised_fees::CommandFee {
+                    command: i32_from_fee_command(c.command),
+                    command_name: c.command_name,
+                    period: c.period.map(|p| epp_proto::common::Period {
+                        unit: super::utils::i32_from_period_unit(p.unit),
+                        value: p.value,
+                    }),
+                    total: c.total,
+                    components: c
+                        .components
+                        .into_iter()
+                        .map(|f| epp_proto::fee::normalised_fees::Component {
+                            amount: f.amount,
+                            description: f.description,
+                            refundable: f.refundable,
+                            grace_period: f.grace_period,
+                            delayed: f.delayed,
+                        })
+                        .collect(),
+                    reason: c.reason,
+                })
+                .collect(),
+            balance: from.balance,
+            credit_limit: from.credit_limit,
+        }
+    }
+}
+
+/// Normalised fees of a check response, to be returned alongside the raw fee data
+///
+/// # Arguments
+/// * `fee_check` - IETF fee extension data
+/// * `donuts_fee_check` - Donuts charge extension data
+pub fn normalised_check_fees(
+    fee_check: &Option<client::fee::FeeCheckData>,
+    donuts_fee_check: &Option<client::fee::DonutsFeeData>,
+) -> Option<epp_proto::fee::NormalisedFees> {
+    client::fee::NormalisedFees::from_check(fee_check.as_ref(), donuts_fee_check.as_ref())
+        .map(Into::into)
+}
+
+/// Normalised fees of a transform command response, to be returned alongside the raw fee data
+///
+/// # Arguments
+/// * `command` - Command the response is to
+/// * `fee_data` - IETF fee extension data
+/// * `donuts_fee_data` - Donuts charge extension data
+pub fn normalised_fees(
+    command: client::fee::Command,
+    fee_data: &Option<client::fee::FeeData>,
+    donuts_fee_data: &Option<client::fee::DonutsFeeData>,
+) -> Option<epp_proto::fee::NormalisedFees> {
+    client::fee::NormalisedFees::from_data(command, fee_data.as_ref(), donuts_fee_data.as_ref())
+        .map(Into::into)
+}
@@ -0,0 +1,152 @@
+use std::convert::TryFrom;
+use super::super::client;
+use super::epp_proto;
+
+impl From<epp_proto::fred::Nameserver> for client::nsset::Nameserver {
+    fn from(res: epp_proto::fred::Nameserver) -> Self {
+        client::nsset::Nameserver {
+            name: res.name,
+            addresses: res.addresses,
+        }
+    }
+}
+
+impl From<client::nsset::Nameserver> for epp_proto::fred::Nameserver {
+    fn from(res: client::nsset::Nameserver) -> Self {
+        epp_proto::fred::Nameserver {
+            name: res.name,
+            addresses: res.addresses,
+        }
+    }
+}
+
+pub fn report_level_from_proto(from: Option<u32>) -> Result<Option<u8>, tonic::Status> {
+    match from {
+        Some(l) if l > 10 => Err(tonic::Status::invalid_argument(
+            "report level must be between 0 and 10",
+        )),
+        Some(l) => Ok(Some(l as u8)),
+        None => Ok(None),
+    }
+}
+
+pub fn dns_key_from_proto(
+    from: epp_proto::fred::DnsKey,
+) -> Result<client::keyset::DNSKey, tonic::Status> {
+    Ok(client::keyset::DNSKey {
+        flags: u16::try_from(from.flags)
+            .map_err(|_| tonic::Status::invalid_argument("DNSKEY flags out of range"))?,
+        protocol: u8::try_from(from.protocol)
+            .map_err(|_| tonic::Status::invalid_argument("DNSKEY protocol out of range"))?,
+        algorithm: u8::try_from(from.algorithm)
+            .map_err(|_| tonic::Status::invalid_argument("DNSKEY algorithm out of range"))?,
+        public_key: from.public_key,
+    })
+}
+
+impl From<client::keyset::DNSKey> for epp_proto::fred::DnsKey {
+    fn from(res: client::keyset::DNSKey) -> Self {
+        epp_proto::fred::DnsKey {
+            flags: res.flags as u32,
+            protocol: res.protocol as u32,
+            algorithm: res.algorithm as u32,
+            public_key: res.public_key,
+        }
+    }
+}
+
+impl From<client::nsset::InfoResponse> for epp_proto::fred::NsSetInfoReply {
+    fn from(res: client::nsset::InfoResponse) -> Self {
+        epp_proto::fred::NsSetInfoReply {
+            id: res.id,
+            registry_id: res.registry_id,
+            statuses: res.statuses,
+            client_id: res.client_id,
+            client_created_id: res.client_created_id,
+            creation_date: super::utils::chrono_to_proto(res.creation_date),
+            last_updated_client: res.last_updated_client,
+            last_updated_date: super::utils::chrono_to_proto(res.last_updated_date),
+            last_transfer_date: super::utils::chrono_to_proto(res.last_transfer_date),
+            auth_info: res.auth_info,
+            nameservers: res.nameservers.into_iter().map(Into::into).collect(),
+            tech_contacts: res.tech_contacts,
+            report_level: res.report_level.map(|l| l as u32),
+            cmd_resp: None,
+        }
+    }
+}
+
+impl From<client::keyset::InfoResponse> for epp_proto::fred::KeySetInfoReply {
+    fn from(res: client::keyset::InfoResponse) -> Self {
+        epp_proto::fred::KeySetInfoReply {
+            id: res.id,
+            registry_id: res.registry_id,
+            statuses: res.statuses,
+            client_id: res.client_id,
+            client_created_id: res.client_created_id,
+            creation_date: super::utils::chrono_to_proto(res.creation_date),
+            last_updated_client: res.last_updated_client,
+            last_updated_date: super::utils::chrono_to_proto(res.last_updated_date),
+            last_transfer_date: super::utils::chrono_to_proto(res.last_transfer_date),
+            auth_info: res.auth_info,
+            keys: res.keys.into_iter().map(Into::into).collect(),
+            tech_contacts: res.tech_contacts,
+            cmd_resp: None,
+        }
+    }
+}
+
+fn object_type_to_proto(from: client::fred::ObjectType) -> i32 {
+    match from {
+        client::fred::ObjectType::NSSet => epp_proto::fred::ObjectType::NsSet.into(),
+        client::fred::ObjectType::KeySet => epp_proto::fred::ObjectType::KeySet.into(),
+    }
+}
+
+impl From<client::fred::TransferData> for epp_proto::fred::Transfer {
+    fn from(res: client::fred::TransferData) -> Self {
+        epp_proto::fred::Transfer {
+            object_type: object_type_to_proto(res.object_type),
+            id: res.id,
+            transfer_date: super::utils::chrono_to_proto(Some(res.transfer_date)),
+            client_id: res.client_id,
+        }
+    }
+}
+
+impl From<client::fred::IdleDeleteData> for epp_proto::fred::IdleDelete {
+    fn from(res: client::fred::IdleDeleteData) -> Self {
+        epp_proto::fred::IdleDelete {
+            object_type: object_type_to_proto(res.object_type),
+            id: res.id,
+        }
+    }
+}
+
+impl From<client::fred::NSSetTestData> for epp_proto::fred::NsSetTest {
+    fn from(res: client::fred::NSSetTestData) -> Self {
+        epp_proto::fred::NsSetTest {
+            id: res.id,
+            names: res.names,
+            results: res
+                .results
+                .into_iter()
+                .map(|r| epp_proto::fred::ns_set_test::TestResult {
+                    test_name: r.test_name,
+                    passed: r.passed,
+                    note: r.note,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<client::fred::LowCreditData> for epp_proto::fred::LowCredit {
+    fn from(res: client::fred::LowCreditData) -> Self {
+        epp_proto::fred::LowCredit {
+            zone: res.zone,
+            limit: res.limit,
+            credit: res.credit,
+        }
+    }
+}
@@ -5,11 +5,14 @@ use crate::grpc::utils::proto_to_chrono;
 use futures::sink::SinkExt;
 use std::convert::{TryFrom, TryInto};
 
+mod admin;
 mod contact;
 mod dac;
 mod domain;
+mod email_forward;
 mod eurid;
 mod fee;
+mod fred;
 mod host;
 mod isnic;
 mod keysys;
@@ -17,6 +20,7 @@ mod launch;
 mod maintenance;
 mod mark;
 mod nominet;
+mod provision;
 mod rgp;
 mod tmch;
 mod utils;
@@ -27,6 +31,10 @@ pub mod epp_proto {
     pub const FILE_DESCRIPTOR_SET: &[u8] =
         tonic::include_file_descriptor_set!("file_descriptor_set");
 
+    pub mod admin {
+        tonic::include_proto!("epp.admin");
+    }
+
     pub mod common {
         tonic::include_proto!("epp.common");
     }
@@ -39,10 +47,18 @@ pub mod epp_proto {
         tonic::include_proto!("epp.domain_common");
     }
 
+    pub mod email_forward {
+        tonic::include_proto!("epp.email_forward");
+    }
+
     pub mod host {
         tonic::include_proto!("epp.host");
     }
 
+    pub mod fred {
+        tonic::include_proto!("epp.fred");
+    }
+
     pub mod contact {
         tonic::include_proto!("epp.contact");
 
@@ -108,9 +124,12 @@ pub mod epp_proto {
     }
 }
 
+pub use admin::EPPProxyAdmin;
+
 #[derive(Debug)]
 pub struct EPPProxy {
-    pub client_router: super::Router,
+    /// Shared so that the routing table can be swapped out on config reload
+    pub client_router: std::sync::Arc<std::sync::RwLock<super::Router>>,
 }
 
 impl From<client::traficom::TrnData> for epp_proto::traficom::TrnData {
@@ -130,10 +149,13 @@ impl From<client::traficom::TrnData> for epp_proto::traficom::TrnData {
 // }
 
 fn client_by_domain_or_id(
-    router: &super::Router,
+    router: &std::sync::RwLock<super::Router>,
     domain: &str,
     registry_id: Option<String>,
 ) -> Result<(client::RequestSender, String), tonic::Status> {
+    let router = router
+        .read()
+        .map_err(|_| tonic::Status::internal("router lock poisoned"))?;
     if let Some(r) = registry_id {
         if let Some(c) = router.client_by_id(&r) {
             return Ok((c, r));
@@ -145,13 +167,288 @@ fn client_by_domain_or_id(
     }
 }
 
-fn client_by_id(router: &super::Router, id: &str) -> Result<client::RequestSender, tonic::Status> {
+fn client_by_id(
+    router: &std::sync::RwLock<super::Router>,
+    id: &str,
+) -> Result<client::RequestSender, tonic::Status> {
+    let router = router
+        .read()
+        .map_err(|_| tonic::Status::internal("router lock poisoned"))?;
     match router.client_by_id(id) {
         Some(c) => Ok(c),
         None => Err(tonic::Status::not_found("unknown registry")),
     }
 }
 
+fn max_check_size(
+    router: &std::sync::RwLock<super::Router>,
+    id: &str,
+) -> Result<usize, tonic::Status> {
+    let router = router
+        .read()
+        .map_err(|_| tonic::Status::internal("router lock poisoned"))?;
+    Ok(router.max_check_size(id))
+}
+
+fn fee_policy(
+    router: &std::sync::RwLock<super::Router>,
+    id: &str,
+) -> Result<Option<client::fee::FeePolicy>, tonic::Status> {
+    let router = router
+        .read()
+        .map_err(|_| tonic::Status::internal("router lock poisoned"))?;
+    Ok(router.fee_policy(id))
+}
+
+/// Fee extensions that define agreements to send with billable commands; the older drafts
+/// only quote prices
+const FEE_AGREEMENT_EXTENSIONS: [&str; 2] = [
+    "urn:ietf:params:xml:ns:epp:fee-1.0",
+    "urn:ietf:params:xml:ns:fee-0.11",
+];
+
+/// Did a registry's session log in with a fee extension that takes agreements
+fn fee_agreement_supported(
+    router: &std::sync::RwLock<super::Router>,
+    id: &str,
+) -> Result<bool, tonic::Status> {
+    let router = router
+        .read()
+        .map_err(|_| tonic::Status::internal("router lock poisoned"))?;
+    Ok(router.status_by_id(id).map_or(false, |s| {
+        s.extensions
+            .iter()
+            .any(|e| FEE_AGREEMENT_EXTENSIONS.contains(&e.as_str()))
+    }))
+}
+
+/// Fee agreements to send with a billable command
+#[derive(Default)]
+struct FeeAgreements {
+    fee_agreement: Option<client::fee::FeeAgreement>,
+    donuts_fee_agreement: Option<client::fee::DonutsFeeData>,
+}
+
+impl FeeAgreements {
+    /// Replaces the agreements from a fee policy check with any the caller gave explicitly
+    fn or_explicit(
+        self,
+        fee_agreement: Option<epp_proto::fee::FeeAgreement>,
+        donuts_fee_agreement: Option<epp_proto::fee::DonutsFeeData>,
+    ) -> Result<Self, tonic::Status> {
+        if fee_agreement.is_none() && donuts_fee_agreement.is_none() {
+            return Ok(self);
+        }
+        Ok(FeeAgreements {
+            fee_agreement: fee_agreement.map(Into::into),
+            donuts_fee_agreement: donuts_fee_agreement
+                .map(TryInto::try_into)
+                .map_or(Ok(None), |v| v.map(Some))?,
+        })
+    }
+}
+
+/// Checks the registry price of a billable command against the caller's maximum price,
+/// returning agreements to the quoted fees to send with the command
+///
+/// Nothing is checked if the registry has no fee policy and the caller gave no maximum price.
+/// Sessions on fee-0.5 to fee-0.9 have their prices checked, but get no agreement as those
+/// drafts don't define one.
+async fn enforce_fee_policy(
+    router: &std::sync::RwLock<super::Router>,
+    registry_name: &str,
+    name: &str,
+    command: client::fee::Command,
+    period: Option<client::Period>,
+    ceiling: Option<epp_proto::fee::FeeCeiling>,
+    allocation_token: Option<&str>,
+    sender: &mut client::RequestSender,
+) -> Result<FeeAgreements, tonic::Status> {
+    let policy = match (fee_policy(router, registry_name)?, &ceiling) {
+        (Some(p), _) => p,
+        (None, Some(_)) => client::fee::FeePolicy::default(),
+        (None, None) => return Ok(FeeAgreements::default()),
+    };
+    let ceiling: client::fee::FeeCeiling = match ceiling {
+        Some(c) => c.into(),
+        None if policy.require_max_price => {
+            return Err(tonic::Status::failed_precondition(
+                client::fee::FeeCeilingError::NoCeiling.to_string(),
+            ));
+        }
+        None => return Ok(FeeAgreements::default()),
+    };
+
+    let fee_check = client::fee::FeeCheck {
+        currency: ceiling.currency.clone(),
+        commands: vec![client::fee::FeeCheckCommand {
+            command,
+            period,
+            custom_name: None,
+            phase: None,
+            sub_phase: None,
+        }],
+    };
+    // Donuts charge data comes back on a plain check
+    let res = match client::domain::check(
+        name,
+        Some(fee_check),
+        None,
+        None,
+        allocation_token,
+        sender,
+    )
+    .await
+    {
+        Err(client::Error::Unsupported) => {
+            client::domain::check(name, None, None, None, allocation_token, sender).await
+        }
+        r => r,
+    }?
+    .response;
+
+    let fees = client::fee::NormalisedFees::from_check(
+        res.fee_check.as_ref(),
+        res.donuts_fee_check.as_ref(),
+    );
+    ceiling
+        .check(command, fees.as_ref(), &policy)
+        .map_err(|e| tonic::Status::failed_precondition(e.to_string()))?;
+
+    Ok(match &res.fee_check {
+        Some(fee_check) if fee_agreement_supported(router, registry_name)? => FeeAgreements {
+            fee_agreement: fee_check.agreement(command),
+            donuts_fee_agreement: None,
+        },
+        Some(_) => FeeAgreements::default(),
+        None => FeeAgreements {
+            fee_agreement: None,
+            donuts_fee_agreement: res
+                .donuts_fee_check
+                .as_ref()
+                .and_then(|f| f.agreement(command)),
+        },
+    })
+}
+
+const MISSING_CHECK_RESULT: &str = "registry didn't return a result";
+
+/// Domains to check on a single registry
+struct CheckGroup {
+    registry_name: String,
+    sender: client::RequestSender,
+    max_check_size: usize,
+    names: Vec<String>,
+}
+
+/// Groups domains by the registry responsible for them
+///
+/// Returns the groups, and the domains no registry is responsible for.
+///
+/// # Arguments
+/// * `router` - Router to look registries up in
+/// * `names` - Domains to group
+/// * `registry_name` - Registry to send every domain to, an unknown registry is an error
+fn group_by_registry(
+    router: &std::sync::RwLock<super::Router>,
+    names: &[String],
+    registry_name: Option<&str>,
+) -> Result<(Vec<CheckGroup>, Vec<String>), tonic::Status> {
+    let router = router
+        .read()
+        .map_err(|_| tonic::Status::internal("router lock poisoned"))?;
+    let registry = match registry_name {
+        Some(r) => match router.client_by_id(r) {
+            Some(c) => Some((c, r.to_string())),
+            None => return Err(tonic::Status::not_found("unknown registry")),
+        },
+        None => None,
+    };
+    let mut groups: Vec<CheckGroup> = vec![];
+    let mut unrouted = vec![];
+    for name in names {
+        let client = registry.clone().or_else(|| router.client_by_domain(name));
+        match client {
+            Some((sender, registry_name)) => {
+                match groups.iter_mut().find(|g| g.registry_name == registry_name) {
+                    Some(g) => g.names.push(name.clone()),
+                    None => groups.push(CheckGroup {
+                        max_check_size: router.max_check_size(&registry_name),
+                        registry_name,
+                        sender,
+                        names: vec![name.clone()],
+                    }),
+                }
+            }
+            None => unrouted.push(name.clone()),
+        }
+    }
+    Ok((groups, unrouted))
+}
+
+/// Checks objects that all live on a single registry
+///
+/// Returns a result for every object, in the order given, and the command response of each
+/// chunk sent to the registry.
+///
+/// # Arguments
+/// * `router` - Router to look the registry up in
+/// * `registry_name` - Registry to check the objects on
+/// * `objects` - Objects to check, already deduplicated
+/// * `check` - Sends the check command for a single chunk
+/// * `check_results` - Splits a check response into the ID and result of each object
+/// * `error_result` - Builds the result for an object that couldn't be checked
+async fn single_registry_batch_check<T, R, F, Fut>(
+    router: &std::sync::RwLock<super::Router>,
+    registry_name: &str,
+    objects: Vec<String>,
+    check: F,
+    check_results: impl Fn(T) -> Vec<(String, R)>,
+    error_result: impl Fn(String, &str) -> R,
+) -> Result<(Vec<R>, Vec<epp_proto::common::CommandResponse>), tonic::Status>
+where
+    F: Fn(Vec<String>, client::RequestSender) -> Fut,
+    Fut: std::future::Future<Output = Result<client::CommandResponse<T>, client::Error>>,
+{
+    let sender = client_by_id(router, registry_name)?;
+    let max_check_size = max_check_size(router, registry_name)?;
+
+    let chunks = utils::chunked_check(objects.clone(), max_check_size, |chunk| {
+        check(chunk, sender.clone())
+    })
+    .await;
+
+    let mut results = std::collections::HashMap::new();
+    let mut cmd_resp = vec![];
+    for (chunk, res) in chunks {
+        match res {
+            Ok(res) => {
+                let (res, chunk_cmd_resp) = utils::map_command_response(res);
+                cmd_resp.push(chunk_cmd_resp);
+                for (id, r) in check_results(res) {
+                    results.insert(id.to_lowercase(), r);
+                }
+            }
+            Err(e) => {
+                let err = tonic::Status::from(e);
+                for id in chunk {
+                    results.insert(id.to_lowercase(), error_result(id, err.message()));
+                }
+            }
+        }
+    }
+
+    let results = objects
+        .into_iter()
+        .map(|o| match results.remove(&o.to_lowercase()) {
+            Some(r) => r,
+            None => error_result(o, MISSING_CHECK_RESULT),
+        })
+        .collect();
+
+    Ok((results, cmd_resp))
+}
+
 #[tonic::async_trait]
 impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
     async fn domain_check(
@@ -167,6 +464,7 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
                 res.fee_check.map(Into::into),
                 None,
                 res.keysys.map(Into::into),
+                res.allocation_token.as_deref(),
                 &mut sender,
             )
             .await?,
@@ -179,6 +477,218 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
         Ok(tonic::Response::new(reply))
     }
 
+    async fn domain_batch_check(
+        &self,
+        request: tonic::Request<epp_proto::domain::DomainBatchCheckRequest>,
+    ) -> Result<tonic::Response<epp_proto::domain::DomainBatchCheckReply>, tonic::Status> {
+        let request = request.into_inner();
+        let fee_check: Option<client::fee::FeeCheck> = request.fee_check.map(Into::into);
+        let names = utils::dedup_check_objects(request.names);
+
+        let mut results = std::collections::HashMap::new();
+        let (groups, unrouted) = group_by_registry(
+            &self.client_router,
+            &names,
+            request.registry_name.as_deref(),
+        )?;
+        for name in unrouted {
+            results.insert(
+                name.to_lowercase(),
+                domain::batch_check_error(name, "", "unsupported domain"),
+            );
+        }
+
+        let checks = groups.into_iter().map(|group| {
+            let CheckGroup {
+                registry_name,
+                sender,
+                max_check_size,
+                names,
+            } = group;
+            let fee_check = &fee_check;
+            async move {
+                let res = utils::chunked_check(names, max_check_size, |chunk| {
+                    let mut sender = sender.clone();
+                    let fee_check = fee_check.clone();
+                    async move { client::domain::batch_check(&chunk, fee_check, &mut sender).await }
+                })
+                .await;
+                (registry_name, res)
+            }
+        });
+
+        let mut cmd_resp = vec![];
+        for (registry_name, chunks) in futures::future::join_all(checks).await {
+            for (chunk, res) in chunks {
+                match res {
+                    Ok(res) => {
+                        let (res, chunk_cmd_resp) = utils::map_command_response(res);
+                        cmd_resp.push(chunk_cmd_resp);
+                        for r in res.results {
+                            results.insert(
+                                r.name.to_lowercase(),
+                                domain::batch_check_result(&registry_name, r),
+                            );
+                        }
+                        for name in chunk {
+                            results.entry(name.to_lowercase()).or_insert_with(|| {
+                                domain::batch_check_error(
+                                    name,
+                                    &registry_name,
+                                    MISSING_CHECK_RESULT,
+                                )
+                            });
+                        }
+                    }
+                    Err(e) => {
+                        let err = tonic::Status::from(e);
+                        for name in chunk {
+                            results.insert(
+                                name.to_lowercase(),
+                                domain::batch_check_error(name, &registry_name, err.message()),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        let reply = epp_proto::domain::DomainBatchCheckReply {
+            results: names
+                .into_iter()
+                .filter_map(|n| results.remove(&n.to_lowercase()))
+                .collect(),
+            cmd_resp,
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn domain_search(
+        &self,
+        request: tonic::Request<epp_proto::domain::DomainSearchRequest>,
+    ) -> Result<tonic::Response<epp_proto::domain::DomainSearchReply>, tonic::Status> {
+        let request = request.into_inner();
+        let label = request.label.trim().trim_end_matches('.').to_lowercase();
+        if label.is_empty() || label.contains('.') {
+            return Err(tonic::Status::invalid_argument(
+                "label must be a single DNS label",
+            ));
+        }
+        let names = utils::dedup_check_objects(
+            request
+                .tlds
+                .iter()
+                .map(|t| t.trim().trim_matches('.'))
+                .filter(|t| !t.is_empty())
+                .map(|t| format!("{}.{}", label, t))
+                .collect(),
+        );
+
+        let period: client::Period = match request.period {
+            Some(p) => p.into(),
+            None => client::Period {
+                unit: client::PeriodUnit::Years,
+                value: 1,
+            },
+        };
+        let fee_check = client::fee::FeeCheck {
+            currency: request.currency,
+            commands: [
+                (client::fee::Command::Create, Some(period.clone())),
+                (client::fee::Command::Renew, Some(period.clone())),
+                (client::fee::Command::Transfer, Some(period)),
+                (client::fee::Command::Restore, None),
+            ]
+            .iter()
+            .cloned()
+            .map(|(command, period)| client::fee::FeeCheckCommand {
+                command,
+                period,
+                custom_name: None,
+                phase: None,
+                sub_phase: None,
+            })
+            .collect(),
+        };
+
+        let mut results = std::collections::HashMap::new();
+        let (groups, unrouted) = group_by_registry(&self.client_router, &names, None)?;
+        for name in unrouted {
+            results.insert(
+                name.to_lowercase(),
+                domain::search_error(name, "", "no registry for TLD"),
+            );
+        }
+
+        let checks = groups.into_iter().map(|group| {
+            let CheckGroup {
+                registry_name,
+                sender,
+                max_check_size,
+                names,
+            } = group;
+            let fee_check = &fee_check;
+            async move {
+                let res = utils::chunked_check(names, max_check_size, |chunk| {
+                    let mut sender = sender.clone();
+                    let fee_check = fee_check.clone();
+                    async move {
+                        // Still report availability from registries without a fee extension
+                        match client::domain::batch_check(&chunk, Some(fee_check), &mut sender)
+                            .await
+                        {
+                            Err(client::Error::Unsupported) => {
+                                client::domain::batch_check(&chunk, None, &mut sender).await
+                            }
+                            r => r,
+                        }
+                    }
+                })
+                .await;
+                (registry_name, res)
+            }
+        });
+
+        for (registry_name, chunks) in futures::future::join_all(checks).await {
+            for (chunk, res) in chunks {
+                match res {
+                    Ok(res) => {
+                        for r in res.response.results {
+                            results.insert(
+                                r.name.to_lowercase(),
+                                domain::search_result(&registry_name, r),
+                            );
+                        }
+                        for name in chunk {
+                            results.entry(name.to_lowercase()).or_insert_with(|| {
+                                domain::search_error(name, &registry_name, MISSING_CHECK_RESULT)
+                            });
+                        }
+                    }
+                    Err(e) => {
+                        let err = tonic::Status::from(e);
+                        for name in chunk {
+                            results.insert(
+                                name.to_lowercase(),
+                                domain::search_error(name, &registry_name, err.message()),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        let reply = epp_proto::domain::DomainSearchReply {
+            results: names
+                .into_iter()
+                .filter_map(|n| results.remove(&n.to_lowercase()))
+                .collect(),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
     async fn domain_claims_check(
         &self,
         request: tonic::Request<epp_proto::domain::DomainClaimsCheckRequest>,
@@ -263,6 +773,7 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
                     None => None,
                 },
                 req.eurid_data.map(Into::into),
+                req.allocation_token,
                 &mut sender,
             )
             .await?,
@@ -328,6 +839,19 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
             }
         }
 
+        let agreements = enforce_fee_policy(
+            &self.client_router,
+            &registry_name,
+            &request.name,
+            client::fee::Command::Create,
+            request.period.clone().map(Into::into),
+            request.fee_ceiling,
+            request.allocation_token.as_deref(),
+            &mut sender,
+        )
+        .await?
+        .or_explicit(request.fee_agreement, request.donuts_fee_agreement)?;
+
         let (res, cmd_resp) = utils::map_command_response(
             client::domain::create(
                 client::domain::CreateInfo {
@@ -395,11 +919,8 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
                         Some(i) => Some(TryInto::try_into(i)?),
                         None => None,
                     },
-                    fee_agreement: request.fee_agreement.map(Into::into),
-                    donuts_fee_agreement: request
-                        .donuts_fee_agreement
-                        .map(TryInto::try_into)
-                        .map_or(Ok(None), |v| v.map(Some))?,
+                    fee_agreement: agreements.fee_agreement,
+                    donuts_fee_agreement: agreements.donuts_fee_agreement,
                     eurid_data: request.eurid_data.map(Into::into),
                     isnic_payment: request.isnic_payment.and_then(Into::into),
                     personal_registration: request.personal_registration.map(|p| {
@@ -412,6 +933,7 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
                         .map(TryInto::try_into)
                         .map_or(Ok(None), |v| v.map(Some))?,
                     nominet_ext: request.nominet_ext.map(Into::into),
+                    allocation_token: request.allocation_token.as_deref(),
                 },
                 &mut sender,
             )
@@ -425,6 +947,14 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
         Ok(tonic::Response::new(reply))
     }
 
+    async fn domain_provision(
+        &self,
+        request: tonic::Request<epp_proto::domain::DomainProvisionRequest>,
+    ) -> Result<tonic::Response<epp_proto::domain::DomainProvisionReply>, tonic::Status> {
+        let reply = provision::provision(self, request.into_inner()).await?;
+        Ok(tonic::Response::new(reply))
+    }
+
     async fn domain_delete(
         &self,
         request: tonic::Request<epp_proto::domain::DomainDeleteRequest>,
@@ -452,6 +982,11 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
 
         let reply = epp_proto::domain::DomainDeleteReply {
             pending: res.pending,
+            normalised_fees: fee::normalised_fees(
+                client::fee::Command::Delete,
+                &res.fee_data,
+                &None,
+            ),
             fee_data: res.fee_data.map(Into::into),
             registry_name,
             cmd_resp: Some(cmd_resp),
@@ -683,8 +1218,14 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
             client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
 
         let (res, cmd_resp) = utils::map_command_response(
-            client::domain::verisign_sync(&request.name, request.month, request.day, &mut sender)
-                .await?,
+            client::domain::verisign_sync(
+                &request.name,
+                request.month,
+                request.day,
+                request.fee_agreement.map(Into::into),
+                &mut sender,
+            )
+            .await?,
         );
 
         let mut reply: epp_proto::domain::DomainUpdateReply = res.into();
@@ -709,18 +1250,29 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
             ));
         }
 
+        let agreements = enforce_fee_policy(
+            &self.client_router,
+            &registry_name,
+            &request.name,
+            client::fee::Command::Renew,
+            request.period.clone().map(Into::into),
+            request.fee_ceiling,
+            request.allocation_token.as_deref(),
+            &mut sender,
+        )
+        .await?
+        .or_explicit(request.fee_agreement, request.donuts_fee_agreement)?;
+
         let (res, cmd_resp) = utils::map_command_response(
             client::domain::renew(
                 &request.name,
                 request.period.map(Into::into),
                 cur_expiry_date.unwrap(),
-                request.fee_agreement.map(Into::into),
-                request
-                    .donuts_fee_agreement
-                    .map(TryInto::try_into)
-                    .map_or(Ok(None), |v| v.map(Some))?,
+                agreements.fee_agreement,
+                agreements.donuts_fee_agreement,
                 request.isnic_payment.and_then(Into::into),
                 request.keysys.map(Into::into),
+                request.allocation_token.as_deref(),
                 &mut sender,
             )
             .await?,
@@ -759,18 +1311,28 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
         let request = request.into_inner();
         let (mut sender, registry_name) =
             client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
+        let agreements = enforce_fee_policy(
+            &self.client_router,
+            &registry_name,
+            &request.name,
+            client::fee::Command::Transfer,
+            request.period.clone().map(Into::into),
+            request.fee_ceiling,
+            request.allocation_token.as_deref(),
+            &mut sender,
+        )
+        .await?
+        .or_explicit(request.fee_agreement, request.donuts_fee_agreement)?;
         let (res, cmd_resp) = utils::map_command_response(
             client::domain::transfer_request(
                 &request.name,
                 request.period.map(Into::into),
                 &request.auth_info,
-                request.fee_agreement.map(Into::into),
-                request
-                    .donuts_fee_agreement
-                    .map(TryInto::try_into)
-                    .map_or(Ok(None), |v| v.map(Some))?,
+                agreements.fee_agreement,
+                agreements.donuts_fee_agreement,
                 request.eurid_data.map(Into::into),
                 request.keysys.map(Into::into),
+                request.allocation_token.as_deref(),
                 &mut sender,
             )
             .await?,
@@ -847,12 +1409,23 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
         let res = request.into_inner();
         let (mut sender, registry_name) =
             client_by_domain_or_id(&self.client_router, &res.name, res.registry_name)?;
+        let agreements = enforce_fee_policy(
+            &self.client_router,
+            &registry_name,
+            &res.name,
+            client::fee::Command::Restore,
+            None,
+            res.fee_ceiling,
+            None,
+            &mut sender,
+        )
+        .await?
+        .or_explicit(res.fee_agreement, res.donuts_fee_agreement)?;
         let (res, cmd_resp) = utils::map_command_response(
             client::rgp::request(
                 &res.name,
-                res.donuts_fee_agreement
-                    .map(TryInto::try_into)
-                    .map_or(Ok(None), |v| v.map(Some))?,
+                agreements.fee_agreement,
+                agreements.donuts_fee_agreement,
                 &mut sender,
             )
             .await?,
@@ -865,6 +1438,11 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
                 .into_iter()
                 .map(rgp::i32_from_restore_status)
                 .collect(),
+            normalised_fees: fee::normalised_fees(
+                client::fee::Command::Restore,
+                &res.fee_data,
+                &None,
+            ),
             fee_data: res.fee_data.map(Into::into),
             registry_name,
             cmd_resp: Some(cmd_resp),
@@ -922,6 +1500,11 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
 
         let reply = epp_proto::rgp::ReportReply {
             pending: res.pending,
+            normalised_fees: fee::normalised_fees(
+                client::fee::Command::Restore,
+                &res.fee_data,
+                &None,
+            ),
             fee_data: res.fee_data.map(Into::into),
             registry_name,
             cmd_resp: Some(cmd_resp),
@@ -930,82 +1513,742 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
         Ok(tonic::Response::new(reply))
     }
 
-    async fn host_check(
+    async fn email_forward_check(
         &self,
-        request: tonic::Request<epp_proto::host::HostCheckRequest>,
-    ) -> Result<tonic::Response<epp_proto::host::HostCheckReply>, tonic::Status> {
+        request: tonic::Request<epp_proto::email_forward::EmailForwardCheckRequest>,
+    ) -> Result<tonic::Response<epp_proto::email_forward::EmailForwardCheckReply>, tonic::Status>
+    {
         let request = request.into_inner();
-        let name: String = request.name;
-        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
-        let (res, cmd_resp) =
-            utils::map_command_response(client::host::check(&name, &mut sender).await?);
+        let (mut sender, registry_name) =
+            client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::email_forward::check(
+                &request.name,
+                request.fee_check.map(Into::into),
+                &mut sender,
+            )
+            .await?,
+        );
 
-        let reply = epp_proto::host::HostCheckReply {
-            available: res.avail,
-            reason: res.reason,
-            cmd_resp: Some(cmd_resp),
-        };
+        let mut reply: epp_proto::email_forward::EmailForwardCheckReply = res.into();
+        reply.registry_name = registry_name;
+        reply.cmd_resp = Some(cmd_resp);
 
         Ok(tonic::Response::new(reply))
     }
 
-    async fn host_info(
+    async fn email_forward_info(
         &self,
-        request: tonic::Request<epp_proto::host::HostInfoRequest>,
-    ) -> Result<tonic::Response<epp_proto::host::HostInfoReply>, tonic::Status> {
+        request: tonic::Request<epp_proto::email_forward::EmailForwardInfoRequest>,
+    ) -> Result<tonic::Response<epp_proto::email_forward::EmailForwardInfoReply>, tonic::Status>
+    {
         let request = request.into_inner();
-        let name: String = request.name;
-        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
-        let (res, cmd_resp) =
-            utils::map_command_response(client::host::info(&name, &mut sender).await?);
+        let (mut sender, registry_name) =
+            client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::email_forward::info(&request.name, request.auth_info.as_deref(), &mut sender)
+                .await?,
+        );
 
-        let mut reply: epp_proto::host::HostInfoReply = res.into();
+        let mut reply: epp_proto::email_forward::EmailForwardInfoReply = res.into();
+        reply.registry_name = registry_name;
         reply.cmd_resp = Some(cmd_resp);
 
         Ok(tonic::Response::new(reply))
     }
 
-    async fn host_create(
+    async fn email_forward_create(
         &self,
-        request: tonic::Request<epp_proto::host::HostCreateRequest>,
-    ) -> Result<tonic::Response<epp_proto::host::HostCreateReply>, tonic::Status> {
+        request: tonic::Request<epp_proto::email_forward::EmailForwardCreateRequest>,
+    ) -> Result<tonic::Response<epp_proto::email_forward::EmailForwardCreateReply>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let (mut sender, registry_name) =
+            client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::email_forward::create(
+                client::email_forward::CreateInfo {
+                    email: &request.name,
+                    period: request.period.map(Into::into),
+                    registrant: &request.registrant,
+                    contacts: request
+                        .contacts
+                        .into_iter()
+                        .map(|c| client::domain::InfoContact {
+                            contact_id: c.id,
+                            contact_type: c.r#type,
+                        })
+                        .collect(),
+                    auth_info: &request.auth_info,
+                    forward_to: &request.forward_to,
+                    fee_agreement: request.fee_agreement.map(Into::into),
+                    personal_registration: request.personal_registration.map(|p| {
+                        client::personal_registration::PersonalRegistrationInfo {
+                            consent_id: p.consent_id,
+                        }
+                    }),
+                },
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let mut reply: epp_proto::email_forward::EmailForwardCreateReply = res.into();
+        reply.registry_name = registry_name;
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn email_forward_delete(
+        &self,
+        request: tonic::Request<epp_proto::email_forward::EmailForwardDeleteRequest>,
+    ) -> Result<tonic::Response<epp_proto::email_forward::EmailForwardDeleteReply>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let (mut sender, registry_name) =
+            client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::email_forward::delete(&request.name, &mut sender).await?,
+        );
+
+        let mut reply: epp_proto::email_forward::EmailForwardDeleteReply = res.into();
+        reply.registry_name = registry_name;
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn email_forward_update(
+        &self,
+        request: tonic::Request<epp_proto::email_forward::EmailForwardUpdateRequest>,
+    ) -> Result<tonic::Response<epp_proto::email_forward::EmailForwardUpdateReply>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let (mut sender, registry_name) =
+            client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
+
+        let map_param = |p: epp_proto::email_forward::email_forward_update_request::Param,
+                         l: &mut Vec<client::email_forward::UpdateObject>| {
+            match p.param {
+                Some(
+                    epp_proto::email_forward::email_forward_update_request::param::Param::Contact(
+                        c,
+                    ),
+                ) => {
+                    l.push(client::email_forward::UpdateObject::Contact(
+                        client::domain::InfoContact {
+                            contact_id: c.id,
+                            contact_type: c.r#type,
+                        },
+                    ));
+                }
+                Some(
+                    epp_proto::email_forward::email_forward_update_request::param::Param::State(s),
+                ) => {
+                    if let Some(s) = domain::domain_status_from_i32(s) {
+                        l.push(client::email_forward::UpdateObject::Status(s));
+                    }
+                }
+                None => {}
+            }
+        };
+
+        let mut add = vec![];
+        let mut remove = vec![];
+        for p in request.add {
+            map_param(p, &mut add);
+        }
+        for p in request.remove {
+            map_param(p, &mut remove);
+        }
+
+        let (res, cmd_resp) = utils::map_command_response(
+            client::email_forward::update(
+                client::email_forward::UpdateInfo {
+                    email: &request.name,
+                    add,
+                    remove,
+                    new_registrant: request.new_registrant.as_deref(),
+                    new_forward_to: request.new_forward_to.as_deref(),
+                    new_auth_info: request.new_auth_info.as_deref(),
+                    fee_agreement: request.fee_agreement.map(Into::into),
+                },
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let mut reply: epp_proto::email_forward::EmailForwardUpdateReply = res.into();
+        reply.registry_name = registry_name;
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn email_forward_renew(
+        &self,
+        request: tonic::Request<epp_proto::email_forward::EmailForwardRenewRequest>,
+    ) -> Result<tonic::Response<epp_proto::email_forward::EmailForwardRenewReply>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let (mut sender, registry_name) =
+            client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
+
+        let cur_expiry_date = match utils::proto_to_chrono(request.current_expiry_date) {
+            Some(d) => d,
+            None => {
+                return Err(tonic::Status::invalid_argument(
+                    "current_expiry_date must be specified",
+                ));
+            }
+        };
+
+        let (res, cmd_resp) = utils::map_command_response(
+            client::email_forward::renew(
+                &request.name,
+                request.period.map(Into::into),
+                cur_expiry_date,
+                request.fee_agreement.map(Into::into),
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let mut reply: epp_proto::email_forward::EmailForwardRenewReply = res.into();
+        reply.registry_name = registry_name;
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn email_forward_transfer_query(
+        &self,
+        request: tonic::Request<epp_proto::email_forward::EmailForwardTransferQueryRequest>,
+    ) -> Result<tonic::Response<epp_proto::email_forward::EmailForwardTransferReply>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let (mut sender, registry_name) =
+            client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::email_forward::transfer_query(
+                &request.name,
+                request.auth_info.as_deref(),
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let mut reply: epp_proto::email_forward::EmailForwardTransferReply = res.into();
+        reply.registry_name = registry_name;
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn email_forward_transfer_request(
+        &self,
+        request: tonic::Request<epp_proto::email_forward::EmailForwardTransferRequestRequest>,
+    ) -> Result<tonic::Response<epp_proto::email_forward::EmailForwardTransferReply>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let (mut sender, registry_name) =
+            client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::email_forward::transfer_request(
+                &request.name,
+                request.period.map(Into::into),
+                &request.auth_info,
+                request.fee_agreement.map(Into::into),
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let mut reply: epp_proto::email_forward::EmailForwardTransferReply = res.into();
+        reply.registry_name = registry_name;
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn email_forward_transfer_cancel(
+        &self,
+        request: tonic::Request<epp_proto::email_forward::EmailForwardTransferAcceptRejectRequest>,
+    ) -> Result<tonic::Response<epp_proto::email_forward::EmailForwardTransferReply>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let (mut sender, registry_name) =
+            client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::email_forward::transfer_cancel(
+                &request.name,
+                request.auth_info.as_deref(),
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let mut reply: epp_proto::email_forward::EmailForwardTransferReply = res.into();
+        reply.registry_name = registry_name;
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn email_forward_transfer_accept(
+        &self,
+        request: tonic::Request<epp_proto::email_forward::EmailForwardTransferAcceptRejectRequest>,
+    ) -> Result<tonic::Response<epp_proto::email_forward::EmailForwardTransferReply>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let (mut sender, registry_name) =
+            client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::email_forward::transfer_accept(
+                &request.name,
+                request.auth_info.as_deref(),
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let mut reply: epp_proto::email_forward::EmailForwardTransferReply = res.into();
+        reply.registry_name = registry_name;
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn email_forward_transfer_reject(
+        &self,
+        request: tonic::Request<epp_proto::email_forward::EmailForwardTransferAcceptRejectRequest>,
+    ) -> Result<tonic::Response<epp_proto::email_forward::EmailForwardTransferReply>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let (mut sender, registry_name) =
+            client_by_domain_or_id(&self.client_router, &request.name, request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::email_forward::transfer_reject(
+                &request.name,
+                request.auth_info.as_deref(),
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let mut reply: epp_proto::email_forward::EmailForwardTransferReply = res.into();
+        reply.registry_name = registry_name;
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn host_check(
+        &self,
+        request: tonic::Request<epp_proto::host::HostCheckRequest>,
+    ) -> Result<tonic::Response<epp_proto::host::HostCheckReply>, tonic::Status> {
+        let request = request.into_inner();
+        let name: String = request.name;
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) =
+            utils::map_command_response(client::host::check(&name, &mut sender).await?);
+
+        let reply = epp_proto::host::HostCheckReply {
+            available: res.avail,
+            reason: res.reason,
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn host_batch_check(
+        &self,
+        request: tonic::Request<epp_proto::host::HostBatchCheckRequest>,
+    ) -> Result<tonic::Response<epp_proto::host::HostBatchCheckReply>, tonic::Status> {
+        use epp_proto::host::host_batch_check_reply::CheckResult;
+
+        let request = request.into_inner();
+        let names = utils::dedup_check_objects(request.names);
+        let (results, cmd_resp) = single_registry_batch_check(
+            &self.client_router,
+            &request.registry_name,
+            names,
+            |chunk, mut sender| async move { client::host::batch_check(&chunk, &mut sender).await },
+            |res: client::host::BatchCheckResponse| {
+                res.results
+                    .into_iter()
+                    .map(|r| {
+                        (
+                            r.name.clone(),
+                            CheckResult {
+                                name: r.name,
+                                available: r.avail,
+                                reason: r.reason,
+                                error: None,
+                            },
+                        )
+                    })
+                    .collect()
+            },
+            |name, error| CheckResult {
+                name,
+                available: false,
+                reason: None,
+                error: Some(error.to_string()),
+            },
+        )
+        .await?;
+
+        let reply = epp_proto::host::HostBatchCheckReply { results, cmd_resp };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn host_info(
+        &self,
+        request: tonic::Request<epp_proto::host::HostInfoRequest>,
+    ) -> Result<tonic::Response<epp_proto::host::HostInfoReply>, tonic::Status> {
+        let request = request.into_inner();
+        let name: String = request.name;
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) =
+            utils::map_command_response(client::host::info(&name, &mut sender).await?);
+
+        let mut reply: epp_proto::host::HostInfoReply = res.into();
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn host_create(
+        &self,
+        request: tonic::Request<epp_proto::host::HostCreateRequest>,
+    ) -> Result<tonic::Response<epp_proto::host::HostCreateReply>, tonic::Status> {
+        let request = request.into_inner();
+        let name: String = request.name;
+        let addresses: Vec<client::host::Address> = request
+            .addresses
+            .into_iter()
+            .map(|a| {
+                Ok(client::host::Address {
+                    address: a.address,
+                    ip_version: match epp_proto::common::ip_address::IpVersion::try_from(a.r#type) {
+                        Ok(epp_proto::common::ip_address::IpVersion::IPv4) => {
+                            client::host::AddressVersion::IPv4
+                        }
+                        Ok(epp_proto::common::ip_address::IpVersion::IPv6) => {
+                            client::host::AddressVersion::IPv6
+                        }
+                        Err(_) | Ok(epp_proto::common::ip_address::IpVersion::Unknown) => {
+                            return Err(tonic::Status::invalid_argument(
+                                "unknown IP address version",
+                            ));
+                        }
+                    },
+                })
+            })
+            .collect::<Result<Vec<client::host::Address>, tonic::Status>>()?;
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::host::create(
+                &name,
+                addresses,
+                request.isnic_info.map(Into::into),
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let reply = epp_proto::host::HostCreateReply {
+            name: res.name,
+            pending: res.pending,
+            creation_date: utils::chrono_to_proto(res.creation_date),
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn host_delete(
+        &self,
+        request: tonic::Request<epp_proto::host::HostDeleteRequest>,
+    ) -> Result<tonic::Response<epp_proto::host::HostDeleteReply>, tonic::Status> {
+        let request = request.into_inner();
+        let name: String = request.name;
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) =
+            utils::map_command_response(client::host::delete(&name, &mut sender).await?);
+
+        let reply = epp_proto::host::HostDeleteReply {
+            pending: res.pending,
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn host_update(
+        &self,
+        request: tonic::Request<epp_proto::host::HostUpdateRequest>,
+    ) -> Result<tonic::Response<epp_proto::host::HostUpdateReply>, tonic::Status> {
+        let request = request.into_inner();
+        let name: String = request.name;
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+
+        let mut add = vec![];
+        let mut remove = vec![];
+
+        let map_addr = |addr: epp_proto::common::IpAddress| {
+            Ok(client::host::Address {
+                address: addr.address,
+                ip_version: match epp_proto::common::ip_address::IpVersion::try_from(addr.r#type) {
+                    Ok(epp_proto::common::ip_address::IpVersion::IPv4) => {
+                        client::host::AddressVersion::IPv4
+                    }
+                    Ok(epp_proto::common::ip_address::IpVersion::IPv6) => {
+                        client::host::AddressVersion::IPv6
+                    }
+                    Err(_) | Ok(epp_proto::common::ip_address::IpVersion::Unknown) => {
+                        return Err(tonic::Status::invalid_argument(
+                            "unknown IP address version",
+                        ));
+                    }
+                },
+            })
+        };
+
+        for a in request.add {
+            match a.param {
+                Some(epp_proto::host::host_update_request::param::Param::Address(addr)) => {
+                    add.push(client::host::UpdateObject::Address(map_addr(addr)?))
+                }
+                Some(epp_proto::host::host_update_request::param::Param::State(s)) => {
+                    if let Some(s) = host::host_status_from_i32(s) {
+                        add.push(client::host::UpdateObject::Status(s))
+                    }
+                }
+                None => {}
+            }
+        }
+        for r in request.remove {
+            match r.param {
+                Some(epp_proto::host::host_update_request::param::Param::Address(addr)) => {
+                    remove.push(client::host::UpdateObject::Address(map_addr(addr)?))
+                }
+                Some(epp_proto::host::host_update_request::param::Param::State(s)) => {
+                    if let Some(s) = host::host_status_from_i32(s) {
+                        remove.push(client::host::UpdateObject::Status(s))
+                    }
+                }
+                None => {}
+            }
+        }
+
+        let (res, cmd_resp) = utils::map_command_response(
+            client::host::update(
+                &name,
+                add,
+                remove,
+                request.new_name,
+                request.isnic_info.map(Into::into),
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let reply = epp_proto::host::HostUpdateReply {
+            pending: res.pending,
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn ns_set_check(
+        &self,
+        request: tonic::Request<epp_proto::fred::NsSetCheckRequest>,
+    ) -> Result<tonic::Response<epp_proto::fred::NsSetCheckReply>, tonic::Status> {
+        let request = request.into_inner();
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) =
+            utils::map_command_response(client::nsset::check(&request.id, &mut sender).await?);
+
+        let reply = epp_proto::fred::NsSetCheckReply {
+            available: res.avail,
+            reason: res.reason,
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn ns_set_info(
+        &self,
+        request: tonic::Request<epp_proto::fred::NsSetInfoRequest>,
+    ) -> Result<tonic::Response<epp_proto::fred::NsSetInfoReply>, tonic::Status> {
+        let request = request.into_inner();
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) =
+            utils::map_command_response(client::nsset::info(&request.id, &mut sender).await?);
+
+        let mut reply: epp_proto::fred::NsSetInfoReply = res.into();
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn ns_set_create(
+        &self,
+        request: tonic::Request<epp_proto::fred::NsSetCreateRequest>,
+    ) -> Result<tonic::Response<epp_proto::fred::NsSetCreateReply>, tonic::Status> {
+        let request = request.into_inner();
+        let report_level = fred::report_level_from_proto(request.report_level)?;
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::nsset::create(
+                &request.id,
+                request.nameservers.into_iter().map(Into::into).collect(),
+                request.tech_contacts,
+                request.auth_info.as_deref(),
+                report_level,
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let reply = epp_proto::fred::NsSetCreateReply {
+            id: res.id,
+            pending: res.pending,
+            creation_date: utils::chrono_to_proto(res.creation_date),
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn ns_set_delete(
+        &self,
+        request: tonic::Request<epp_proto::fred::NsSetDeleteRequest>,
+    ) -> Result<tonic::Response<epp_proto::fred::NsSetDeleteReply>, tonic::Status> {
+        let request = request.into_inner();
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) =
+            utils::map_command_response(client::nsset::delete(&request.id, &mut sender).await?);
+
+        let reply = epp_proto::fred::NsSetDeleteReply {
+            pending: res.pending,
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn ns_set_update(
+        &self,
+        request: tonic::Request<epp_proto::fred::NsSetUpdateRequest>,
+    ) -> Result<tonic::Response<epp_proto::fred::NsSetUpdateReply>, tonic::Status> {
+        let request = request.into_inner();
+        let new_report_level = fred::report_level_from_proto(request.new_report_level)?;
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::nsset::update(
+                &request.id,
+                request.add_nameservers.into_iter().map(Into::into).collect(),
+                request.remove_nameservers,
+                request.add_tech_contacts,
+                request.remove_tech_contacts,
+                request.new_auth_info.as_deref(),
+                new_report_level,
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let reply = epp_proto::fred::NsSetUpdateReply {
+            pending: res.pending,
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn ns_set_transfer(
+        &self,
+        request: tonic::Request<epp_proto::fred::NsSetTransferRequest>,
+    ) -> Result<tonic::Response<epp_proto::fred::NsSetTransferReply>, tonic::Status> {
+        let request = request.into_inner();
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::nsset::transfer(&request.id, &request.auth_info, &mut sender).await?,
+        );
+
+        let reply = epp_proto::fred::NsSetTransferReply {
+            pending: res.pending,
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn key_set_check(
+        &self,
+        request: tonic::Request<epp_proto::fred::KeySetCheckRequest>,
+    ) -> Result<tonic::Response<epp_proto::fred::KeySetCheckReply>, tonic::Status> {
+        let request = request.into_inner();
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) =
+            utils::map_command_response(client::keyset::check(&request.id, &mut sender).await?);
+
+        let reply = epp_proto::fred::KeySetCheckReply {
+            available: res.avail,
+            reason: res.reason,
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn key_set_info(
+        &self,
+        request: tonic::Request<epp_proto::fred::KeySetInfoRequest>,
+    ) -> Result<tonic::Response<epp_proto::fred::KeySetInfoReply>, tonic::Status> {
+        let request = request.into_inner();
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) =
+            utils::map_command_response(client::keyset::info(&request.id, &mut sender).await?);
+
+        let mut reply: epp_proto::fred::KeySetInfoReply = res.into();
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn key_set_create(
+        &self,
+        request: tonic::Request<epp_proto::fred::KeySetCreateRequest>,
+    ) -> Result<tonic::Response<epp_proto::fred::KeySetCreateReply>, tonic::Status> {
         let request = request.into_inner();
-        let name: String = request.name;
-        let addresses: Vec<client::host::Address> = request
-            .addresses
+        let keys = request
+            .keys
             .into_iter()
-            .map(|a| {
-                Ok(client::host::Address {
-                    address: a.address,
-                    ip_version: match epp_proto::common::ip_address::IpVersion::try_from(a.r#type) {
-                        Ok(epp_proto::common::ip_address::IpVersion::IPv4) => {
-                            client::host::AddressVersion::IPv4
-                        }
-                        Ok(epp_proto::common::ip_address::IpVersion::IPv6) => {
-                            client::host::AddressVersion::IPv6
-                        }
-                        Err(_) | Ok(epp_proto::common::ip_address::IpVersion::Unknown) => {
-                            return Err(tonic::Status::invalid_argument(
-                                "unknown IP address version",
-                            ));
-                        }
-                    },
-                })
-            })
-            .collect::<Result<Vec<client::host::Address>, tonic::Status>>()?;
+            .map(fred::dns_key_from_proto)
+            .collect::<Result<Vec<_>, _>>()?;
         let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
         let (res, cmd_resp) = utils::map_command_response(
-            client::host::create(
-                &name,
-                addresses,
-                request.isnic_info.map(Into::into),
+            client::keyset::create(
+                &request.id,
+                keys,
+                request.tech_contacts,
+                request.auth_info.as_deref(),
                 &mut sender,
             )
             .await?,
         );
 
-        let reply = epp_proto::host::HostCreateReply {
-            name: res.name,
+        let reply = epp_proto::fred::KeySetCreateReply {
+            id: res.id,
             pending: res.pending,
             creation_date: utils::chrono_to_proto(res.creation_date),
             cmd_resp: Some(cmd_resp),
@@ -1014,17 +2257,16 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
         Ok(tonic::Response::new(reply))
     }
 
-    async fn host_delete(
+    async fn key_set_delete(
         &self,
-        request: tonic::Request<epp_proto::host::HostDeleteRequest>,
-    ) -> Result<tonic::Response<epp_proto::host::HostDeleteReply>, tonic::Status> {
+        request: tonic::Request<epp_proto::fred::KeySetDeleteRequest>,
+    ) -> Result<tonic::Response<epp_proto::fred::KeySetDeleteReply>, tonic::Status> {
         let request = request.into_inner();
-        let name: String = request.name;
         let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
         let (res, cmd_resp) =
-            utils::map_command_response(client::host::delete(&name, &mut sender).await?);
+            utils::map_command_response(client::keyset::delete(&request.id, &mut sender).await?);
 
-        let reply = epp_proto::host::HostDeleteReply {
+        let reply = epp_proto::fred::KeySetDeleteReply {
             pending: res.pending,
             cmd_resp: Some(cmd_resp),
         };
@@ -1032,76 +2274,54 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
         Ok(tonic::Response::new(reply))
     }
 
-    async fn host_update(
+    async fn key_set_update(
         &self,
-        request: tonic::Request<epp_proto::host::HostUpdateRequest>,
-    ) -> Result<tonic::Response<epp_proto::host::HostUpdateReply>, tonic::Status> {
+        request: tonic::Request<epp_proto::fred::KeySetUpdateRequest>,
+    ) -> Result<tonic::Response<epp_proto::fred::KeySetUpdateReply>, tonic::Status> {
         let request = request.into_inner();
-        let name: String = request.name;
+        let add_keys = request
+            .add_keys
+            .into_iter()
+            .map(fred::dns_key_from_proto)
+            .collect::<Result<Vec<_>, _>>()?;
+        let remove_keys = request
+            .remove_keys
+            .into_iter()
+            .map(fred::dns_key_from_proto)
+            .collect::<Result<Vec<_>, _>>()?;
         let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::keyset::update(
+                &request.id,
+                add_keys,
+                remove_keys,
+                request.add_tech_contacts,
+                request.remove_tech_contacts,
+                request.new_auth_info.as_deref(),
+                &mut sender,
+            )
+            .await?,
+        );
 
-        let mut add = vec![];
-        let mut remove = vec![];
-
-        let map_addr = |addr: epp_proto::common::IpAddress| {
-            Ok(client::host::Address {
-                address: addr.address,
-                ip_version: match epp_proto::common::ip_address::IpVersion::try_from(addr.r#type) {
-                    Ok(epp_proto::common::ip_address::IpVersion::IPv4) => {
-                        client::host::AddressVersion::IPv4
-                    }
-                    Ok(epp_proto::common::ip_address::IpVersion::IPv6) => {
-                        client::host::AddressVersion::IPv6
-                    }
-                    Err(_) | Ok(epp_proto::common::ip_address::IpVersion::Unknown) => {
-                        return Err(tonic::Status::invalid_argument(
-                            "unknown IP address version",
-                        ));
-                    }
-                },
-            })
+        let reply = epp_proto::fred::KeySetUpdateReply {
+            pending: res.pending,
+            cmd_resp: Some(cmd_resp),
         };
 
-        for a in request.add {
-            match a.param {
-                Some(epp_proto::host::host_update_request::param::Param::Address(addr)) => {
-                    add.push(client::host::UpdateObject::Address(map_addr(addr)?))
-                }
-                Some(epp_proto::host::host_update_request::param::Param::State(s)) => {
-                    if let Some(s) = host::host_status_from_i32(s) {
-                        add.push(client::host::UpdateObject::Status(s))
-                    }
-                }
-                None => {}
-            }
-        }
-        for r in request.remove {
-            match r.param {
-                Some(epp_proto::host::host_update_request::param::Param::Address(addr)) => {
-                    remove.push(client::host::UpdateObject::Address(map_addr(addr)?))
-                }
-                Some(epp_proto::host::host_update_request::param::Param::State(s)) => {
-                    if let Some(s) = host::host_status_from_i32(s) {
-                        remove.push(client::host::UpdateObject::Status(s))
-                    }
-                }
-                None => {}
-            }
-        }
+        Ok(tonic::Response::new(reply))
+    }
 
+    async fn key_set_transfer(
+        &self,
+        request: tonic::Request<epp_proto::fred::KeySetTransferRequest>,
+    ) -> Result<tonic::Response<epp_proto::fred::KeySetTransferReply>, tonic::Status> {
+        let request = request.into_inner();
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
         let (res, cmd_resp) = utils::map_command_response(
-            client::host::update(
-                &name,
-                add,
-                remove,
-                request.new_name,
-                request.isnic_info.map(Into::into),
-                &mut sender,
-            )
-            .await?,
+            client::keyset::transfer(&request.id, &request.auth_info, &mut sender).await?,
         );
 
-        let reply = epp_proto::host::HostUpdateReply {
+        let reply = epp_proto::fred::KeySetTransferReply {
             pending: res.pending,
             cmd_resp: Some(cmd_resp),
         };
@@ -1128,6 +2348,49 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
         Ok(tonic::Response::new(reply))
     }
 
+    async fn contact_batch_check(
+        &self,
+        request: tonic::Request<epp_proto::contact::ContactBatchCheckRequest>,
+    ) -> Result<tonic::Response<epp_proto::contact::ContactBatchCheckReply>, tonic::Status> {
+        use epp_proto::contact::contact_batch_check_reply::CheckResult;
+
+        let request = request.into_inner();
+        let ids = utils::dedup_check_objects(request.ids);
+        let (results, cmd_resp) = single_registry_batch_check(
+            &self.client_router,
+            &request.registry_name,
+            ids,
+            |ids, mut sender| async move { client::contact::batch_check(&ids, &mut sender).await },
+            |res: client::contact::BatchCheckResponse| {
+                res.results
+                    .into_iter()
+                    .map(|r| {
+                        (
+                            r.id.clone(),
+                            CheckResult {
+                                id: r.id,
+                                available: r.avail,
+                                reason: r.reason,
+                                error: None,
+                            },
+                        )
+                    })
+                    .collect()
+            },
+            |id, error| CheckResult {
+                id,
+                available: false,
+                reason: None,
+                error: Some(error.to_string()),
+            },
+        )
+        .await?;
+
+        let reply = epp_proto::contact::ContactBatchCheckReply { results, cmd_resp };
+
+        Ok(tonic::Response::new(reply))
+    }
+
     async fn contact_info(
         &self,
         request: tonic::Request<epp_proto::contact::ContactInfoRequest>,
@@ -1657,6 +2920,16 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
                                             change_data: _,
                                             data: i
                                         } => Some(epp_proto::poll_reply::Data::NominetRegistrantTransfer(i.into())),
+                                        client::poll::PollData::NominetAbuseFeedData(i) =>
+                                            Some(epp_proto::poll_reply::Data::NominetAbuseFeed(i.into())),
+                                        client::poll::PollData::FredTransferData(i) =>
+                                            Some(epp_proto::poll_reply::Data::FredTransfer(i.into())),
+                                        client::poll::PollData::FredIdleDeleteData(i) =>
+                                            Some(epp_proto::poll_reply::Data::FredIdleDelete(i.into())),
+                                        client::poll::PollData::FredNSSetTestData(i) =>
+                                            Some(epp_proto::poll_reply::Data::FredNssetTest(i.into())),
+                                        client::poll::PollData::FredLowCreditData(i) =>
+                                            Some(epp_proto::poll_reply::Data::FredLowCredit(i.into())),
                                         client::poll::PollData::VerisignLowBalanceData(i) =>
                                             Some(epp_proto::poll_reply::Data::VerisignLowBalance(i.into())),
                                         client::poll::PollData::TraficomTrnData(i) =>
@@ -1665,6 +2938,8 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
                                             Some(epp_proto::poll_reply::Data::EuridPoll(i.into())),
                                         client::poll::PollData::MaintenanceData(i) =>
                                             Some(epp_proto::poll_reply::Data::MaintenanceInfo(i.into())),
+                                        client::poll::PollData::Raw(i) =>
+                                            Some(epp_proto::poll_reply::Data::RawXml(i)),
                                         client::poll::PollData::None => None
                                     },
                                 }))
@@ -1854,6 +3129,88 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
         Ok(tonic::Response::new(reply))
     }
 
+    async fn nominet_unrenew(
+        &self,
+        request: tonic::Request<epp_proto::nominet::UnrenewRequest>,
+    ) -> Result<tonic::Response<epp_proto::nominet::UnrenewReply>, tonic::Status> {
+        let request = request.into_inner();
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+
+        let (resp, cmd_resp) = utils::map_command_response(
+            client::nominet::unrenew(request.domains, &mut sender).await?,
+        );
+
+        let reply = epp_proto::nominet::UnrenewReply {
+            domains: resp
+                .domains
+                .into_iter()
+                .map(|d| epp_proto::nominet::unrenew_reply::Domain {
+                    name: d.name,
+                    expiry_date: utils::chrono_to_proto(d.expiry_date),
+                })
+                .collect(),
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn nominet_list(
+        &self,
+        request: tonic::Request<epp_proto::nominet::ListRequest>,
+    ) -> Result<tonic::Response<epp_proto::nominet::ListReply>, tonic::Status> {
+        let request = request.into_inner();
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+
+        let (resp, cmd_resp) = utils::map_command_response(
+            client::nominet::list_domains(
+                match nominet::list_type_from_i32(request.list_type) {
+                    Some(t) => t,
+                    None => {
+                        return Err(tonic::Status::invalid_argument("unknown list type"));
+                    }
+                },
+                request.year,
+                request.month,
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let reply = epp_proto::nominet::ListReply {
+            domains: resp.domains,
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn nominet_fork(
+        &self,
+        request: tonic::Request<epp_proto::nominet::ForkRequest>,
+    ) -> Result<tonic::Response<epp_proto::nominet::ForkReply>, tonic::Status> {
+        let request = request.into_inner();
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+
+        let (resp, cmd_resp) = utils::map_command_response(
+            client::nominet::fork(
+                &request.contact_id,
+                request.new_contact_id.as_deref(),
+                &request.domains.iter().map(|d| d.as_str()).collect::<Vec<_>>(),
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let reply = epp_proto::nominet::ForkReply {
+            new_contact_id: resp.new_contact_id,
+            creation_date: utils::chrono_to_proto(resp.creation_date),
+            cmd_resp: Some(cmd_resp),
+        };
+
+        Ok(tonic::Response::new(reply))
+    }
+
     async fn nominet_accept(
         &self,
         request: tonic::Request<epp_proto::nominet::HandshakeAcceptRequest>,
@@ -2186,6 +3543,52 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
         Ok(tonic::Response::new(reply))
     }
 
+    async fn tmch_trex_activate(
+        &self,
+        request: tonic::Request<epp_proto::tmch::MarkTrexActivateRequest>,
+    ) -> Result<tonic::Response<epp_proto::tmch::MarkTrexActivateResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::tmch::trex_activate(
+                &request.id,
+                request.labels.into_iter().map(Into::into).collect(),
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let mut reply: epp_proto::tmch::MarkTrexActivateResponse = res.into();
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
+    async fn tmch_trex_renew(
+        &self,
+        request: tonic::Request<epp_proto::tmch::MarkTrexRenewRequest>,
+    ) -> Result<tonic::Response<epp_proto::tmch::MarkTrexRenewResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let mut sender = client_by_id(&self.client_router, &request.registry_name)?;
+        let (res, cmd_resp) = utils::map_command_response(
+            client::tmch::trex_renew(
+                &request.id,
+                request
+                    .labels
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<Vec<_>, _>>()?,
+                &mut sender,
+            )
+            .await?,
+        );
+
+        let mut reply: epp_proto::tmch::MarkTrexRenewResponse = res.into();
+        reply.cmd_resp = Some(cmd_resp);
+
+        Ok(tonic::Response::new(reply))
+    }
+
     async fn dac_domain(
         &self,
         request: tonic::Request<epp_proto::dac::DomainRequest>,
@@ -2256,3 +3659,376 @@ impl epp_proto::epp_proxy_server::EppProxy for EPPProxy {
         Ok(tonic::Response::new(reply))
     }
 }
+
+#[cfg(test)]
+mod grpc_tests {
+    use super::*;
+    use futures::StreamExt;
+    use std::sync::{Arc, Mutex};
+
+    type Chunks = Arc<Mutex<Vec<Vec<String>>>>;
+
+    /// Registry answering batch checks for every name except those starting with `missing`,
+    /// or failing them all
+    fn registry(fail: bool, chunks: Chunks) -> client::RequestSender {
+        let (sender, mut receiver) = client::queue::channel(16, std::time::Duration::from_secs(5));
+        // Answered out of order and in upper case, as registries may
+        let answered = move |names: &[String]| {
+            chunks.lock().unwrap().push(names.to_vec());
+            names
+                .iter()
+                .rev()
+                .filter(|n| !n.starts_with("missing"))
+                .map(|n| n.to_uppercase())
+                .collect::<Vec<_>>()
+        };
+
+        tokio::spawn(async move {
+            while let Some((_, req)) = receiver.next().await {
+                match req {
+                    client::RequestMessage::DomainBatchCheck(r) => {
+                        let results = answered(&r.names)
+                            .into_iter()
+                            .map(|name| client::domain::BatchCheckResult {
+                                name,
+                                avail: true,
+                                reason: None,
+                                fee_check: None,
+                                donuts_fee_check: None,
+                            })
+                            .collect();
+                        let _ = r
+                            .return_path
+                            .send(reply(fail, client::domain::BatchCheckResponse { results }));
+                    }
+                    client::RequestMessage::HostBatchCheck(r) => {
+                        let results = answered(&r.names)
+                            .into_iter()
+                            .map(|name| client::host::BatchCheckResult {
+                                name,
+                                avail: true,
+                                reason: None,
+                            })
+                            .collect();
+                        let _ = r
+                            .return_path
+                            .send(reply(fail, client::host::BatchCheckResponse { results }));
+                    }
+                    req => req.reject(client::Error::Unsupported),
+                }
+            }
+        });
+        sender
+    }
+
+    fn reply<T>(fail: bool, response: T) -> client::Response<client::CommandResponse<T>> {
+        if fail {
+            return Err(client::Error::EPP(client::EPPError {
+                code: 2400,
+                message: "command failed".to_string(),
+                reasons: vec![],
+                transaction_id: client::router::CommandTransactionID {
+                    client: "client-1".to_string(),
+                    server: "server-1".to_string(),
+                },
+            }));
+        }
+        Ok(client::CommandResponse {
+            response,
+            extra_values: vec![],
+            unhandled_namespaces: vec![],
+            transaction_id: None,
+        })
+    }
+
+    /// Proxy to registry `one` for `.com`, checking two objects at a time, and registry `two`
+    /// for `.net`, failing every command
+    fn proxy(chunks: &Chunks) -> EPPProxy {
+        let one = registry(false, chunks.clone());
+        let two = registry(true, chunks.clone());
+        let mut router = crate::Router::new();
+        router
+            .zone_to_client
+            .insert("com".to_string(), (one.clone(), "one".to_string()));
+        router
+            .zone_to_client
+            .insert("net".to_string(), (two.clone(), "two".to_string()));
+        router.id_to_client.insert("one".to_string(), one);
+        router.id_to_client.insert("two".to_string(), two);
+        router.id_to_max_check_size.insert("one".to_string(), 2);
+
+        EPPProxy {
+            client_router: Arc::new(std::sync::RwLock::new(router)),
+        }
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn group_by_registry() {
+        let proxy = proxy(&Default::default());
+        let router = &proxy.client_router;
+        let group_names = |groups: Vec<CheckGroup>| {
+            groups
+                .into_iter()
+                .map(|g| (g.registry_name, g.max_check_size, g.names))
+                .collect::<Vec<_>>()
+        };
+
+        let (groups, unrouted) = super::group_by_registry(
+            router,
+            &names(&["a.com", "b.NET", "www.c.com", "d.org"]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            group_names(groups),
+            vec![
+                ("one".to_string(), 2, names(&["a.com", "www.c.com"])),
+                ("two".to_string(), 5, names(&["b.NET"])),
+            ]
+        );
+        assert_eq!(unrouted, names(&["d.org"]));
+
+        let (groups, unrouted) =
+            super::group_by_registry(router, &names(&["a.com", "d.org"]), Some("two")).unwrap();
+        assert_eq!(
+            group_names(groups),
+            vec![("two".to_string(), 5, names(&["a.com", "d.org"]))]
+        );
+        assert!(unrouted.is_empty());
+
+        let err = super::group_by_registry(router, &names(&["a.com"]), Some("three"))
+            .err()
+            .unwrap();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn domain_batch_check() {
+        use epp_proto::epp_proxy_server::EppProxy;
+
+        let chunks = Chunks::default();
+        let proxy = proxy(&chunks);
+        let reply = proxy
+            .domain_batch_check(tonic::Request::new(
+                epp_proto::domain::DomainBatchCheckRequest {
+                    names: names(&[
+                        "b.com",
+                        "a.net",
+                        "A.com",
+                        "missing.com",
+                        "B.COM",
+                        "c.com",
+                        "d.org",
+                    ]),
+                    fee_check: None,
+                    registry_name: None,
+                },
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let results = reply
+            .results
+            .iter()
+            .map(|r| {
+                (
+                    r.name.as_str(),
+                    r.registry_name.as_str(),
+                    r.available,
+                    r.error.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            vec![
+                ("B.COM", "one", true, None),
+                ("a.net", "two", false, Some("Server error: command failed")),
+                ("A.COM", "one", true, None),
+                ("missing.com", "one", false, Some(MISSING_CHECK_RESULT)),
+                ("C.COM", "one", true, None),
+                ("d.org", "", false, Some("unsupported domain")),
+            ]
+        );
+        // One response for each chunk the registry answered
+        assert_eq!(reply.cmd_resp.len(), 2);
+        let mut chunks = chunks.lock().unwrap().clone();
+        chunks.sort();
+        assert_eq!(
+            chunks,
+            vec![
+                names(&["a.net"]),
+                names(&["b.com", "A.com"]),
+                names(&["missing.com", "c.com"]),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn host_batch_check() {
+        use epp_proto::epp_proxy_server::EppProxy;
+
+        let chunks = Chunks::default();
+        let proxy = proxy(&chunks);
+        let check = |names, registry_name: &str| {
+            proxy.host_batch_check(tonic::Request::new(
+                epp_proto::host::HostBatchCheckRequest {
+                    names,
+                    registry_name: registry_name.to_string(),
+                },
+            ))
+        };
+
+        let reply = check(
+            names(&[
+                "ns1.example.com",
+                "missing.example.com",
+                "NS1.example.com",
+                "ns2.example.com",
+            ]),
+            "one",
+        )
+        .await
+        .unwrap()
+        .into_inner();
+        let results = reply
+            .results
+            .iter()
+            .map(|r| (r.name.as_str(), r.available, r.error.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            vec![
+                ("NS1.EXAMPLE.COM", true, None),
+                ("missing.example.com", false, Some(MISSING_CHECK_RESULT)),
+                ("NS2.EXAMPLE.COM", true, None),
+            ]
+        );
+        assert_eq!(reply.cmd_resp.len(), 2);
+
+        let reply = check(names(&["ns1.example.net"]), "two")
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            reply.results[0].error.as_deref(),
+            Some("Server error: command failed")
+        );
+        assert!(reply.cmd_resp.is_empty());
+
+        let err = check(names(&["ns1.example.com"]), "three")
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    /// Registry quoting 10.00 USD to create any domain, logged in with the given fee extension
+    fn fee_router(fee_extension: &str) -> std::sync::RwLock<crate::Router> {
+        let (sender, mut receiver) = client::queue::channel(16, std::time::Duration::from_secs(5));
+        tokio::spawn(async move {
+            while let Some((_, req)) = receiver.next().await {
+                match req {
+                    client::RequestMessage::DomainCheck(r) => {
+                        let fee_check = client::fee::FeeCheckData {
+                            available: true,
+                            commands: vec![client::fee::FeeCommand {
+                                command: client::fee::Command::Create,
+                                period: None,
+                                standard: None,
+                                currency: "USD".to_string(),
+                                fees: vec![client::fee::Fee {
+                                    value: "10.00".to_string(),
+                                    description: None,
+                                    refundable: None,
+                                    grace_period: None,
+                                    applied: client::fee::Applied::Unspecified,
+                                }],
+                                credits: vec![],
+                                reason: None,
+                                class: None,
+                            }],
+                            reason: None,
+                        };
+                        let _ = r.return_path.send(reply(
+                            false,
+                            client::domain::CheckResponse {
+                                avail: true,
+                                reason: None,
+                                fee_check: Some(fee_check),
+                                donuts_fee_check: None,
+                                eurid_check: None,
+                                eurid_idn: None,
+                            },
+                        ));
+                    }
+                    req => req.reject(client::Error::Unsupported),
+                }
+            }
+        });
+
+        let mut router = crate::Router::new();
+        router.id_to_client.insert("fee".to_string(), sender);
+        router.id_to_status.insert(
+            "fee".to_string(),
+            client::status::StatusHandle::new(client::status::ClientStatus {
+                extensions: vec![fee_extension.to_string()],
+                ..Default::default()
+            }),
+        );
+        std::sync::RwLock::new(router)
+    }
+
+    async fn enforce_create_fee(
+        router: &std::sync::RwLock<crate::Router>,
+        max_price: &str,
+    ) -> Result<FeeAgreements, tonic::Status> {
+        let mut sender = router.read().unwrap().client_by_id("fee").unwrap();
+        enforce_fee_policy(
+            router,
+            "fee",
+            "example.com",
+            client::fee::Command::Create,
+            None,
+            Some(epp_proto::fee::FeeCeiling {
+                max_price: max_price.to_string(),
+                currency: None,
+            }),
+            None,
+            &mut sender,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn fee_agreement() {
+        let router = fee_router("urn:ietf:params:xml:ns:epp:fee-1.0");
+        let agreement = enforce_create_fee(&router, "20.00")
+            .await
+            .unwrap()
+            .fee_agreement
+            .unwrap();
+        assert_eq!(agreement.currency.as_deref(), Some("USD"));
+        assert_eq!(agreement.fees.len(), 1);
+        assert_eq!(agreement.fees[0].value, "10.00");
+
+        let err = enforce_create_fee(&router, "5.00").await.err().unwrap();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn fee_agreement_pre_011() {
+        let router = fee_router("urn:ietf:params:xml:ns:fee-0.9");
+        let agreements = enforce_create_fee(&router, "20.00").await.unwrap();
+        assert!(agreements.fee_agreement.is_none());
+        assert!(agreements.donuts_fee_agreement.is_none());
+
+        let err = enforce_create_fee(&router, "5.00").await.err().unwrap();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+    }
+}
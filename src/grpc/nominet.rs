@@ -2,6 +2,17 @@ use std::convert::TryFrom;
 use super::super::client;
 use super::epp_proto;
 
+pub fn list_type_from_i32(from: i32) -> Option<client::nominet::ListType> {
+    epp_proto::nominet::list_request::ListType::try_from(from)
+        .ok()
+        .map(|t| match t {
+            epp_proto::nominet::list_request::ListType::Registration => {
+                client::nominet::ListType::Registration
+            }
+            epp_proto::nominet::list_request::ListType::Expiry => client::nominet::ListType::Expiry,
+        })
+}
+
 impl From<client::nominet::CancelData> for epp_proto::nominet::DomainCancel {
     fn from(res: client::nominet::CancelData) -> Self {
         epp_proto::nominet::DomainCancel {
@@ -83,6 +94,24 @@ impl From<client::nominet::DomainFailData> for epp_proto::nominet::DomainFail {
     }
 }
 
+impl From<client::nominet::AbuseFeedData> for epp_proto::nominet::AbuseFeed {
+    fn from(res: client::nominet::AbuseFeedData) -> Self {
+        epp_proto::nominet::AbuseFeed {
+            key: res.key,
+            activity: res.activity,
+            source: res.source,
+            hostname: res.hostname,
+            url: res.url,
+            date: super::utils::chrono_to_proto(Some(res.date)),
+            ip: res.ip,
+            nameserver: res.nameserver,
+            dns_admin: res.dns_admin,
+            target: res.target,
+            whole_domain: res.whole_domain,
+        }
+    }
+}
+
 impl From<client::nominet::RegistrantTransferData> for epp_proto::nominet::RegistrantTransfer {
     fn from(res: client::nominet::RegistrantTransferData) -> Self {
         epp_proto::nominet::RegistrantTransfer {
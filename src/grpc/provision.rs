@@ -0,0 +1,682 @@
+//! Registering a domain along with the contacts and hosts it needs in one call
+
+use super::epp_proto;
+use epp_proto::domain::domain_provision_reply::{Action, Step};
+use epp_proto::epp_proxy_server::EppProxy;
+
+/// Object created while provisioning, to be deleted again if a later step fails
+enum Created {
+    Contact(String),
+    Host(String),
+    Domain(String),
+}
+
+struct Provision<'a> {
+    proxy: &'a super::EPPProxy,
+    registry_name: String,
+    steps: Vec<Step>,
+    created: Vec<Created>,
+    domain: Option<epp_proto::domain::DomainCreateReply>,
+}
+
+impl Provision<'_> {
+    fn record(
+        &mut self,
+        action: Action,
+        object_id: &str,
+        res: Result<Option<epp_proto::common::CommandResponse>, &tonic::Status>,
+    ) {
+        self.steps.push(match res {
+            Ok(cmd_resp) => Step {
+                action: action.into(),
+                object_id: object_id.to_string(),
+                success: true,
+                error: None,
+                cmd_resp,
+            },
+            Err(err) => Step {
+                action: action.into(),
+                object_id: object_id.to_string(),
+                success: false,
+                error: Some(err.message().to_string()),
+                cmd_resp: super::utils::status_command_response(err),
+            },
+        });
+    }
+
+    /// Creates a contact, unless one with the same ID already exists
+    async fn contact(
+        &mut self,
+        contact: epp_proto::contact::ContactCreateRequest,
+    ) -> Result<(), tonic::Status> {
+        let id = contact.id.clone();
+        let res = self
+            .proxy
+            .contact_check(tonic::Request::new(
+                epp_proto::contact::ContactCheckRequest {
+                    id: id.clone(),
+                    registry_name: self.registry_name.clone(),
+                },
+            ))
+            .await
+            .map(tonic::Response::into_inner);
+        self.record(
+            Action::ContactCheck,
+            &id,
+            res.as_ref().map(|r| r.cmd_resp.clone()),
+        );
+        if !res?.available {
+            return Ok(());
+        }
+
+        let res = self
+            .proxy
+            .contact_create(tonic::Request::new(
+                epp_proto::contact::ContactCreateRequest {
+                    registry_name: self.registry_name.clone(),
+                    ..contact
+                },
+            ))
+            .await
+            .map(tonic::Response::into_inner);
+        self.record(
+            Action::ContactCreate,
+            &id,
+            res.as_ref().map(|r| r.cmd_resp.clone()),
+        );
+        res?;
+        self.created.push(Created::Contact(id));
+        Ok(())
+    }
+
+    /// Creates a host, unless one with the same name already exists
+    ///
+    /// Hosts subordinate to the domain being provisioned can't exist yet, so aren't checked for.
+    async fn host(
+        &mut self,
+        host: epp_proto::host::HostCreateRequest,
+        check: bool,
+    ) -> Result<(), tonic::Status> {
+        let name = host.name.clone();
+        if check {
+            let res = self
+                .proxy
+                .host_check(tonic::Request::new(epp_proto::host::HostCheckRequest {
+                    name: name.clone(),
+                    registry_name: self.registry_name.clone(),
+                }))
+                .await
+                .map(tonic::Response::into_inner);
+            self.record(
+                Action::HostCheck,
+                &name,
+                res.as_ref().map(|r| r.cmd_resp.clone()),
+            );
+            if !res?.available {
+                return Ok(());
+            }
+        }
+
+        let res = self
+            .proxy
+            .host_create(tonic::Request::new(epp_proto::host::HostCreateRequest {
+                registry_name: self.registry_name.clone(),
+                ..host
+            }))
+            .await
+            .map(tonic::Response::into_inner);
+        self.record(
+            Action::HostCreate,
+            &name,
+            res.as_ref().map(|r| r.cmd_resp.clone()),
+        );
+        res?;
+        self.created.push(Created::Host(name));
+        Ok(())
+    }
+
+    async fn domain(
+        &mut self,
+        domain: epp_proto::domain::DomainCreateRequest,
+    ) -> Result<(), tonic::Status> {
+        let name = domain.name.clone();
+        let res = self
+            .proxy
+            .domain_create(tonic::Request::new(domain))
+            .await
+            .map(tonic::Response::into_inner);
+        self.record(
+            Action::DomainCreate,
+            &name,
+            res.as_ref().map(|r| r.cmd_resp.clone()),
+        );
+        let res = res?;
+        // A pending create can't be deleted, so there's nothing to roll back
+        if !res.pending {
+            self.created.push(Created::Domain(name));
+        }
+        self.domain = Some(res);
+        Ok(())
+    }
+
+    /// Is the registry still to complete the domain create
+    fn domain_pending(&self) -> bool {
+        self.domain.as_ref().map_or(false, |d| d.pending)
+    }
+
+    /// Adds nameservers that could only be created once the domain existed
+    async fn nameservers(
+        &mut self,
+        name: &str,
+        nameservers: Vec<epp_proto::domain::NameServer>,
+    ) -> Result<(), tonic::Status> {
+        use epp_proto::domain::domain_update_request as update;
+
+        let res = self
+            .proxy
+            .domain_update(tonic::Request::new(
+                epp_proto::domain::DomainUpdateRequest {
+                    name: name.to_string(),
+                    add: nameservers
+                        .into_iter()
+                        .map(|n| update::Param {
+                            param: Some(update::param::Param::Nameserver(n)),
+                        })
+                        .collect(),
+                    registry_name: Some(self.registry_name.clone()),
+                    ..Default::default()
+                },
+            ))
+            .await
+            .map(tonic::Response::into_inner);
+        self.record(
+            Action::DomainUpdate,
+            name,
+            res.as_ref().map(|r| r.cmd_resp.clone()),
+        );
+        res?;
+        Ok(())
+    }
+
+    /// Deletes everything created so far, newest first, returning whether every delete succeeded
+    async fn roll_back(&mut self) -> bool {
+        let mut clean = true;
+        while let Some(created) = self.created.pop() {
+            let (action, id, res) = match created {
+                Created::Contact(id) => {
+                    let res = self
+                        .proxy
+                        .contact_delete(tonic::Request::new(
+                            epp_proto::contact::ContactDeleteRequest {
+                                id: id.clone(),
+                                registry_name: self.registry_name.clone(),
+                            },
+                        ))
+                        .await
+                        .map(|r| r.into_inner().cmd_resp);
+                    (Action::ContactDelete, id, res)
+                }
+                Created::Host(name) => {
+                    let res = self
+                        .proxy
+                        .host_delete(tonic::Request::new(epp_proto::host::HostDeleteRequest {
+                            name: name.clone(),
+                            registry_name: self.registry_name.clone(),
+                        }))
+                        .await
+                        .map(|r| r.into_inner().cmd_resp);
+                    (Action::HostDelete, name, res)
+                }
+                Created::Domain(name) => {
+                    let res = self
+                        .proxy
+                        .domain_delete(tonic::Request::new(
+                            epp_proto::domain::DomainDeleteRequest {
+                                name: name.clone(),
+                                registry_name: Some(self.registry_name.clone()),
+                                ..Default::default()
+                            },
+                        ))
+                        .await
+                        .map(|r| r.into_inner().cmd_resp);
+                    (Action::DomainDelete, name, res)
+                }
+            };
+            clean &= res.is_ok();
+            self.record(action, &id, res.as_ref().map(Clone::clone));
+        }
+        clean
+    }
+}
+
+/// Splits out the hosts subordinate to a domain, and the nameservers of the domain referring to
+/// them, as neither can be created until the domain exists
+///
+/// Returns the subordinate hosts, the other hosts, and the nameservers taken off the domain.
+///
+/// # Arguments
+/// * `domain` - Domain to be created
+/// * `hosts` - Hosts to be created along with it
+fn split_glue(
+    domain: &mut epp_proto::domain::DomainCreateRequest,
+    hosts: Vec<epp_proto::host::HostCreateRequest>,
+) -> (
+    Vec<epp_proto::host::HostCreateRequest>,
+    Vec<epp_proto::host::HostCreateRequest>,
+    Vec<epp_proto::domain::NameServer>,
+) {
+    let suffix = format!(".{}", domain.name.trim_end_matches('.').to_lowercase());
+    let (glue, hosts): (Vec<_>, Vec<_>) = hosts.into_iter().partition(|h| {
+        h.name
+            .trim_end_matches('.')
+            .to_lowercase()
+            .ends_with(&suffix)
+    });
+    let glue_names = glue
+        .iter()
+        .map(|h| h.name.trim_end_matches('.').to_lowercase())
+        .collect::<std::collections::HashSet<_>>();
+    let (deferred, nameservers): (Vec<_>, Vec<_>) =
+        domain.nameservers.drain(..).partition(|n| match &n.server {
+            Some(epp_proto::domain::name_server::Server::HostObj(h)) => {
+                glue_names.contains(&h.trim_end_matches('.').to_lowercase())
+            }
+            _ => false,
+        });
+    domain.nameservers = nameservers;
+    (glue, hosts, deferred)
+}
+
+/// Creates any missing contacts and hosts a domain needs, then the domain itself, deleting
+/// whatever was created if a step fails
+///
+/// Hosts subordinate to the domain are created after it, and any of its nameservers referring
+/// to them are added in a follow up update. If the registry leaves the domain create pending
+/// nothing after it is attempted, and nothing is rolled back as the domain still needs its
+/// contacts and hosts; this is an error only if subordinate hosts were still to be created.
+pub async fn provision(
+    proxy: &super::EPPProxy,
+    request: epp_proto::domain::DomainProvisionRequest,
+) -> Result<epp_proto::domain::DomainProvisionReply, tonic::Status> {
+    let mut domain = match request.domain {
+        Some(d) => d,
+        None => return Err(tonic::Status::invalid_argument("domain must be specified")),
+    };
+    let (_, registry_name) = super::client_by_domain_or_id(
+        &proxy.client_router,
+        &domain.name,
+        domain.registry_name.take(),
+    )?;
+    domain.registry_name = Some(registry_name.clone());
+
+    let (glue, hosts, deferred) = split_glue(&mut domain, request.hosts);
+    let domain_name = domain.name.clone();
+    let contacts = request.contacts;
+
+    let mut provision = Provision {
+        proxy,
+        registry_name: registry_name.clone(),
+        steps: vec![],
+        created: vec![],
+        domain: None,
+    };
+    let res = async {
+        for contact in contacts {
+            provision.contact(contact).await?;
+        }
+        for host in hosts {
+            provision.host(host, true).await?;
+        }
+        provision.domain(domain).await?;
+        if provision.domain_pending() {
+            if glue.is_empty() && deferred.is_empty() {
+                return Ok(());
+            }
+            return Err(tonic::Status::failed_precondition(
+                "domain create is pending, hosts under it can't be created until it completes",
+            ));
+        }
+        for host in glue {
+            provision.host(host, false).await?;
+        }
+        if !deferred.is_empty() {
+            provision.nameservers(&domain_name, deferred).await?;
+        }
+        Ok::<_, tonic::Status>(())
+    }
+    .await;
+
+    let (success, rolled_back, error) = match res {
+        Ok(()) => (true, false, None),
+        Err(err) => {
+            // Objects a pending domain refers to have to stay for it to complete
+            let rolled_back = !provision.domain_pending() && provision.roll_back().await;
+            (false, rolled_back, Some(err.message().to_string()))
+        }
+    };
+
+    Ok(epp_proto::domain::DomainProvisionReply {
+        success,
+        rolled_back,
+        error,
+        steps: provision.steps,
+        domain: provision.domain,
+        registry_name,
+    })
+}
+
+#[cfg(test)]
+mod provision_tests {
+    use super::super::EPPProxy;
+    use super::*;
+    use crate::client;
+    use futures::StreamExt;
+    use std::convert::TryFrom;
+
+    fn reply<T>(fail: bool, response: T) -> client::Response<client::CommandResponse<T>> {
+        if fail {
+            return Err(client::Error::EPP(client::EPPError {
+                code: 2306,
+                message: "command failed".to_string(),
+                reasons: vec![],
+                transaction_id: client::router::CommandTransactionID {
+                    client: "client-1".to_string(),
+                    server: "server-1".to_string(),
+                },
+            }));
+        }
+        Ok(client::CommandResponse {
+            response,
+            extra_values: vec![],
+            unhandled_namespaces: vec![],
+            transaction_id: None,
+        })
+    }
+
+    /// Proxy to a registry where every contact and host is available, failing the given commands
+    fn proxy(domain_pending: bool, fail: &'static [Action]) -> EPPProxy {
+        let (sender, mut receiver) = client::queue::channel(16, std::time::Duration::from_secs(5));
+        let mut router = crate::Router::new();
+        router.id_to_client.insert("test".to_string(), sender);
+
+        tokio::spawn(async move {
+            while let Some((_, req)) = receiver.next().await {
+                match req {
+                    client::RequestMessage::ContactCheck(r) => {
+                        let _ = r.return_path.send(reply(
+                            fail.contains(&Action::ContactCheck),
+                            client::contact::CheckResponse {
+                                avail: true,
+                                reason: None,
+                            },
+                        ));
+                    }
+                    client::RequestMessage::ContactCreate(r) => {
+                        let _ = r.return_path.send(reply(
+                            fail.contains(&Action::ContactCreate),
+                            client::contact::CreateResponse {
+                                id: String::new(),
+                                pending: false,
+                                creation_date: None,
+                            },
+                        ));
+                    }
+                    client::RequestMessage::ContactDelete(r) => {
+                        let _ = r.return_path.send(reply(
+                            fail.contains(&Action::ContactDelete),
+                            client::contact::DeleteResponse { pending: false },
+                        ));
+                    }
+                    client::RequestMessage::HostCheck(r) => {
+                        let _ = r.return_path.send(reply(
+                            fail.contains(&Action::HostCheck),
+                            client::host::CheckResponse {
+                                avail: true,
+                                reason: None,
+                            },
+                        ));
+                    }
+                    client::RequestMessage::HostCreate(r) => {
+                        let _ = r.return_path.send(reply(
+                            fail.contains(&Action::HostCreate),
+                            client::host::CreateResponse {
+                                name: String::new(),
+                                pending: false,
+                                transaction_id: String::new(),
+                                creation_date: None,
+                            },
+                        ));
+                    }
+                    client::RequestMessage::HostDelete(r) => {
+                        let _ = r.return_path.send(reply(
+                            fail.contains(&Action::HostDelete),
+                            client::host::DeleteResponse {
+                                pending: false,
+                                transaction_id: String::new(),
+                            },
+                        ));
+                    }
+                    client::RequestMessage::DomainCreate(r) => {
+                        let _ = r.return_path.send(reply(
+                            fail.contains(&Action::DomainCreate),
+                            client::domain::CreateResponse {
+                                pending: domain_pending,
+                                data: client::domain::CreateData {
+                                    name: "example.com".to_string(),
+                                    creation_date: None,
+                                    expiration_date: None,
+                                    eurid_idn: None,
+                                    personal_registration: None,
+                                },
+                                fee_data: None,
+                                donuts_fee_data: None,
+                                launch_create: None,
+                            },
+                        ));
+                    }
+                    client::RequestMessage::DomainUpdate(r) => {
+                        let _ = r.return_path.send(reply(
+                            fail.contains(&Action::DomainUpdate),
+                            client::domain::UpdateResponse {
+                                pending: false,
+                                fee_data: None,
+                                donuts_fee_data: None,
+                            },
+                        ));
+                    }
+                    client::RequestMessage::DomainDelete(r) => {
+                        let _ = r.return_path.send(reply(
+                            fail.contains(&Action::DomainDelete),
+                            client::domain::DeleteResponse {
+                                pending: false,
+                                fee_data: None,
+                                eurid_idn: None,
+                            },
+                        ));
+                    }
+                    req => req.reject(client::Error::Unsupported),
+                }
+            }
+        });
+
+        EPPProxy {
+            client_router: std::sync::Arc::new(std::sync::RwLock::new(router)),
+        }
+    }
+
+    fn host_obj(name: &str) -> epp_proto::domain::NameServer {
+        epp_proto::domain::NameServer {
+            server: Some(epp_proto::domain::name_server::Server::HostObj(
+                name.to_string(),
+            )),
+            ..Default::default()
+        }
+    }
+
+    fn host(name: &str) -> epp_proto::host::HostCreateRequest {
+        epp_proto::host::HostCreateRequest {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn request() -> epp_proto::domain::DomainProvisionRequest {
+        epp_proto::domain::DomainProvisionRequest {
+            domain: Some(epp_proto::domain::DomainCreateRequest {
+                name: "example.com".to_string(),
+                nameservers: vec![host_obj("ns1.example.com"), host_obj("ns.example.net")],
+                registry_name: Some("test".to_string()),
+                ..Default::default()
+            }),
+            contacts: vec![epp_proto::contact::ContactCreateRequest {
+                id: "contact1".to_string(),
+                ..Default::default()
+            }],
+            hosts: vec![host("ns1.example.com"), host("ns.example.net")],
+        }
+    }
+
+    fn steps(reply: &epp_proto::domain::DomainProvisionReply) -> Vec<(Action, &str, bool)> {
+        reply
+            .steps
+            .iter()
+            .map(|s| {
+                (
+                    Action::try_from(s.action).unwrap(),
+                    s.object_id.as_str(),
+                    s.success,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn glue_partition() {
+        let mut domain = epp_proto::domain::DomainCreateRequest {
+            name: "Example.com.".to_string(),
+            nameservers: vec![
+                host_obj("ns1.example.com."),
+                host_obj("ns.example.net"),
+                epp_proto::domain::NameServer {
+                    server: Some(epp_proto::domain::name_server::Server::HostName(
+                        "ns3.example.com".to_string(),
+                    )),
+                    ..Default::default()
+                },
+                host_obj("NS2.example.com"),
+            ],
+            ..Default::default()
+        };
+        let (glue, hosts, deferred) = split_glue(
+            &mut domain,
+            vec![
+                host("ns1.example.com"),
+                host("ns.example.net"),
+                host("ns.notexample.com"),
+                host("ns2.EXAMPLE.com."),
+            ],
+        );
+
+        let names = |hosts: &[epp_proto::host::HostCreateRequest]| {
+            hosts.iter().map(|h| h.name.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(names(&glue), vec!["ns1.example.com", "ns2.EXAMPLE.com."]);
+        assert_eq!(names(&hosts), vec!["ns.example.net", "ns.notexample.com"]);
+        assert_eq!(
+            deferred,
+            vec![host_obj("ns1.example.com."), host_obj("NS2.example.com")]
+        );
+        assert_eq!(domain.nameservers.len(), 2);
+        assert_eq!(domain.nameservers[0], host_obj("ns.example.net"));
+    }
+
+    #[tokio::test]
+    async fn provision_deferred_nameservers() {
+        let proxy = proxy(false, &[]);
+        let reply = provision(&proxy, request()).await.unwrap();
+
+        assert!(reply.success);
+        assert!(!reply.rolled_back);
+        assert_eq!(
+            steps(&reply),
+            vec![
+                (Action::ContactCheck, "contact1", true),
+                (Action::ContactCreate, "contact1", true),
+                (Action::HostCheck, "ns.example.net", true),
+                (Action::HostCreate, "ns.example.net", true),
+                (Action::DomainCreate, "example.com", true),
+                (Action::HostCreate, "ns1.example.com", true),
+                (Action::DomainUpdate, "example.com", true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn provision_roll_back() {
+        let proxy = proxy(false, &[Action::DomainUpdate]);
+        let reply = provision(&proxy, request()).await.unwrap();
+
+        assert!(!reply.success);
+        assert!(reply.rolled_back);
+        assert_eq!(reply.error.as_deref(), Some("command failed"));
+        assert_eq!(
+            steps(&reply)[6..],
+            [
+                (Action::DomainUpdate, "example.com", false),
+                (Action::HostDelete, "ns1.example.com", true),
+                (Action::DomainDelete, "example.com", true),
+                (Action::HostDelete, "ns.example.net", true),
+                (Action::ContactDelete, "contact1", true),
+            ]
+        );
+        let transaction_id = reply.steps[6]
+            .cmd_resp
+            .as_ref()
+            .and_then(|r| r.transaction_id.as_ref())
+            .unwrap();
+        assert_eq!(transaction_id.client, "client-1");
+        assert_eq!(transaction_id.server, "server-1");
+    }
+
+    #[tokio::test]
+    async fn provision_roll_back_incomplete() {
+        let proxy = proxy(false, &[Action::DomainUpdate, Action::DomainDelete]);
+        let reply = provision(&proxy, request()).await.unwrap();
+
+        assert!(!reply.success);
+        assert!(!reply.rolled_back);
+        assert_eq!(
+            steps(&reply)[7..],
+            [
+                (Action::HostDelete, "ns1.example.com", true),
+                (Action::DomainDelete, "example.com", false),
+                (Action::HostDelete, "ns.example.net", true),
+                (Action::ContactDelete, "contact1", true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn provision_domain_pending() {
+        let proxy = proxy(true, &[]);
+        let reply = provision(&proxy, request()).await.unwrap();
+
+        assert!(!reply.success);
+        assert!(!reply.rolled_back);
+        assert!(reply.domain.as_ref().unwrap().pending);
+        assert_eq!(steps(&reply).len(), 5);
+        assert_eq!(
+            steps(&reply)[4],
+            (Action::DomainCreate, "example.com", true)
+        );
+
+        let mut request = request();
+        request.hosts.clear();
+        if let Some(d) = request.domain.as_mut() {
+            d.nameservers.clear();
+        }
+        let reply = provision(&proxy, request).await.unwrap();
+        assert!(reply.success);
+        assert!(reply.domain.as_ref().unwrap().pending);
+    }
+}
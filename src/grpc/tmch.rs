@@ -328,3 +328,43 @@ impl From<epp_proto::tmch::CaseUpdate> for client::tmch::CaseUpdate {
         }
     }
 }
+
+impl From<epp_proto::tmch::TrexActivateLabel> for client::tmch::TrexActivateLabel {
+    fn from(from: epp_proto::tmch::TrexActivateLabel) -> Self {
+        client::tmch::TrexActivateLabel {
+            label: from.label,
+            period: from.period.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<epp_proto::tmch::TrexRenewLabel> for client::tmch::TrexRenewLabel {
+    type Error = tonic::Status;
+
+    fn try_from(from: epp_proto::tmch::TrexRenewLabel) -> Result<Self, Self::Error> {
+        Ok(client::tmch::TrexRenewLabel {
+            label: from.label,
+            current_expiry_date: match super::utils::proto_to_chrono(from.current_expiry_date) {
+                Some(d) => d.date_naive(),
+                None => {
+                    return Err(tonic::Status::invalid_argument(
+                        "current_expiry_date must be specified",
+                    ))
+                }
+            },
+            period: from.add_period.map(Into::into),
+        })
+    }
+}
+
+impl From<client::tmch::TrexActivateResponse> for epp_proto::tmch::MarkTrexActivateResponse {
+    fn from(_res: client::tmch::TrexActivateResponse) -> Self {
+        epp_proto::tmch::MarkTrexActivateResponse { cmd_resp: None }
+    }
+}
+
+impl From<client::tmch::TrexRenewResponse> for epp_proto::tmch::MarkTrexRenewResponse {
+    fn from(_res: client::tmch::TrexRenewResponse) -> Self {
+        epp_proto::tmch::MarkTrexRenewResponse { cmd_resp: None }
+    }
+}
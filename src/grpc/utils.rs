@@ -44,10 +44,151 @@ pub fn proto_to_chrono(
     }
 }
 
+/// Removes duplicate objects from a batch check, keeping the order they were first given in
+pub fn dedup_check_objects(objects: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    objects
+        .into_iter()
+        .filter(|o| !o.is_empty() && seen.insert(o.to_lowercase()))
+        .collect()
+}
+
+/// Runs a check command for each chunk of objects in parallel
+///
+/// # Arguments
+/// * `objects` - Objects to check, all on the same registry
+/// * `max_check_size` - Most objects the registry accepts in one check command
+/// * `check` - Sends the check command for a single chunk
+pub async fn chunked_check<T, F, Fut>(
+    objects: Vec<String>,
+    max_check_size: usize,
+    check: F,
+) -> Vec<(
+    Vec<String>,
+    Result<client::CommandResponse<T>, client::Error>,
+)>
+where
+    F: Fn(Vec<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<client::CommandResponse<T>, client::Error>>,
+{
+    futures::future::join_all(objects.chunks(max_check_size.max(1)).map(|chunk| {
+        let chunk = chunk.to_vec();
+        let res = check(chunk.clone());
+        async move { (chunk, res.await) }
+    }))
+    .await
+}
+
+/// gRPC status code and `ErrorInfo` reason for an EPP error result code
+fn epp_result_status(code: u16) -> (tonic::Code, &'static str) {
+    use tonic::Code;
+
+    match code {
+        2000 => (Code::InvalidArgument, "UNKNOWN_COMMAND"),
+        2001 => (Code::InvalidArgument, "COMMAND_SYNTAX_ERROR"),
+        2002 => (Code::FailedPrecondition, "COMMAND_USE_ERROR"),
+        2003 => (Code::InvalidArgument, "REQUIRED_PARAMETER_MISSING"),
+        2004 => (Code::OutOfRange, "PARAMETER_VALUE_RANGE_ERROR"),
+        2005 => (Code::InvalidArgument, "PARAMETER_VALUE_SYNTAX_ERROR"),
+        2100 => (Code::Unimplemented, "UNIMPLEMENTED_PROTOCOL_VERSION"),
+        2101 => (Code::Unimplemented, "UNIMPLEMENTED_COMMAND"),
+        2102 => (Code::Unimplemented, "UNIMPLEMENTED_OPTION"),
+        2103 => (Code::Unimplemented, "UNIMPLEMENTED_EXTENSION"),
+        2104 => (Code::FailedPrecondition, "BILLING_FAILURE"),
+        2105 => (Code::FailedPrecondition, "OBJECT_NOT_ELIGIBLE_FOR_RENEWAL"),
+        2106 => (Code::FailedPrecondition, "OBJECT_NOT_ELIGIBLE_FOR_TRANSFER"),
+        2200 => (Code::Unauthenticated, "AUTHENTICATION_ERROR"),
+        2201 => (Code::PermissionDenied, "AUTHORIZATION_ERROR"),
+        2202 => (Code::PermissionDenied, "INVALID_AUTHORIZATION"),
+        2300 => (Code::FailedPrecondition, "OBJECT_PENDING_TRANSFER"),
+        2301 => (Code::FailedPrecondition, "OBJECT_NOT_PENDING_TRANSFER"),
+        2302 => (Code::AlreadyExists, "OBJECT_EXISTS"),
+        2303 => (Code::NotFound, "OBJECT_DOES_NOT_EXIST"),
+        2304 => (
+            Code::FailedPrecondition,
+            "OBJECT_STATUS_PROHIBITS_OPERATION",
+        ),
+        2305 => (
+            Code::FailedPrecondition,
+            "OBJECT_ASSOCIATION_PROHIBITS_OPERATION",
+        ),
+        2306 => (Code::InvalidArgument, "PARAMETER_VALUE_POLICY_ERROR"),
+        2307 => (Code::Unimplemented, "UNIMPLEMENTED_OBJECT_SERVICE"),
+        2308 => (Code::FailedPrecondition, "DATA_MANAGEMENT_POLICY_VIOLATION"),
+        2400 => (Code::Internal, "COMMAND_FAILED"),
+        2500 => (
+            Code::Unavailable,
+            "COMMAND_FAILED_SERVER_CLOSING_CONNECTION",
+        ),
+        2501 => (
+            Code::Unavailable,
+            "AUTHENTICATION_ERROR_SERVER_CLOSING_CONNECTION",
+        ),
+        2502 => (Code::Unavailable, "SESSION_LIMIT_EXCEEDED"),
+        _ => (Code::Unknown, "UNKNOWN_RESULT"),
+    }
+}
+
+/// Domain of the `ErrorInfo` attached to EPP errors
+pub const EPP_ERROR_DOMAIN: &str = "epp-proxy";
+
+impl From<client::EPPError> for tonic::Status {
+    /// Maps the EPP result code to the closest gRPC code, and attaches a `google.rpc.Status`
+    /// payload with an `ErrorInfo` carrying the result code, a `RequestInfo` carrying the client
+    /// and server transaction IDs, and a `BadRequest` listing any `extValue` reasons.
+    fn from(err: client::EPPError) -> Self {
+        use tonic_types::StatusExt;
+
+        let (code, reason) = epp_result_status(err.code);
+        let message = err.to_string();
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert("result_code".to_string(), err.code.to_string());
+        let mut details = tonic_types::ErrorDetails::new();
+        details
+            .set_error_info(reason, EPP_ERROR_DOMAIN, metadata)
+            .set_request_info(err.transaction_id.client, err.transaction_id.server);
+        for r in err.reasons {
+            details.add_bad_request_violation(r.value, r.reason);
+        }
+        tonic::Status::with_error_details(code, message, details)
+    }
+}
+
+/// Command response for a failed command, from the transaction IDs and reasons attached to a
+/// status made from an EPP error
+pub fn status_command_response(
+    status: &tonic::Status,
+) -> Option<epp_proto::common::CommandResponse> {
+    use tonic_types::StatusExt;
+
+    let details = status.get_error_details();
+    let request_info = details.request_info()?;
+    Some(epp_proto::common::CommandResponse {
+        extra_values: details
+            .bad_request()
+            .map(|b| {
+                b.field_violations
+                    .iter()
+                    .map(|v| epp_proto::common::CommandExtraValue {
+                        reason: v.description.clone(),
+                        value: v.field.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        transaction_id: Some(epp_proto::common::CommandTransactionId {
+            client: request_info.request_id.clone(),
+            server: request_info.serving_data.clone(),
+        }),
+        unhandled_namespaces: vec![],
+    })
+}
+
 impl From<client::Error> for tonic::Status {
     fn from(err: client::Error) -> Self {
         match err {
             client::Error::Err(s) => tonic::Status::invalid_argument(s),
+            client::Error::EPP(e) => e.into(),
             client::Error::NotReady => tonic::Status::unavailable("not yet ready"),
             client::Error::Unsupported => {
                 tonic::Status::unimplemented("unsupported operation for registrar")
@@ -121,6 +262,14 @@ pub fn map_command_response<T>(
                     client: t.client,
                     server: t.server,
                 }),
+            unhandled_namespaces: from
+                .unhandled_namespaces
+                .into_iter()
+                .map(|u| epp_proto::common::UnhandledNamespace {
+                    namespace: u.namespace,
+                    xml: u.xml,
+                })
+                .collect(),
         },
     )
 }
@@ -168,3 +317,125 @@ impl From<client::Phone> for epp_proto::common::Phone {
         }
     }
 }
+
+#[cfg(test)]
+mod utils_tests {
+    use super::client;
+
+    #[test]
+    fn dedup_check_objects() {
+        assert_eq!(
+            super::dedup_check_objects(vec![
+                "b.com".to_string(),
+                "A.com".to_string(),
+                String::new(),
+                "a.com".to_string(),
+                "B.COM".to_string(),
+                "c.com".to_string(),
+            ]),
+            vec!["b.com", "A.com", "c.com"]
+        );
+    }
+
+    /// Chunks `n` objects are checked in, making sure each chunk got its own response
+    async fn check_chunks(n: usize, max_check_size: usize) -> Vec<Vec<String>> {
+        let objects = (0..n).map(|i| i.to_string()).collect::<Vec<_>>();
+        let res = super::chunked_check(objects.clone(), max_check_size, |chunk| async move {
+            Ok(client::CommandResponse {
+                response: chunk,
+                extra_values: vec![],
+                unhandled_namespaces: vec![],
+                transaction_id: None,
+            })
+        })
+        .await;
+        let chunks = res
+            .into_iter()
+            .map(|(chunk, res)| {
+                assert_eq!(res.unwrap().response, chunk);
+                chunk
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(chunks.concat(), objects);
+        chunks
+    }
+
+    #[tokio::test]
+    async fn chunked_check() {
+        let sizes = |chunks: Vec<Vec<String>>| chunks.iter().map(Vec::len).collect::<Vec<_>>();
+        assert_eq!(sizes(check_chunks(6, 3).await), vec![3, 3]);
+        assert_eq!(sizes(check_chunks(7, 3).await), vec![3, 3, 1]);
+        assert_eq!(sizes(check_chunks(3, 5).await), vec![3]);
+        assert_eq!(sizes(check_chunks(2, 0).await), vec![1, 1]);
+        assert!(check_chunks(0, 3).await.is_empty());
+    }
+
+    #[test]
+    fn epp_error_status() {
+        use tonic_types::StatusExt;
+
+        let status: tonic::Status = client::EPPError {
+            code: 2302,
+            message: "(ObjectExists) Object exists".to_string(),
+            reasons: vec![client::router::CommandExtraValue {
+                value: "<domain:name>example.com</domain:name>".to_string(),
+                reason: "Domain is already registered".to_string(),
+            }],
+            transaction_id: client::router::CommandTransactionID {
+                client: "ABC-12345".to_string(),
+                server: "54322-XYZ".to_string(),
+            },
+        }
+        .into();
+        assert_eq!(status.code(), tonic::Code::AlreadyExists);
+        assert_eq!(
+            status.message(),
+            "(ObjectExists) Object exists: (<domain:name>example.com</domain:name>) Domain is already registered"
+        );
+
+        let details = status.get_error_details();
+        let error_info = details.error_info().unwrap();
+        assert_eq!(error_info.reason, "OBJECT_EXISTS");
+        assert_eq!(error_info.domain, super::EPP_ERROR_DOMAIN);
+        assert_eq!(
+            error_info.metadata.get("result_code").map(String::as_str),
+            Some("2302")
+        );
+        let request_info = details.request_info().unwrap();
+        assert_eq!(request_info.request_id, "ABC-12345");
+        assert_eq!(request_info.serving_data, "54322-XYZ");
+        let bad_request = details.bad_request().unwrap();
+        assert_eq!(bad_request.field_violations.len(), 1);
+        assert_eq!(
+            bad_request.field_violations[0].description,
+            "Domain is already registered"
+        );
+
+        let cmd_resp = super::status_command_response(&status).unwrap();
+        let transaction_id = cmd_resp.transaction_id.unwrap();
+        assert_eq!(transaction_id.client, "ABC-12345");
+        assert_eq!(transaction_id.server, "54322-XYZ");
+        assert_eq!(cmd_resp.extra_values.len(), 1);
+        assert_eq!(
+            cmd_resp.extra_values[0].value,
+            "<domain:name>example.com</domain:name>"
+        );
+        assert!(super::status_command_response(&tonic::Status::invalid_argument("bad")).is_none());
+    }
+
+    #[test]
+    fn epp_server_error_status() {
+        let status: tonic::Status = client::EPPError {
+            code: 2400,
+            message: "(CommandFailed) Command failed".to_string(),
+            reasons: vec![],
+            transaction_id: client::router::CommandTransactionID {
+                client: "ABC-12345".to_string(),
+                server: "54322-XYZ".to_string(),
+            },
+        }
+        .into();
+        assert_eq!(status.code(), tonic::Code::Internal);
+        assert_eq!(status.message(), "Server error: (CommandFailed) Command failed");
+    }
+}
@@ -11,26 +11,28 @@ pub mod client;
 pub mod grpc;
 pub mod metrics;
 pub mod proto;
+pub mod redact;
+pub mod secret;
 
 #[allow(missing_docs)]
 pub mod built_info {
     include!(concat!(env!("OUT_DIR"), "/built.rs"));
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 #[serde(untagged)]
 enum ClientCertConfig {
     PKCS12(String),
     PKCS11 { key_id: String, cert_chain: String },
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 struct NominetDACConfig {
     real_time: String,
     time_delay: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct ConfigFile {
     /// Unique registry ID
     pub id: String,
@@ -45,8 +47,10 @@ pub struct ConfigFile {
     /// Client ID to login to the server
     pub tag: String,
     /// Password to login to the server
+    #[serde(deserialize_with = "secret::deserialize")]
     pub password: String,
     /// New password if the password is to be changed
+    #[serde(default, deserialize_with = "secret::deserialize_opt")]
     pub new_password: Option<String>,
     /// The zones the server is responsible for such as `co.uk` or `ch`
     zones: Vec<String>,
@@ -60,12 +64,140 @@ pub struct ConfigFile {
     danger_accept_invalid_hostnames: Option<bool>,
     /// Does the server support pipelining?
     pipelining: bool,
+    /// Number of sessions to hold open to the server at once
+    #[serde(default)]
+    sessions: Option<usize>,
+    /// Most objects the server accepts in a single check command
+    #[serde(default)]
+    max_check_size: Option<usize>,
+    /// Elements to remove from the message log, replacing the defaults
+    #[serde(default)]
+    redact: Option<Vec<redact::RedactRule>>,
     /// For naughty servers
     pub errata: Option<String>,
     nominet_dac: Option<NominetDACConfig>,
+    /// Waiting queue for requests made while the server is reconnecting
+    #[serde(default)]
+    queue: QueueConfig,
+    /// Timeouts to use with this server
+    #[serde(default)]
+    timeouts: TimeoutConfig,
+    /// Backoff and circuit breaker policy for reconnecting
+    #[serde(default)]
+    reconnect: ReconnectConfig,
+    /// Fee check and maximum price enforcement on billable commands, off if not given
+    #[serde(default)]
+    fee_policy: Option<FeePolicyConfig>,
+    /// Automatic password changes when the server warns the password is expiring, off if not given
+    #[serde(default)]
+    password_rotation: Option<PasswordRotationConfig>,
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct PasswordRotationConfig {
+    /// Where to store new passwords, before and after they're set
+    sink: SecretSinkConfig,
+    /// Length of generated passwords, defaults to 16
+    length: Option<usize>,
+    /// Days before the password expires to change it, defaults to 14
+    rotate_before_days: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum SecretSinkConfig {
+    /// Overwrite a file with the new password
+    File(String),
+    /// Run a command with the new password on its standard input
+    Command(Vec<String>),
+}
+
+impl From<&PasswordRotationConfig> for client::login_sec::PasswordRotation {
+    fn from(from: &PasswordRotationConfig) -> Self {
+        let sink: std::sync::Arc<dyn client::login_sec::SecretSink> = match &from.sink {
+            SecretSinkConfig::File(path) => {
+                std::sync::Arc::new(client::login_sec::FileSecretSink::new(path))
+            }
+            SecretSinkConfig::Command(command) => std::sync::Arc::new(
+                client::login_sec::CommandSecretSink::new(command.clone()),
+            ),
+        };
+        client::login_sec::PasswordRotation::new(
+            sink,
+            from.length.unwrap_or(client::login_sec::MAX_BASE_PASSWORD_LENGTH),
+            chrono::Duration::days(from.rotate_before_days.unwrap_or(14)),
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+struct FeePolicyConfig {
+    /// Refuse billable commands without a maximum price, defaults to true
+    require_max_price: Option<bool>,
+    /// Allow billable commands the registry doesn't quote a price for, defaults to false
+    allow_unpriced: Option<bool>,
+}
+
+impl From<&FeePolicyConfig> for client::fee::FeePolicy {
+    fn from(from: &FeePolicyConfig) -> Self {
+        let default = client::fee::FeePolicy::default();
+        client::fee::FeePolicy {
+            require_max_price: from.require_max_price.unwrap_or(default.require_max_price),
+            allow_unpriced: from.allow_unpriced.unwrap_or(default.allow_unpriced),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+struct ReconnectConfig {
+    /// Maximum seconds to wait between connection attempts
+    max_delay: Option<u64>,
+    /// Factor the delay is multiplied by after each failure, at least 1
+    #[serde(default, deserialize_with = "deserialize_multiplier")]
+    multiplier: Option<f64>,
+    /// Fraction of the delay to randomly subtract, between 0 and 1
+    jitter: Option<f64>,
+    /// Consecutive failures before giving up for a while
+    breaker_threshold: Option<u32>,
+    /// Seconds to give up for once the threshold is reached
+    breaker_reset: Option<u64>,
+}
+
+fn deserialize_multiplier<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<f64>, D::Error> {
+    use serde::de::{Deserialize, Error};
+
+    match Option::<f64>::deserialize(deserializer)? {
+        Some(m) if m.is_nan() || m < 1.0 => Err(D::Error::custom(format!(
+            "reconnect multiplier must be at least 1, got {}",
+            m
+        ))),
+        m => Ok(m),
+    }
+}
+
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+struct TimeoutConfig {
+    /// Seconds a caller will wait for a response to a request
+    request: Option<u64>,
+    /// Seconds to wait for the server to respond to a command before reconnecting
+    response: Option<u64>,
+    /// Seconds between keepalive commands
+    keepalive_interval: Option<u64>,
+    /// Seconds to wait before reconnecting after a connection failure
+    reconnect_delay: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+struct QueueConfig {
+    /// Maximum number of requests to hold
+    size: Option<usize>,
+    /// Maximum time in seconds to hold a request for
+    max_wait: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
 enum ConfigServerType {
     #[serde(rename = "EPP")]
     #[default]
@@ -76,14 +208,21 @@ enum ConfigServerType {
 
 #[derive(Debug, Deserialize)]
 struct HSMConfigFile {
+    #[serde(deserialize_with = "secret::deserialize")]
     pin: String,
 }
 
+/// Objects to include in a check command when the registry config doesn't say
+const DEFAULT_MAX_CHECK_SIZE: usize = 5;
+
 /// Route requests to the correct EPP client for the authoritative registry
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Router {
     pub id_to_client: HashMap<String, client::RequestSender>,
     zone_to_client: HashMap<String, (client::RequestSender, String)>,
+    id_to_status: HashMap<String, client::status::StatusHandle>,
+    id_to_max_check_size: HashMap<String, usize>,
+    id_to_fee_policy: HashMap<String, client::fee::FeePolicy>,
 }
 
 impl Router {
@@ -94,20 +233,68 @@ impl Router {
     }
 
     pub fn add_client(&mut self, epp_client: Box<dyn client::Client>, config: ConfigFile) {
+        let status = epp_client.status();
         let epp_client_sender = epp_client.start().0;
 
         for zone in &config.zones {
             self.zone_to_client
                 .insert(zone.clone(), (epp_client_sender.clone(), config.id.clone()));
         }
+        self.id_to_status.insert(config.id.clone(), status);
+        match config.max_check_size {
+            Some(s) => self.id_to_max_check_size.insert(config.id.clone(), s.max(1)),
+            None => self.id_to_max_check_size.remove(&config.id),
+        };
+        match &config.fee_policy {
+            Some(p) => self.id_to_fee_policy.insert(config.id.clone(), p.into()),
+            None => self.id_to_fee_policy.remove(&config.id),
+        };
         self.id_to_client.insert(config.id, epp_client_sender);
     }
 
+    /// Removes a client and all zones routed to it, returning its sender so it can be logged out
+    pub fn remove_client(&mut self, id: &str) -> Option<client::RequestSender> {
+        self.zone_to_client.retain(|_, (_, zone_id)| zone_id != id);
+        self.id_to_status.remove(id);
+        self.id_to_max_check_size.remove(id);
+        self.id_to_fee_policy.remove(id);
+        self.id_to_client.remove(id)
+    }
+
     /// Fetches client sender by registry ID
     pub fn client_by_id(&self, id: &str) -> Option<client::RequestSender> {
         self.id_to_client.get(id).cloned()
     }
 
+    /// Most objects that can be checked in one command on a registry
+    pub fn max_check_size(&self, id: &str) -> usize {
+        self.id_to_max_check_size
+            .get(id)
+            .copied()
+            .unwrap_or(DEFAULT_MAX_CHECK_SIZE)
+    }
+
+    /// Fee policy for billable commands on a registry, if it has one
+    pub fn fee_policy(&self, id: &str) -> Option<client::fee::FeePolicy> {
+        self.id_to_fee_policy.get(id).copied()
+    }
+
+    /// Fetches the current session status of a client by registry ID
+    pub fn status_by_id(&self, id: &str) -> Option<client::status::ClientStatus> {
+        self.id_to_status.get(id).map(|s| s.get())
+    }
+
+    /// Lists the session status of every client, ordered by registry ID
+    pub fn statuses(&self) -> Vec<(String, client::status::ClientStatus)> {
+        let mut statuses = self
+            .id_to_status
+            .iter()
+            .map(|(id, s)| (id.clone(), s.get()))
+            .collect::<Vec<_>>();
+        statuses.sort_by(|a, b| a.0.cmp(&b.0));
+        statuses
+    }
+
     /// Searches for client sender by a domain the client is authoritative for
     pub fn client_by_domain(&self, domain: &str) -> Option<(client::RequestSender, String)> {
         let mut domain_parts = domain.split('.').collect::<Vec<_>>();
@@ -285,10 +472,76 @@ pub async fn create_client<M: metrics::Metrics<Subordinate = M> + 'static>(
     metrics_registry: M,
     keepalive: bool,
 ) -> Box<dyn client::Client> {
+    let log_storage = match &config.redact {
+        Some(rules) => log_storage.with_redactor(redact::Redactor::new(rules.clone())),
+        None => log_storage,
+    };
+    let password_rotation: Option<client::login_sec::PasswordRotation> =
+        config.password_rotation.as_ref().map(Into::into);
+    let sessions = config.sessions.unwrap_or(1).max(1);
+    if sessions == 1 {
+        return create_session(
+            log_storage,
+            config,
+            pkcs11_engine,
+            metrics_registry,
+            keepalive,
+            0,
+            password_rotation,
+        )
+        .await;
+    }
+
+    let mut clients = Vec::with_capacity(sessions);
+    for session in 0..sessions {
+        let session_metrics = if session == 0 {
+            metrics_registry.clone()
+        } else {
+            metrics_registry.subordinate(&format!("session{}", session))
+        };
+        clients.push(
+            create_session(
+                log_storage.clone(),
+                config,
+                pkcs11_engine,
+                session_metrics,
+                keepalive,
+                session,
+                password_rotation.as_ref().map(|r| match session {
+                    0 => r.clone(),
+                    _ => r.follower(),
+                }),
+            )
+            .await,
+        );
+    }
+    let timeout = config
+        .timeouts
+        .request
+        .map(std::time::Duration::from_secs)
+        .unwrap_or_else(|| client::Timeouts::default().request);
+    Box::new(client::pool::ClientPool::new(clients, timeout))
+}
+
+async fn create_session<M: metrics::Metrics<Subordinate = M> + 'static>(
+    log_storage: StorageScoped,
+    config: &ConfigFile,
+    pkcs11_engine: &Option<P11Engine>,
+    metrics_registry: M,
+    keepalive: bool,
+    session: usize,
+    password_rotation: Option<client::login_sec::PasswordRotation>,
+) -> Box<dyn client::Client> {
+    // Only the first session changes the password, the rest log in once it has
+    let (password, new_password) = match (session, config.new_password.as_deref()) {
+        (0, new_password) => (config.password.as_str(), new_password),
+        (_, Some(new_password)) => (new_password, None),
+        (_, None) => (config.password.as_str(), None),
+    };
     let client_conf = client::ClientConf {
         host: &config.server,
         tag: &config.tag,
-        password: &config.password,
+        password,
         source_address: config.source_address.as_ref(),
         log_storage,
         metrics_registry,
@@ -306,13 +559,52 @@ pub async fn create_client<M: metrics::Metrics<Subordinate = M> + 'static>(
         },
         danger_accept_invalid_certs: config.danger_accept_invalid_certs.unwrap_or(false),
         danger_accept_invalid_hostname: config.danger_accept_invalid_hostnames.unwrap_or(false),
-        new_password: config.new_password.as_deref(),
+        new_password,
         pipelining: config.pipelining,
         errata: config.errata.clone(),
         nominet_dac: config.nominet_dac.as_ref().map(|d| client::NominetDACConf {
             real_time: &d.real_time,
             time_delay: &d.time_delay,
         }),
+        queue: {
+            let default = client::queue::QueueConf::default();
+            client::queue::QueueConf {
+                size: config.queue.size.unwrap_or(default.size),
+                max_wait: config
+                    .queue
+                    .max_wait
+                    .map(std::time::Duration::from_secs)
+                    .unwrap_or(default.max_wait),
+            }
+        },
+        timeouts: {
+            let default = client::Timeouts::default();
+            let secs = |v: Option<u64>, d| v.map(std::time::Duration::from_secs).unwrap_or(d);
+            client::Timeouts {
+                request: secs(config.timeouts.request, default.request),
+                response: secs(config.timeouts.response, default.response),
+                keepalive_interval: secs(
+                    config.timeouts.keepalive_interval,
+                    default.keepalive_interval,
+                ),
+                reconnect_delay: secs(config.timeouts.reconnect_delay, default.reconnect_delay),
+            }
+        },
+        reconnect: {
+            let default = client::reconnect::ReconnectConf::default();
+            let secs = |v: Option<u64>, d| v.map(std::time::Duration::from_secs).unwrap_or(d);
+            client::reconnect::ReconnectConf {
+                max_delay: secs(config.reconnect.max_delay, default.max_delay),
+                multiplier: config.reconnect.multiplier.unwrap_or(default.multiplier),
+                jitter: config.reconnect.jitter.unwrap_or(default.jitter),
+                breaker_threshold: config
+                    .reconnect
+                    .breaker_threshold
+                    .unwrap_or(default.breaker_threshold),
+                breaker_reset: secs(config.reconnect.breaker_reset, default.breaker_reset),
+            }
+        },
+        password_rotation,
     };
     match match config.server_type {
         ConfigServerType::Epp => client::epp::EPPClient::new(client_conf, pkcs11_engine.clone())
@@ -455,6 +747,7 @@ impl Storage for S3Storage {
 pub struct StorageScoped {
     storage: std::sync::Arc<Box<dyn Storage>>,
     tag: String,
+    redactor: std::sync::Arc<redact::Redactor>,
 }
 
 impl StorageScoped {
@@ -462,6 +755,7 @@ impl StorageScoped {
         Self {
             storage: std::sync::Arc::new(storage),
             tag: tag.to_string(),
+            redactor: Default::default(),
         }
     }
 
@@ -469,14 +763,80 @@ impl StorageScoped {
         Self {
             storage: storage.into(),
             tag: tag.to_string(),
+            redactor: Default::default(),
         }
     }
 
+    /// Replaces the default rules used to redact messages before they are logged
+    pub fn with_redactor(mut self, redactor: redact::Redactor) -> Self {
+        self.redactor = std::sync::Arc::new(redactor);
+        self
+    }
+
+    /// Writes a message to the log, naming it after its client transaction ID if it has one
     async fn write_msg_log(
         &self,
         msg: &str,
         msg_type: &str,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        self.storage.write_msg_log(&self.tag, msg, msg_type).await
+        let msg_type = match message_client_transaction_id(msg) {
+            Some(id) => format!("{}_{}", log_file_name_part(id), msg_type),
+            None => msg_type.to_string(),
+        };
+        let msg = self.redactor.redact(msg)?;
+        self.storage.write_msg_log(&self.tag, &msg, &msg_type).await
+    }
+}
+
+/// Finds the contents of the `clTRID` element of an EPP or TMCH message
+fn message_client_transaction_id(msg: &str) -> Option<&str> {
+    let start = msg.find("clTRID>")? + "clTRID>".len();
+    let id = msg[start..].split('<').next()?.trim();
+    if id.is_empty() {
+        None
+    } else {
+        Some(id)
+    }
+}
+
+/// Replaces anything that isn't safe in a file or object name
+fn log_file_name_part(id: &str) -> String {
+    id.chars()
+        .take(client::router::MAX_CLIENT_TRANSACTION_ID_LENGTH)
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod lib_tests {
+    #[test]
+    fn reconnect_multiplier() {
+        let conf: super::ReconnectConfig = serde_json::from_str(r#"{"multiplier": 1.5}"#).unwrap();
+        assert_eq!(conf.multiplier, Some(1.5));
+        let conf: super::ReconnectConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(conf.multiplier, None);
+        assert!(serde_json::from_str::<super::ReconnectConfig>(r#"{"multiplier": 0.5}"#).is_err());
+        assert!(serde_json::from_str::<super::ReconnectConfig>(r#"{"multiplier": -2}"#).is_err());
+    }
+
+    #[test]
+    fn message_client_transaction_id() {
+        let msg = r#"<epp:epp xmlns:epp="urn:ietf:params:xml:ns:epp-1.0"><epp:command><epp:logout/><epp:clTRID>order/1234 x</epp:clTRID></epp:command></epp:epp>"#;
+        let id = super::message_client_transaction_id(msg).unwrap();
+        assert_eq!(id, "order/1234 x");
+        assert_eq!(super::log_file_name_part(id), "order_1234_x");
+
+        let msg = r#"<epp><response><trID><clTRID>abc-123</clTRID><svTRID>54321</svTRID></trID></response></epp>"#;
+        assert_eq!(super::message_client_transaction_id(msg), Some("abc-123"));
+        assert_eq!(
+            super::message_client_transaction_id("<epp><hello/></epp>"),
+            None
+        );
     }
 }
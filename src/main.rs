@@ -16,7 +16,66 @@
 //! new EPP password if it is to be changed on login, zones is a list of DNS
 //! zones said server is responsible for such as `ch`, `co.uk`, and `org.uk`, client_cert
 //! is an optional TLS certificated bundle in PKCS12 format, pipelining defines support for multiple
-//! in flight commands, errata defines server errata.
+//! in flight commands, errata defines server errata, and queue optionally sets the `size` and
+//! `max_wait` (in seconds) of the queue that holds requests while the server is reconnecting.
+//!
+//! Requests are sent in the interactive lane unless the gRPC call sets the `x-epp-priority`
+//! metadata to `batch`; interactive requests are always sent to the registry first.
+//!
+//! Commands are sent with a random client transaction ID (clTRID) unless the gRPC call sets the
+//! `x-epp-cltrid` metadata to the ID to use, or `x-epp-cltrid-prefix` to a prefix that a random
+//! UUID is added to. IDs must be 3 to 64 printable ASCII characters without spaces, so a prefix
+//! can be at most 28; calls with an invalid ID are rejected with `INVALID_ARGUMENT`. Calls that send
+//! more than one command use the exact ID for the first, and add `-2`, `-3`... for the rest,
+//! shortening the ID if needed to stay within 64 characters. A command whose exact ID is still
+//! awaiting a response from an earlier command is rejected with `INVALID_ARGUMENT`. The clTRID
+//! is returned in the transaction ID of the reply, and is part of the name of each message log
+//! entry, as `{time}_{clTRID}_{send|recv}.xml`.
+//!
+//! Error results from a registry are returned with the gRPC code closest to the EPP result code,
+//! such as `ALREADY_EXISTS` for 2302 or `PERMISSION_DENIED` for 2201. The `google.rpc.Status`
+//! details carry an `ErrorInfo` with the `result_code` in its metadata, a `RequestInfo` with the
+//! client and server transaction IDs, and a `BadRequest` with any `extValue` reasons.
+//!
+//! timeouts optionally overrides, in seconds, how long a `request` waits for a response (60),
+//! how long to wait for the server to `response` before reconnecting (15), the
+//! `keepalive_interval` (120), and the `reconnect_delay` (5). The request timeout can also be set
+//! per call with the gRPC deadline, or the `x-epp-timeout` metadata in seconds.
+//!
+//! reconnect optionally sets how failed connections are retried. The delay starts at the
+//! `reconnect_delay` and is multiplied by `multiplier` (2, at least 1) after each failure up to
+//! `max_delay` (300 seconds), less a random `jitter` fraction (0.5). After `breaker_threshold` (10)
+//! consecutive failures the circuit breaker opens; requests are rejected straight away and no
+//! connection is attempted for `breaker_reset` (600 seconds).
+//!
+//! sessions optionally sets how many sessions (1) to hold open to the server at once. Commands are
+//! spread over the connected sessions, and poll commands are always sent on the first session.
+//! When a new password is set it is changed by the first session before the others log in.
+//!
+//! password_rotation optionally changes the password automatically when the server warns at login
+//! that it is expiring (RFC 8807 login security). The new password is `length` (16) random
+//! characters, set at the next login once expiry is within `rotate_before_days` (14). It is handed
+//! to the `sink` as pending before it is sent, and the change is skipped if that fails, then again
+//! as current once the server accepts it. The sink is either `{"file": "path"}`, which is
+//! overwritten, with the pending password kept in `path.pending`, or
+//! `{"command": ["program", "args"...]}`, which is run with the password on its standard input and
+//! `EPP_SECRET_STATE` set to `pending` or `current`.
+//! Login security events are also counted in the metrics and can be fetched from the admin service.
+//!
+//! password and new_password, and the pin in the HSM config, can be given as a reference instead
+//! of a string; `{"file": "path"}` reads a file such as a mounted Kubernetes secret,
+//! `{"env": "NAME"}` reads an environment variable, and `{"command": ["program", "args"...]}` runs
+//! a secret provider and uses its output. References are resolved again when the config is
+//! reloaded. Pointing the password at the file a password rotation `sink` writes keeps a rotated
+//! password across restarts.
+//!
+//! Passwords and auth info are removed from the message log. redact optionally replaces the
+//! elements removed with a list of `element` names, each with an optional `namespace`; the
+//! default removes the contents of every `pw`, `newPW`, and `authInfo` element.
+//!
+//! Sending the process `SIGHUP` re-reads the config folder; registries that were added or changed
+//! are connected, and those that were removed or changed are logged out once their in flight
+//! commands finish. Registries whose config is unchanged keep their existing session.
 //!
 //! Supported errata are:
 //! * `traficom`
@@ -38,7 +97,19 @@
 //!  "client_cert": "priv/as207960-registrar.pfx",
 //!  "root_certs": ["root/uniregistry.pem"],
 //!  "pipelining": true,
-//!  "errata": "traficom"
+//!  "errata": "traficom",
+//!  "queue": {
+//!    "size": 64,
+//!    "max_wait": 30
+//!  },
+//!  "timeouts": {
+//!    "request": 120,
+//!    "response": 60
+//!  },
+//!  "reconnect": {
+//!    "max_delay": 120,
+//!    "breaker_threshold": 20
+//!  }
 //! }
 //! ```
 
@@ -212,48 +283,11 @@ async fn main() {
         epp_proxy::setup_pkcs11_engine(matches.get_one::<String>("hsm_conf").map(|s| s.as_str()))
             .await;
 
-    let conf_dir_path = matches.get_one::<String>("conf").unwrap();
-    let mut configs = vec![];
-    let conf_dir = match std::fs::read_dir(conf_dir_path) {
-        Ok(r) => r,
-        Err(e) => {
-            error!("Can't list config directory: {}", e);
-            return;
-        }
+    let conf_dir_path = matches.get_one::<String>("conf").unwrap().to_owned();
+    let configs = match read_configs(&conf_dir_path) {
+        Some(c) => c,
+        None => return,
     };
-    for conf_file in conf_dir {
-        let conf_file = conf_file.unwrap();
-        let conf_file_type = conf_file.file_type().unwrap();
-        if !conf_file_type.is_dir() {
-            let conf_file_path = conf_file.path();
-            if conf_file_path.extension().unwrap_or_default() != "json" {
-                continue;
-            }
-            let file = match std::fs::File::open(conf_file_path) {
-                Ok(f) => f,
-                Err(e) => {
-                    error!(
-                        "Can't open config file {}: {}",
-                        conf_file.path().to_string_lossy(),
-                        e
-                    );
-                    return;
-                }
-            };
-            let conf: epp_proxy::ConfigFile = match serde_json::from_reader(file) {
-                Ok(c) => c,
-                Err(e) => {
-                    error!(
-                        "Can't parse config file {}: {}",
-                        conf_file.path().to_string_lossy(),
-                        e
-                    );
-                    return;
-                }
-            };
-            configs.push(conf);
-        }
-    }
 
     let storage: std::sync::Arc<Box<dyn epp_proxy::Storage>> =
         match matches.get_one::<String>("log_driver").unwrap().as_str() {
@@ -295,25 +329,18 @@ async fn main() {
     let mut clients = vec![];
     let metrics =
         std::sync::Arc::new(epp_proxy::metrics::PrometheusMetrics::new().expect("create metrics registry"));
-    for config in configs {
-        let scoped_storage = epp_proxy::StorageScoped::new_arc(storage.clone(), &config.id);
-        let metrics_registry = metrics.new_scope(config.id.clone());
-        let epp_client = epp_proxy::create_client(
-            scoped_storage,
-            &config,
-            &pkcs11_engine,
-            metrics_registry,
-            true,
-        )
-        .await;
-        clients.push((epp_client, config))
+    for config in &configs {
+        let epp_client = start_client(&storage, config, &pkcs11_engine, &metrics).await;
+        clients.push((epp_client, config.clone()))
     }
 
     for (client, config) in clients {
         router.add_client(client, config)
     }
 
-    let handles: Vec<_> = router.id_to_client.values().cloned().collect();
+    let router = std::sync::Arc::new(std::sync::RwLock::new(router));
+
+    let term_router = router.clone();
     tokio::spawn(async move {
         use futures::future::FutureExt;
         let mut term_stream =
@@ -328,18 +355,78 @@ async fn main() {
             _ = term_fut => {}
             _ = int_fut => {}
         }
-        let mut futs = vec![];
-        for c in handles {
-            futs.push(epp_proxy::client::logout(c));
-        }
-        for res in futures::future::join_all(futs).await {
-            if let Err(err) = res {
-                warn!("Failed to logout from server: {:?}", err);
+        let handles: Vec<_> = term_router
+            .read()
+            .unwrap()
+            .id_to_client
+            .values()
+            .cloned()
+            .collect();
+        logout_clients(handles).await;
+        std::process::exit(0);
+    });
+
+    let reload_router = router.clone();
+    tokio::spawn(async move {
+        let mut hup_stream =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()).unwrap();
+        let mut current_configs = configs
+            .into_iter()
+            .map(|c| (c.id.clone(), c))
+            .collect::<std::collections::HashMap<_, _>>();
+
+        while hup_stream.recv().await.is_some() {
+            info!("Reloading config files from {}", conf_dir_path);
+            // Secret references can run commands, so the configs are read off the runtime
+            let read_path = conf_dir_path.clone();
+            let new_configs = match tokio::task::spawn_blocking(move || read_configs(&read_path))
+                .await
+                .ok()
+                .flatten()
+            {
+                Some(c) => c
+                    .into_iter()
+                    .map(|c| (c.id.clone(), c))
+                    .collect::<std::collections::HashMap<_, _>>(),
+                None => {
+                    warn!("Config reload failed, keeping existing configuration");
+                    continue;
+                }
+            };
+
+            // Build the new routing table off to the side so that requests keep being served
+            // by the old one until the swap
+            let mut new_router = reload_router.read().unwrap().clone();
+            let mut old_clients = vec![];
+            for (id, config) in &current_configs {
+                if new_configs.get(id) != Some(config) {
+                    if let Some(c) = new_router.remove_client(id) {
+                        info!("Removing client {}", id);
+                        old_clients.push(c);
+                    }
+                }
+            }
+            for (id, config) in &new_configs {
+                if current_configs.get(id) != Some(config) {
+                    info!("Starting client {}", id);
+                    let epp_client = start_client(&storage, config, &pkcs11_engine, &metrics).await;
+                    new_router.add_client(epp_client, config.clone());
+                }
             }
+
+            *reload_router.write().unwrap() = new_router;
+            current_configs = new_configs;
+            info!("Config reload complete");
+
+            // Logout is queued behind any commands already sent to the old clients, so it's left
+            // to run on its own rather than holding up the next reload
+            tokio::spawn(logout_clients(old_clients));
         }
-        std::process::exit(0);
     });
 
+    let admin_server = epp_proxy::grpc::EPPProxyAdmin {
+        client_router: router.clone(),
+    };
     let server = epp_proxy::grpc::EPPProxy {
         client_router: router,
     };
@@ -349,9 +436,18 @@ async fn main() {
         .unwrap();
 
     let svc = epp_proxy::grpc::epp_proto::epp_proxy_server::EppProxyServer::new(server);
+    let auth = std::sync::Arc::new(auth);
     let w_svc = AuthService {
         inner: svc,
-        auth: std::sync::Arc::new(auth),
+        auth: auth.clone(),
+    };
+    let admin_svc =
+        epp_proxy::grpc::epp_proto::admin::epp_proxy_admin_server::EppProxyAdminServer::new(
+            admin_server,
+        );
+    let w_admin_svc = AuthService {
+        inner: admin_svc,
+        auth,
     };
 
     let reflection_svc = tonic_reflection::server::Builder::configure()
@@ -372,11 +468,87 @@ async fn main() {
         .unwrap()
         .add_service(reflection_svc)
         .add_service(w_svc)
+        .add_service(w_admin_svc)
         .serve(addr)
         .await
         .unwrap();
 }
 
+fn read_configs(conf_dir_path: &str) -> Option<Vec<epp_proxy::ConfigFile>> {
+    let mut configs = vec![];
+    let conf_dir = match std::fs::read_dir(conf_dir_path) {
+        Ok(r) => r,
+        Err(e) => {
+            error!("Can't list config directory: {}", e);
+            return None;
+        }
+    };
+    for conf_file in conf_dir {
+        let conf_file = conf_file.unwrap();
+        let conf_file_type = conf_file.file_type().unwrap();
+        if !conf_file_type.is_dir() {
+            let conf_file_path = conf_file.path();
+            if conf_file_path.extension().unwrap_or_default() != "json" {
+                continue;
+            }
+            let file = match std::fs::File::open(conf_file_path) {
+                Ok(f) => f,
+                Err(e) => {
+                    error!(
+                        "Can't open config file {}: {}",
+                        conf_file.path().to_string_lossy(),
+                        e
+                    );
+                    return None;
+                }
+            };
+            let conf: epp_proxy::ConfigFile = match serde_json::from_reader(file) {
+                Ok(c) => c,
+                Err(e) => {
+                    error!(
+                        "Can't parse config file {}: {}",
+                        conf_file.path().to_string_lossy(),
+                        e
+                    );
+                    return None;
+                }
+            };
+            configs.push(conf);
+        }
+    }
+    Some(configs)
+}
+
+async fn start_client(
+    storage: &std::sync::Arc<Box<dyn epp_proxy::Storage>>,
+    config: &epp_proxy::ConfigFile,
+    pkcs11_engine: &Option<epp_proxy::P11Engine>,
+    metrics: &std::sync::Arc<epp_proxy::metrics::PrometheusMetrics>,
+) -> Box<dyn epp_proxy::client::Client> {
+    let scoped_storage = epp_proxy::StorageScoped::new_arc(storage.clone(), &config.id);
+    let metrics_registry = metrics.new_scope(config.id.clone());
+    epp_proxy::create_client(
+        scoped_storage,
+        config,
+        pkcs11_engine,
+        metrics_registry,
+        true,
+    )
+    .await
+}
+
+async fn logout_clients(handles: Vec<epp_proxy::client::RequestSender>) {
+    let mut futs = vec![];
+    for c in handles {
+        futs.push(epp_proxy::client::logout(c));
+    }
+    for res in futures::future::join_all(futs).await {
+        if let Err(err) = res {
+            warn!("Failed to logout from server: {:?}", err);
+        }
+    }
+}
+
 async fn metrics_handler() -> Result<impl warp::Reply, warp::Rejection> {
     use prometheus::Encoder;
 
@@ -433,6 +605,88 @@ impl Auth for StaticAuth {
     }
 }
 
+fn request_priority(headers: &http::HeaderMap) -> epp_proxy::client::queue::Priority {
+    match headers.get("x-epp-priority").and_then(|p| p.to_str().ok()) {
+        Some("batch") => epp_proxy::client::queue::Priority::Batch,
+        _ => epp_proxy::client::queue::Priority::Interactive,
+    }
+}
+
+/// Per-call timeout override, from `x-epp-timeout` in seconds or the gRPC deadline
+fn request_timeout(headers: &http::HeaderMap) -> Option<std::time::Duration> {
+    if let Some(t) = headers
+        .get("x-epp-timeout")
+        .and_then(|t| t.to_str().ok())
+        .and_then(|t| t.parse::<u64>().ok())
+    {
+        return Some(std::time::Duration::from_secs(t));
+    }
+
+    let grpc_timeout = headers.get("grpc-timeout")?.to_str().ok()?;
+    if grpc_timeout.is_empty() {
+        return None;
+    }
+    let (value, unit) = grpc_timeout.split_at(grpc_timeout.len() - 1);
+    // The gRPC spec allows at most 8 digits, which keeps the conversions below from overflowing
+    if value.is_empty() || value.len() > 8 || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let value = value.parse::<u64>().ok()?;
+    match unit {
+        "H" => Some(std::time::Duration::from_secs(value * 60 * 60)),
+        "M" => Some(std::time::Duration::from_secs(value * 60)),
+        "S" => Some(std::time::Duration::from_secs(value)),
+        "m" => Some(std::time::Duration::from_millis(value)),
+        "u" => Some(std::time::Duration::from_micros(value)),
+        "n" => Some(std::time::Duration::from_nanos(value)),
+        _ => None,
+    }
+}
+
+/// Client transaction ID for the call, from `x-epp-cltrid` or `x-epp-cltrid-prefix`
+fn request_client_transaction_id(
+    headers: &http::HeaderMap,
+) -> Result<Option<epp_proxy::client::router::ClientTransactionID>, String> {
+    use epp_proxy::client::router::ClientTransactionID;
+
+    if let Some(id) = headers.get("x-epp-cltrid") {
+        let id = id
+            .to_str()
+            .map_err(|_| "x-epp-cltrid must be ASCII".to_string())?;
+        return ClientTransactionID::exact(id).map(Some);
+    }
+    if let Some(prefix) = headers.get("x-epp-cltrid-prefix") {
+        let prefix = prefix
+            .to_str()
+            .map_err(|_| "x-epp-cltrid-prefix must be ASCII".to_string())?;
+        return ClientTransactionID::prefix(prefix).map(Some);
+    }
+    Ok(None)
+}
+
+/// Runs a call with the per-call priority, timeout, and client transaction ID
+async fn with_call_settings<F: std::future::Future>(
+    headers: &http::HeaderMap,
+    client_transaction_id: Option<epp_proxy::client::router::ClientTransactionID>,
+    call: F,
+) -> F::Output {
+    let priority = request_priority(headers);
+    let timeout = request_timeout(headers);
+    let call = async move {
+        match timeout {
+            Some(t) => epp_proxy::client::with_timeout(t, call).await,
+            None => call.await,
+        }
+    };
+    let call = async move {
+        match client_transaction_id {
+            Some(i) => epp_proxy::client::queue::with_client_transaction_id(i, call).await,
+            None => call.await,
+        }
+    };
+    epp_proxy::client::queue::with_priority(priority, call).await
+}
+
 #[derive(Clone)]
 struct AuthService<T> {
     inner: T,
@@ -469,17 +723,25 @@ where
                         let auth_token_str = t.trim();
                         if let Some(auth_token) = auth_token_str.strip_prefix("Bearer ") {
                             if auth.auth(auth_token).await {
-                                Ok(inner.call(req).await?)
+                                match request_client_transaction_id(&headers) {
+                                    Ok(client_transaction_id) => Ok(with_call_settings(
+                                        &headers,
+                                        client_transaction_id,
+                                        inner.call(req),
+                                    )
+                                    .await?),
+                                    Err(e) => Err(tonic::Status::invalid_argument(e)),
+                                }
                             } else {
-                                Err("Invalid auth token")
+                                Err(tonic::Status::unauthenticated("Invalid auth token"))
                             }
                         } else {
-                            Err("Invalid auth token")
+                            Err(tonic::Status::unauthenticated("Invalid auth token"))
                         }
                     }
-                    Err(_) => Err("Invalid auth token"),
+                    Err(_) => Err(tonic::Status::unauthenticated("Invalid auth token")),
                 },
-                _ => Err("No valid auth token"),
+                _ => Err(tonic::Status::unauthenticated("No valid auth token")),
             };
 
             match res {
@@ -498,8 +760,8 @@ where
 
                     parts
                         .headers
-                        .insert("grpc-status", http::HeaderValue::from_static("16"));
-                    if let Ok(v) = http::HeaderValue::from_str(status) {
+                        .insert("grpc-status", http::HeaderValue::from(status.code() as i32));
+                    if let Ok(v) = http::HeaderValue::from_str(status.message()) {
                         parts.headers.insert("grpc-message", v);
                     }
 
@@ -516,3 +778,34 @@ where
 {
     const NAME: &'static str = T::NAME;
 }
+
+#[cfg(test)]
+mod main_tests {
+    use super::*;
+
+    fn timeout(header: &str, value: &str) -> Option<std::time::Duration> {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(header, value.parse().unwrap());
+        request_timeout(&headers)
+    }
+
+    #[test]
+    fn grpc_timeout() {
+        assert_eq!(
+            timeout("grpc-timeout", "99999999H"),
+            Some(std::time::Duration::from_secs(99999999 * 60 * 60))
+        );
+        assert_eq!(
+            timeout("grpc-timeout", "1500m"),
+            Some(std::time::Duration::from_millis(1500))
+        );
+        assert_eq!(timeout("grpc-timeout", "184467440737095516H"), None);
+        assert_eq!(timeout("grpc-timeout", "+5S"), None);
+        assert_eq!(timeout("grpc-timeout", "S"), None);
+        assert_eq!(timeout("grpc-timeout", "5x"), None);
+        assert_eq!(
+            timeout("x-epp-timeout", "30"),
+            Some(std::time::Duration::from_secs(30))
+        );
+    }
+}
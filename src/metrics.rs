@@ -5,6 +5,11 @@ pub struct PrometheusMetrics {
     response_count: prometheus::IntCounterVec,
     poll_result_count: prometheus::IntCounterVec,
     response_time: prometheus::HistogramVec,
+    circuit_breaker_state: prometheus::IntGaugeVec,
+    reconnect_count: prometheus::IntCounterVec,
+    login_security_event_count: prometheus::IntCounterVec,
+    login_security_expiry: prometheus::IntGaugeVec,
+    password_rotation_count: prometheus::IntCounterVec,
 }
 
 impl PrometheusMetrics {
@@ -35,6 +40,31 @@ impl PrometheusMetrics {
                 "Time the EPP server took to respond to commands",
                 &["id", "command"]
             )?,
+            circuit_breaker_state: prometheus::register_int_gauge_vec!(
+                "circuit_breaker_state",
+                "State of the reconnect circuit breaker (0 closed, 1 open, 2 half open)",
+                &["id"]
+            )?,
+            reconnect_count: prometheus::register_int_counter_vec!(
+                "reconnect_count",
+                "Number of failed connection attempts to the EPP server",
+                &["id"]
+            )?,
+            login_security_event_count: prometheus::register_int_counter_vec!(
+                "login_security_event_count",
+                "Number and type of login security events received from the EPP server",
+                &["id", "type", "level"]
+            )?,
+            login_security_expiry: prometheus::register_int_gauge_vec!(
+                "login_security_expiry",
+                "Unix time the EPP server says the password or certificate expires",
+                &["id", "type"]
+            )?,
+            password_rotation_count: prometheus::register_int_counter_vec!(
+                "password_rotation_count",
+                "Number of automatic password changes, and whether the new password was stored",
+                &["id", "result"]
+            )?,
         })
     }
 
@@ -54,6 +84,10 @@ pub trait Metrics: Clone + Send + Sync {
     fn response_received(&self);
     fn poll_received(&self, command: &str);
     fn record_response_time(&self, command: &str) -> Option<prometheus::HistogramTimer>;
+    fn circuit_breaker(&self, state: crate::client::reconnect::BreakerState);
+    fn reconnect_attempt(&self);
+    fn login_security_event(&self, event: &crate::client::login_sec::LoginSecurityEvent);
+    fn password_rotated(&self, stored: bool);
     fn subordinate(&self, extra: &str) -> Self::Subordinate;
 }
 
@@ -91,6 +125,42 @@ impl Metrics for ScopedMetrics {
             .start_timer())
     }
 
+    fn circuit_breaker(&self, state: crate::client::reconnect::BreakerState) {
+        self.metrics
+            .circuit_breaker_state
+            .with_label_values(&[&self.id])
+            .set(match state {
+                crate::client::reconnect::BreakerState::Closed => 0,
+                crate::client::reconnect::BreakerState::Open => 1,
+                crate::client::reconnect::BreakerState::HalfOpen => 2,
+            });
+    }
+
+    fn reconnect_attempt(&self) {
+        self.metrics.reconnect_count.with_label_values(&[&self.id]).inc();
+    }
+
+    fn login_security_event(&self, event: &crate::client::login_sec::LoginSecurityEvent) {
+        let event_type = event.event_type.as_str();
+        self.metrics
+            .login_security_event_count
+            .with_label_values(&[&self.id, event_type, event.level.as_str()])
+            .inc();
+        if let Some(expiration_date) = event.expiration_date {
+            self.metrics
+                .login_security_expiry
+                .with_label_values(&[&self.id, event_type])
+                .set(expiration_date.timestamp());
+        }
+    }
+
+    fn password_rotated(&self, stored: bool) {
+        self.metrics
+            .password_rotation_count
+            .with_label_values(&[&self.id, if stored { "stored" } else { "store_failed" }])
+            .inc();
+    }
+
     fn subordinate(&self, extra: &str) -> Self {
         ScopedMetrics {
             metrics: self.metrics.clone(),
@@ -112,6 +182,10 @@ impl Metrics for DummyMetrics {
     fn record_response_time(&self, _command: &str) -> Option<prometheus::HistogramTimer> {
         None
     }
+    fn circuit_breaker(&self, _state: crate::client::reconnect::BreakerState) {}
+    fn reconnect_attempt(&self) {}
+    fn login_security_event(&self, _event: &crate::client::login_sec::LoginSecurityEvent) {}
+    fn password_rotated(&self, _stored: bool) {}
     fn subordinate(&self, _extra: &str) -> Self {
         DummyMetrics::default()
     }
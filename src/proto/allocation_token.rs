@@ -0,0 +1,8 @@
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EPPAllocationToken {
+    #[serde(rename = "$value")]
+    pub token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPAllocationTokenInfo {}
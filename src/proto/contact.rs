@@ -6,6 +6,12 @@ pub struct EPPContactCheck {
     pub id: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct EPPContactBatchCheck {
+    #[serde(rename = "{urn:ietf:params:xml:ns:contact-1.0}contact:id")]
+    pub ids: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct EPPContactCheckData {
     #[serde(rename = "{urn:ietf:params:xml:ns:contact-1.0}cd", default)]
@@ -11,6 +11,12 @@ pub struct EPPDomainCheck {
     pub auth_info: Option<EPPDomainAuthInfo>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct EPPDomainBatchCheck {
+    #[serde(rename = "{urn:ietf:params:xml:ns:domain-1.0}domain:name")]
+    pub names: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct EPPDomainCheckData {
     #[serde(rename = "{urn:ietf:params:xml:ns:domain-1.0}cd", default)]
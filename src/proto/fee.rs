@@ -59,6 +59,8 @@ pub struct EPPFee10Check {
 pub struct EPPFee10CheckCommand {
     #[serde(rename = "$attr:name")]
     pub name: EPPFeeCommandType,
+    #[serde(rename = "$attr:customName", skip_serializing_if = "Option::is_none")]
+    pub custom_name: Option<String>,
     #[serde(rename = "$attr:phase", skip_serializing_if = "Option::is_none")]
     pub phase: Option<String>,
     #[serde(rename = "$attr:subphase", skip_serializing_if = "Option::is_none")]
@@ -236,11 +238,11 @@ pub struct EPPFee011Agreement {
 #[derive(Debug, Serialize)]
 pub struct EPPFee10Agreement {
     #[serde(
-        rename = "{urn:ietf:params:xml:ns:fee-1.0}fee:currency",
+        rename = "{urn:ietf:params:xml:ns:epp:fee-1.0}fee:currency",
         skip_serializing_if = "Option::is_none"
     )]
     pub currency: Option<String>,
-    #[serde(rename = "{urn:ietf:params:xml:ns:fee-1.0}fee:fee")]
+    #[serde(rename = "{urn:ietf:params:xml:ns:epp:fee-1.0}fee:fee")]
     pub fee: Vec<EPPFee10Fee>,
 }
 
@@ -256,6 +258,8 @@ pub enum EPPFeeCommandType {
     Delete,
     #[serde(rename = "restore")]
     Restore,
+    #[serde(rename = "custom")]
+    Custom,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -0,0 +1,17 @@
+#[derive(Debug, Deserialize)]
+pub struct EPPLowCreditData {
+    #[serde(rename = "{http://www.nic.cz/xml/epp/fred-1.5}zone")]
+    pub zone: String,
+    #[serde(rename = "{http://www.nic.cz/xml/epp/fred-1.5}limit")]
+    pub limit: EPPCredit,
+    #[serde(rename = "{http://www.nic.cz/xml/epp/fred-1.5}credit")]
+    pub credit: EPPCredit,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPCredit {
+    #[serde(rename = "{http://www.nic.cz/xml/epp/fred-1.5}zone", default)]
+    pub zone: Option<String>,
+    #[serde(rename = "{http://www.nic.cz/xml/epp/fred-1.5}credit")]
+    pub credit: String,
+}
@@ -6,6 +6,12 @@ pub struct EPPHostCheck {
     pub name: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct EPPHostBatchCheck {
+    #[serde(rename = "{urn:ietf:params:xml:ns:host-1.0}host:name")]
+    pub names: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct EPPHostCheckData {
     #[serde(rename = "{urn:ietf:params:xml:ns:host-1.0}cd", default)]
@@ -0,0 +1,188 @@
+use chrono::prelude::*;
+
+#[derive(Debug, Serialize)]
+pub struct EPPKeySetCheck {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:id")]
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPKeySetCheckData {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}cd", default)]
+    pub data: Vec<EPPKeySetCheckDatum>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPKeySetCheckDatum {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}id")]
+    pub id: EPPKeySetCheckID,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}reason")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPKeySetCheckID {
+    #[serde(rename = "$value")]
+    pub id: String,
+    #[serde(rename = "$attr:avail")]
+    pub available: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPKeySetInfoData {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}id")]
+    pub id: String,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}roid", default)]
+    pub registry_id: Option<String>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}status", default)]
+    pub statuses: Vec<EPPKeySetStatus>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}clID")]
+    pub client_id: String,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}crID")]
+    pub client_created_id: Option<String>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:keyset-1.3}crDate",
+        deserialize_with = "super::deserialize_datetime_opt",
+        default
+    )]
+    pub creation_date: Option<DateTime<Utc>>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}upID")]
+    pub last_updated_client: Option<String>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:keyset-1.3}upDate",
+        deserialize_with = "super::deserialize_datetime_opt",
+        default
+    )]
+    pub last_updated_date: Option<DateTime<Utc>>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:keyset-1.3}trDate",
+        deserialize_with = "super::deserialize_datetime_opt",
+        default
+    )]
+    pub last_transfer_date: Option<DateTime<Utc>>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}authInfo")]
+    pub auth_info: Option<String>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}dnskey", default)]
+    pub keys: Vec<EPPKeySetDNSKey>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}tech", default)]
+    pub tech_contacts: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPKeySetStatus {
+    #[serde(rename = "$attr:s")]
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPKeySetDNSKey {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}flags")]
+    pub flags: u16,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}protocol")]
+    pub protocol: u8,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}alg")]
+    pub algorithm: u8,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}pubKey")]
+    pub public_key: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPKeySetDNSKeyParam {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:flags")]
+    pub flags: u16,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:protocol")]
+    pub protocol: u8,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:alg")]
+    pub algorithm: u8,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:pubKey")]
+    pub public_key: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPKeySetCreate {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:id")]
+    pub id: String,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:dnskey")]
+    pub keys: Vec<EPPKeySetDNSKeyParam>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:tech")]
+    pub tech_contacts: Vec<String>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:authInfo",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub auth_info: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPKeySetCreateData {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}id")]
+    pub id: String,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:keyset-1.3}crDate",
+        deserialize_with = "super::deserialize_datetime_opt",
+        default
+    )]
+    pub creation_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPKeySetUpdate {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:id")]
+    pub id: String,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:add",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub add: Option<EPPKeySetUpdateParams>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:rem",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub remove: Option<EPPKeySetUpdateParams>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:chg",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub change: Option<EPPKeySetUpdateChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPKeySetUpdateParams {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:dnskey")]
+    pub keys: Vec<EPPKeySetDNSKeyParam>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:tech")]
+    pub tech_contacts: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPKeySetUpdateChange {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:authInfo")]
+    pub auth_info: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPKeySetTransfer {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:id")]
+    pub id: String,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:authInfo")]
+    pub auth_info: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPKeySetTransferData {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}id")]
+    pub id: String,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:keyset-1.3}trDate",
+        deserialize_with = "super::deserialize_datetime"
+    )]
+    pub transfer_date: DateTime<Utc>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}clID")]
+    pub client_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPKeySetDeleteData {
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}id")]
+    pub id: String,
+}
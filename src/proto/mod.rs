@@ -4,6 +4,7 @@
 use chrono::prelude::*;
 use std::collections::HashMap;
 
+pub mod allocation_token;
 pub mod centralnic;
 pub mod change_poll;
 pub mod contact;
@@ -12,14 +13,17 @@ pub mod domain;
 pub mod email_forward;
 pub mod eurid;
 pub mod fee;
+pub mod fred;
 pub mod host;
 pub mod isnic;
+pub mod keyset;
 pub mod keysys;
 pub mod launch;
 pub mod login_sec;
 pub mod maintenance;
 pub mod mark;
 pub mod nominet;
+pub mod nsset;
 pub mod personal_registration;
 pub mod qualified_lawyer;
 pub mod rgp;
@@ -254,6 +258,12 @@ pub enum EPPCommandExtensionType {
     KeysysRenew(keysys::Renew),
     #[serde(rename = "{http://www.key-systems.net/epp/keysys-1.0}keysys:trasfer")]
     KeysysTransfer(keysys::Transfer),
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:allocationToken-1.0}allocationToken:allocationToken"
+    )]
+    AllocationToken(allocation_token::EPPAllocationToken),
+    #[serde(rename = "{urn:ietf:params:xml:ns:allocationToken-1.0}allocationToken:info")]
+    AllocationTokenInfo(allocation_token::EPPAllocationTokenInfo),
 }
 
 #[derive(Debug, Serialize)]
@@ -290,6 +300,9 @@ pub struct EPPResponse {
     pub data: Option<EPPResultData>,
     #[serde(rename = "{urn:ietf:params:xml:ns:epp-1.0}trID")]
     pub transaction_id: EPPTransactionIdentifier,
+    /// The message as received from the server
+    #[serde(skip)]
+    pub raw: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -327,37 +340,21 @@ impl EPPResponse {
     pub fn response_msg(&self) -> String {
         let mut output = vec![];
         for r in &self.results {
-            match r.extra_values.as_ref().map(|v| {
+            match r.values.as_ref().map(|v| {
                 v.iter()
-                    .map(|e| format!("({}) {}", e.value, e.reason))
+                    .map(|e| {
+                        e.iter()
+                            .next()
+                            .map(|(k, v)| format!("{}: {}", k, v))
+                            .unwrap_or_default()
+                    })
                     .collect::<Vec<_>>()
             }) {
-                Some(extra) => {
-                    output.push(format!(
-                        "({:?}) {}: {}",
-                        r.code,
-                        r.message,
-                        extra.join(", ")
-                    ));
+                Some(v) => {
+                    output.push(format!("({:?}) {}: {}", r.code, r.message, v.join(", ")));
                 }
                 None => {
-                    match r.values.as_ref().map(|v| {
-                        v.iter()
-                            .map(|e| {
-                                e.iter()
-                                    .next()
-                                    .map(|(k, v)| format!("{}: {}", k, v))
-                                    .unwrap_or_default()
-                            })
-                            .collect::<Vec<_>>()
-                    }) {
-                        Some(v) => {
-                            output.push(format!("({:?}) {}: {}", r.code, r.message, v.join(", ")));
-                        }
-                        None => {
-                            output.push(format!("({:?}) {}", r.code, r.message));
-                        }
-                    }
+                    output.push(format!("({:?}) {}", r.code, r.message));
                 }
             }
         }
@@ -373,8 +370,6 @@ pub struct EPPResult {
     pub message: String,
     #[serde(rename = "{urn:ietf:params:xml:ns:epp-1.0}value")]
     pub values: Option<Vec<HashMap<String, String>>>,
-    #[serde(rename = "{urn:ietf:params:xml:ns:epp-1.0}extValue")]
-    pub extra_values: Option<Vec<EPPResultExtraValue>>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -491,6 +486,48 @@ impl From<u16> for EPPResultCode {
     }
 }
 
+impl From<&EPPResultCode> for u16 {
+    fn from(value: &EPPResultCode) -> u16 {
+        match value {
+            EPPResultCode::Success => 1000,
+            EPPResultCode::SuccessActionPending => 1001,
+            EPPResultCode::SuccessNoMessages => 1300,
+            EPPResultCode::SuccessAckToDequeue => 1301,
+            EPPResultCode::SuccessEndingSession => 1500,
+            EPPResultCode::UnknownCommand => 2000,
+            EPPResultCode::CommandSyntaxError => 2001,
+            EPPResultCode::CommandUseError => 2002,
+            EPPResultCode::RequiredParameterMissing => 2003,
+            EPPResultCode::ParameterValueRangeError => 2004,
+            EPPResultCode::ParameterValueSyntaxError => 2005,
+            EPPResultCode::UnimplementedProtocolVersion => 2100,
+            EPPResultCode::UnimplementedCommand => 2101,
+            EPPResultCode::UnimplementedOption => 2102,
+            EPPResultCode::UnimplementedExtension => 2103,
+            EPPResultCode::BillingFailure => 2104,
+            EPPResultCode::ObjectNotEligibleForRenewal => 2105,
+            EPPResultCode::ObjectNotEligibleForTransfer => 2106,
+            EPPResultCode::AuthenticationError => 2200,
+            EPPResultCode::AuthorizationError => 2201,
+            EPPResultCode::InvalidAuthorization => 2202,
+            EPPResultCode::ObjectPendingTransfer => 2300,
+            EPPResultCode::ObjectNotPendingTransfer => 2301,
+            EPPResultCode::ObjectExists => 2302,
+            EPPResultCode::ObjectDoesNotExist => 2303,
+            EPPResultCode::ObjectStatusProhibitsOperation => 2304,
+            EPPResultCode::ObjectAssociationProhibitsOperation => 2305,
+            EPPResultCode::ParameterValuePolicyError => 2306,
+            EPPResultCode::UnimplementedObjectService => 2307,
+            EPPResultCode::DataManagementPolicyViolation => 2308,
+            EPPResultCode::CommandFailed => 2400,
+            EPPResultCode::CommandFailedServerClosingConnection => 2500,
+            EPPResultCode::AuthenticationServerClosingConnection => 2501,
+            EPPResultCode::SessionLimitExceededServerClosingConnection => 2502,
+            EPPResultCode::Other(o) => *o,
+        }
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for EPPResultCode {
     fn deserialize<D>(deserializer: D) -> Result<EPPResultCode, D::Error>
     where
@@ -551,14 +588,6 @@ impl<'de> serde::Deserialize<'de> for EPPResultCode {
     }
 }
 
-#[derive(Debug, Deserialize)]
-pub struct EPPResultExtraValue {
-    #[serde(rename = "{urn:ietf:params:xml:ns:epp-1.0}value")]
-    pub value: String,
-    #[serde(rename = "{urn:ietf:params:xml:ns:epp-1.0}reason")]
-    pub reason: String,
-}
-
 #[derive(Debug, Deserialize)]
 pub struct EPPMessageQueue {
     #[serde(rename = "$attr:count")]
@@ -575,10 +604,33 @@ pub struct EPPMessageQueue {
     pub message: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug)]
 pub struct EPPResultData {
-    #[serde(rename = "$value")]
     pub value: EPPResultDataValue,
+    /// Any further result data elements, for commands such as Nominet unrenew that return several
+    pub others: Vec<EPPResultDataValue>,
+}
+
+impl<'de> serde::Deserialize<'de> for EPPResultData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Values {
+            #[serde(rename = "$value")]
+            values: Vec<EPPResultDataValue>,
+        }
+
+        let mut values = Values::deserialize(deserializer)?.values.into_iter();
+        match values.next() {
+            Some(value) => Ok(EPPResultData {
+                value,
+                others: values.collect(),
+            }),
+            None => Err(serde::de::Error::custom("empty result data")),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -613,6 +665,30 @@ pub enum EPPResultDataValue {
     EPPHostInfoResult(Box<host::EPPHostInfoData>),
     #[serde(rename = "{urn:ietf:params:xml:ns:host-1.0}creData")]
     EPPHostCreateResult(host::EPPHostCreateData),
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}chkData")]
+    EPPNSSetCheckResult(nsset::EPPNSSetCheckData),
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}infData")]
+    EPPNSSetInfoResult(Box<nsset::EPPNSSetInfoData>),
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}creData")]
+    EPPNSSetCreateResult(nsset::EPPNSSetCreateData),
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}trnData")]
+    EPPNSSetTransferData(nsset::EPPNSSetTransferData),
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}idleDelData")]
+    EPPNSSetDeleteData(nsset::EPPNSSetDeleteData),
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}testData")]
+    EPPNSSetTestData(nsset::EPPNSSetTestData),
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}chkData")]
+    EPPKeySetCheckResult(keyset::EPPKeySetCheckData),
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}infData")]
+    EPPKeySetInfoResult(Box<keyset::EPPKeySetInfoData>),
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}creData")]
+    EPPKeySetCreateResult(keyset::EPPKeySetCreateData),
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}trnData")]
+    EPPKeySetTransferData(keyset::EPPKeySetTransferData),
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}idleDelData")]
+    EPPKeySetDeleteData(keyset::EPPKeySetDeleteData),
+    #[serde(rename = "{http://www.nic.cz/xml/epp/fred-1.5}lowCreditData")]
+    FredLowCreditData(fred::EPPLowCreditData),
     #[serde(rename = "{urn:ietf:params:xml:ns:contact-1.0}chkData")]
     EPPContactCheckResult(contact::EPPContactCheckData),
     #[serde(rename = "{urn:ietf:params:xml:ns:contact-1.0}infData")]
@@ -661,6 +737,10 @@ pub enum EPPResultDataValue {
     NominetHandshakeData(nominet::EPPHandshakeData),
     #[serde(rename = "{http://www.nominet.org.uk/epp/xml/std-release-1.0}releasePending")]
     NominetReleasePending(String),
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/std-list-1.0}listData")]
+    NominetListData(nominet::EPPListData),
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0}infData")]
+    NominetAbuseFeedData(nominet::EPPAbuseFeedData),
     #[serde(rename = "{http://www.eurid.eu/xml/epp/registrarFinance-1.0}infData")]
     EURIDRegistrarFinanceData(eurid::EURIDRegistrarFinanceInfoData),
     #[serde(rename = "{http://www.eurid.eu/xml/epp/registrarHitPoints-1.0}infData")]
@@ -677,6 +757,9 @@ pub enum EPPResultDataValue {
     EURIDDNSSECEligibilityInfoData(eurid::EURIDDNSSECEligibilityInfoData),
     #[serde(rename = "{urn:is.isnic:xml:ns:is-ext-account-1.0}infData")]
     ISNICAccountInfo(isnic::AccountInfo),
+    /// Any result data not understood by the client
+    #[serde(other)]
+    Unknown,
 }
 
 impl EPPResultDataValue {
@@ -691,6 +774,18 @@ impl EPPResultDataValue {
             Self::EPPHostCheckResult(_) => "HostCheck",
             Self::EPPHostInfoResult(_) => "HostInfo",
             Self::EPPHostCreateResult(_) => "HostCreate",
+            Self::EPPNSSetCheckResult(_) => "NSSetCheck",
+            Self::EPPNSSetInfoResult(_) => "NSSetInfo",
+            Self::EPPNSSetCreateResult(_) => "NSSetCreate",
+            Self::EPPNSSetTransferData(_) => "NSSetTransfer",
+            Self::EPPNSSetDeleteData(_) => "NSSetDelete",
+            Self::EPPNSSetTestData(_) => "NSSetTest",
+            Self::EPPKeySetCheckResult(_) => "KeySetCheck",
+            Self::EPPKeySetInfoResult(_) => "KeySetInfo",
+            Self::EPPKeySetCreateResult(_) => "KeySetCreate",
+            Self::EPPKeySetTransferData(_) => "KeySetTransfer",
+            Self::EPPKeySetDeleteData(_) => "KeySetDelete",
+            Self::FredLowCreditData(_) => "FredLowCredit",
             Self::EPPEmailForwardCheckResult(_) => "EmailForwardCheck",
             Self::EPPEmailForwardInfoResult(_) => "EmailForwardInfo",
             Self::EPPEmailForwardCreateResult(_) => "EmailForwardCreate",
@@ -714,6 +809,8 @@ impl EPPResultDataValue {
             Self::NominetRegistrarChangeData(_) => "NominetRegistrarChange",
             Self::NominetReleaseData(_) => "NominetRelease",
             Self::NominetReleasePending(_) => "NominetReleasePending",
+            Self::NominetListData(_) => "NominetList",
+            Self::NominetAbuseFeedData(_) => "NominetAbuseFeed",
             Self::NominetSuspendData(_) => "NominetSuspend",
             Self::NominetTransferData(_) => "NominetTransfer",
             Self::SwitchBalanceInfoResult(_) => "SwitchBalance",
@@ -728,6 +825,7 @@ impl EPPResultDataValue {
             Self::EURIDRegistrationLimitData(_) => "EURIDRegistrationLimit",
             Self::EURIDPollData(_) => "EURIDPoll",
             Self::ISNICAccountInfo(_) => "ISNICAccountInfo",
+            Self::Unknown => "Unknown",
         }
     }
 }
@@ -960,18 +1058,33 @@ pub enum EPPResponseExtensionType {
     KeysysResultData(keysys::ResultData),
     #[serde(rename = "{http://www.key-systems.net/epp/keysys-1.0}poll")]
     KeysysPoll(keysys::Poll),
+    #[serde(rename = "{urn:ietf:params:xml:ns:allocationToken-1.0}allocationToken")]
+    AllocationToken(allocation_token::EPPAllocationToken),
+    /// Any extension not understood by the client
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Debug, Serialize)]
 pub enum EPPCheck {
     #[serde(rename = "{urn:ietf:params:xml:ns:domain-1.0}domain:check")]
     Domain(domain::EPPDomainCheck),
+    #[serde(rename = "{urn:ietf:params:xml:ns:domain-1.0}domain:check")]
+    DomainBatch(domain::EPPDomainBatchCheck),
     #[serde(rename = "{urn:ietf:params:xml:ns:host-1.0}host:check")]
     Host(host::EPPHostCheck),
+    #[serde(rename = "{urn:ietf:params:xml:ns:host-1.0}host:check")]
+    HostBatch(host::EPPHostBatchCheck),
     #[serde(rename = "{urn:ietf:params:xml:ns:contact-1.0}contact:check")]
     Contact(contact::EPPContactCheck),
+    #[serde(rename = "{urn:ietf:params:xml:ns:contact-1.0}contact:check")]
+    ContactBatch(contact::EPPContactBatchCheck),
     #[serde(rename = "{http://www.nic.name/epp/emailFwd-1.0}emailFwd:check")]
     EmailForward(email_forward::EPPEmailForwardCheck),
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:check")]
+    NSSet(nsset::EPPNSSetCheck),
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:check")]
+    KeySet(keyset::EPPKeySetCheck),
 }
 
 #[derive(Debug, Serialize)]
@@ -984,12 +1097,18 @@ pub enum EPPInfo {
     Contact(contact::EPPContactCheck),
     #[serde(rename = "{http://www.nic.name/epp/emailFwd-1.0}emailFwd:info")]
     EmailForward(email_forward::EPPEmailForwardCheck),
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:info")]
+    NSSet(nsset::EPPNSSetCheck),
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:info")]
+    KeySet(keyset::EPPKeySetCheck),
     #[serde(rename = "{urn:ietf:params:xml:ns:epp:maintenance-1.0}maint:info")]
     Maintenance(maintenance::EPPMaintenanceInfo),
     #[serde(rename = "{urn:ietf:params:xml:ns:maintenance-0.2}maint:info")]
     Maintenance02(maintenance::EPPMaintenanceInfo02),
     #[serde(rename = "{http://www.nominet.org.uk/epp/xml/nom-tag-1.0}tag:list")]
     TagList {},
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/std-list-1.0}list:list")]
+    NominetList(nominet::EPPList),
     #[serde(rename = "{https://www.nic.ch/epp/balance-1.0}balance:info")]
     SwitchBalace {},
     #[serde(rename = "{http://www.verisign.com/epp/balance-1.0}balance:info")]
@@ -1022,6 +1141,10 @@ pub enum EPPCreate {
     Domain(domain::EPPDomainCreate),
     #[serde(rename = "{http://www.nic.name/epp/emailFwd-1.0}emailFwd:create")]
     EmailForward(email_forward::EPPEmailForwardCreate),
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:create")]
+    NSSet(nsset::EPPNSSetCreate),
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:create")]
+    KeySet(keyset::EPPKeySetCreate),
 }
 
 #[derive(Debug, Serialize)]
@@ -1034,6 +1157,10 @@ pub enum EPPDelete {
     Domain(domain::EPPDomainCheck),
     #[serde(rename = "{http://www.nic.name/epp/emailFwd-1.0}emailFwd:delete")]
     EmailForward(email_forward::EPPEmailForwardCheck),
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:delete")]
+    NSSet(nsset::EPPNSSetCheck),
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:delete")]
+    KeySet(keyset::EPPKeySetCheck),
 }
 
 #[derive(Debug, Serialize)]
@@ -1046,6 +1173,10 @@ pub enum EPPUpdate {
     Domain(domain::EPPDomainUpdate),
     #[serde(rename = "{http://www.nic.name/epp/emailFwd-1.0}emailFwd:update")]
     EmailForward(email_forward::EPPEmailForwardUpdate),
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:update")]
+    NSSet(nsset::EPPNSSetUpdate),
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:update")]
+    KeySet(keyset::EPPKeySetUpdate),
     #[serde(rename = "{http://www.nominet.org.uk/epp/xml/std-handshake-1.0}handshake:accept")]
     NominetHandshakeAccept(nominet::EPPHandshakeAccept),
     #[serde(rename = "{http://www.nominet.org.uk/epp/xml/std-handshake-1.0}handshake:reject")]
@@ -1056,6 +1187,10 @@ pub enum EPPUpdate {
     NominetLock(nominet::EPPLock),
     #[serde(rename = "{http://www.nominet.org.uk/epp/xml/std-locks-1.0}lock:unlock")]
     NominetUnlock(nominet::EPPLock),
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/std-unrenew-1.0}unrenew:unrenew")]
+    NominetUnrenew(nominet::EPPUnrenew),
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/std-fork-1.0}fork:fork")]
+    NominetFork(nominet::EPPFork),
 }
 
 #[derive(Debug, Serialize)]
@@ -1102,6 +1237,10 @@ pub enum EPPTransferCommand {
     EmailForwardQuery(email_forward::EPPEmailForwardCheck),
     #[serde(rename = "{http://www.nic.name/epp/emailFwd-1.0}emailFwd:transfer")]
     EmailForwardRequest(email_forward::EPPEmailForwardTransfer),
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:transfer")]
+    NSSetRequest(nsset::EPPNSSetTransfer),
+    #[serde(rename = "{urn:ietf:params:xml:ns:keyset-1.3}keyset:transfer")]
+    KeySetRequest(keyset::EPPKeySetTransfer),
 }
 
 #[derive(Debug, Serialize)]
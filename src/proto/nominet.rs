@@ -577,6 +577,91 @@ pub struct EPPUnrenew {
     pub domains: Vec<String>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct EPPList {
+    #[serde(rename = "$value")]
+    pub date: EPPListDate,
+}
+
+#[derive(Debug, Serialize)]
+pub enum EPPListDate {
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/std-list-1.0}list:month")]
+    Month(String),
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/std-list-1.0}list:expiry")]
+    Expiry(String),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPListData {
+    #[serde(rename = "$attr:noDomains", default)]
+    pub count: Option<u32>,
+    #[serde(
+        rename = "{http://www.nominet.org.uk/epp/xml/std-list-1.0}domainName",
+        default
+    )]
+    pub domain_names: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPFork {
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/std-fork-1.0}fork:contactId")]
+    pub contact_id: String,
+    #[serde(
+        rename = "{http://www.nominet.org.uk/epp/xml/std-fork-1.0}fork:newContactId",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub new_contact_id: Option<String>,
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/std-fork-1.0}fork:domainName")]
+    pub domain_names: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPAbuseFeedData {
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0}key")]
+    pub key: String,
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0}activity")]
+    pub activity: String,
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0}source")]
+    pub source: String,
+    #[serde(rename = "{http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0}hostname")]
+    pub hostname: String,
+    #[serde(
+        rename = "{http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0}url",
+        default
+    )]
+    pub url: Option<String>,
+    #[serde(
+        rename = "{http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0}date",
+        deserialize_with = "super::deserialize_datetime"
+    )]
+    pub date: DateTime<Utc>,
+    #[serde(
+        rename = "{http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0}ip",
+        default
+    )]
+    pub ip: Option<String>,
+    #[serde(
+        rename = "{http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0}nameserver",
+        default
+    )]
+    pub nameserver: Option<String>,
+    #[serde(
+        rename = "{http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0}dnsAdmin",
+        default
+    )]
+    pub dns_admin: Option<String>,
+    #[serde(
+        rename = "{http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0}target",
+        default
+    )]
+    pub target: Option<String>,
+    #[serde(
+        rename = "{http://www.nominet.org.uk/epp/xml/nom-abuse-feed-1.0}wholeDomain",
+        default
+    )]
+    pub whole_domain: Option<String>,
+}
+
 #[allow(clippy::trivially_copy_pass_by_ref)]
 fn serialize_flag_bool<S>(d: &Option<bool>, s: S) -> Result<S::Ok, S::Error>
 where
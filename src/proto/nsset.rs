@@ -0,0 +1,223 @@
+use chrono::prelude::*;
+
+#[derive(Debug, Serialize)]
+pub struct EPPNSSetCheck {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:id")]
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPNSSetCheckData {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}cd", default)]
+    pub data: Vec<EPPNSSetCheckDatum>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPNSSetCheckDatum {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}id")]
+    pub id: EPPNSSetCheckID,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}reason")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPNSSetCheckID {
+    #[serde(rename = "$value")]
+    pub id: String,
+    #[serde(rename = "$attr:avail")]
+    pub available: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPNSSetInfoData {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}id")]
+    pub id: String,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}roid", default)]
+    pub registry_id: Option<String>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}status", default)]
+    pub statuses: Vec<EPPNSSetStatus>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}clID")]
+    pub client_id: String,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}crID")]
+    pub client_created_id: Option<String>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:nsset-1.2}crDate",
+        deserialize_with = "super::deserialize_datetime_opt",
+        default
+    )]
+    pub creation_date: Option<DateTime<Utc>>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}upID")]
+    pub last_updated_client: Option<String>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:nsset-1.2}upDate",
+        deserialize_with = "super::deserialize_datetime_opt",
+        default
+    )]
+    pub last_updated_date: Option<DateTime<Utc>>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:nsset-1.2}trDate",
+        deserialize_with = "super::deserialize_datetime_opt",
+        default
+    )]
+    pub last_transfer_date: Option<DateTime<Utc>>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}authInfo")]
+    pub auth_info: Option<String>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}ns", default)]
+    pub nameservers: Vec<EPPNSSetNameserver>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}tech", default)]
+    pub tech_contacts: Vec<String>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}reportlevel")]
+    pub report_level: Option<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPNSSetStatus {
+    #[serde(rename = "$attr:s")]
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPNSSetNameserver {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}name")]
+    pub name: String,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}addr", default)]
+    pub addresses: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPNSSetNameserverParam {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:name")]
+    pub name: String,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:addr")]
+    pub addresses: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPNSSetCreate {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:id")]
+    pub id: String,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:ns")]
+    pub nameservers: Vec<EPPNSSetNameserverParam>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:tech")]
+    pub tech_contacts: Vec<String>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:authInfo",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub auth_info: Option<String>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:reportlevel",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub report_level: Option<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPNSSetCreateData {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}id")]
+    pub id: String,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:nsset-1.2}crDate",
+        deserialize_with = "super::deserialize_datetime_opt",
+        default
+    )]
+    pub creation_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPNSSetUpdate {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:id")]
+    pub id: String,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:add",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub add: Option<EPPNSSetUpdateAdd>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:rem",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub remove: Option<EPPNSSetUpdateRemove>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:chg",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub change: Option<EPPNSSetUpdateChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPNSSetUpdateAdd {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:ns")]
+    pub nameservers: Vec<EPPNSSetNameserverParam>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:tech")]
+    pub tech_contacts: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPNSSetUpdateRemove {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:name")]
+    pub nameservers: Vec<String>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:tech")]
+    pub tech_contacts: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPNSSetUpdateChange {
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:authInfo",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub auth_info: Option<String>,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:reportlevel",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub report_level: Option<u8>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EPPNSSetTransfer {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:id")]
+    pub id: String,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}nsset:authInfo")]
+    pub auth_info: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPNSSetTransferData {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}id")]
+    pub id: String,
+    #[serde(
+        rename = "{urn:ietf:params:xml:ns:nsset-1.2}trDate",
+        deserialize_with = "super::deserialize_datetime"
+    )]
+    pub transfer_date: DateTime<Utc>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}clID")]
+    pub client_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPNSSetDeleteData {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}id")]
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPNSSetTestData {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}id")]
+    pub id: String,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}name", default)]
+    pub names: Vec<String>,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}result", default)]
+    pub results: Vec<EPPNSSetTestResult>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EPPNSSetTestResult {
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}testname")]
+    pub test_name: String,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}status")]
+    pub status: bool,
+    #[serde(rename = "{urn:ietf:params:xml:ns:nsset-1.2}note")]
+    pub note: Option<String>,
+}
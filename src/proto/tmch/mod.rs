@@ -549,6 +549,29 @@ impl TMCHResponse {
         }
         output.join(", ")
     }
+
+    /// Like [`TMCHResponse::response_msg`], but leaving out the reasons given in `extValue`s
+    pub fn result_msg(&self) -> String {
+        self.results
+            .iter()
+            .map(|r| {
+                match r.values.as_ref().map(|v| {
+                    v.iter()
+                        .map(|e| {
+                            e.iter()
+                                .next()
+                                .map(|(k, v)| format!("{}: {}", k, v))
+                                .unwrap_or_default()
+                        })
+                        .collect::<Vec<_>>()
+                }) {
+                    Some(v) => format!("({:?}) {}: {}", r.code, r.message, v.join(", ")),
+                    None => format!("({:?}) {}", r.code, r.message),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -624,6 +647,24 @@ impl From<u16> for TMCHResultCode {
     }
 }
 
+impl From<&TMCHResultCode> for u16 {
+    fn from(value: &TMCHResultCode) -> u16 {
+        match value {
+            TMCHResultCode::Success => 1000,
+            TMCHResultCode::SuccessNoMessages => 1300,
+            TMCHResultCode::SuccessAckToDequeue => 1301,
+            TMCHResultCode::SuccessEndingSession => 1500,
+            TMCHResultCode::CommandSyntaxError => 2001,
+            TMCHResultCode::AuthorizationError => 2201,
+            TMCHResultCode::InvalidAuthorization => 2202,
+            TMCHResultCode::ObjectDoesNotExist => 2303,
+            TMCHResultCode::ParameterValuePolicyError => 2306,
+            TMCHResultCode::CommandFailed => 2400,
+            TMCHResultCode::Other(o) => *o,
+        }
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for TMCHResultCode {
     fn deserialize<D>(deserializer: D) -> Result<TMCHResultCode, D::Error>
     where
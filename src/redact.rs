@@ -0,0 +1,248 @@
+//! Removes credentials from messages before they are written to the message log
+//!
+//! By default the contents of every `pw`, `newPW` and `authInfo` element are replaced, which
+//! covers login passwords, RFC 8807 loginSec passwords, and object authorisation info in both
+//! commands and responses. Rules can be restricted to a single namespace in the registry config.
+
+use quick_xml::events::{BytesText, Event};
+use quick_xml::name::ResolveResult;
+
+/// Text that replaces the contents of redacted elements
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Element whose contents are removed from logged messages
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RedactRule {
+    /// Local name of the element, such as `pw`
+    pub element: String,
+    /// Namespace URI of the element, elements in any namespace match if not set
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+impl RedactRule {
+    pub fn new(element: &str, namespace: Option<&str>) -> Self {
+        Self {
+            element: element.to_string(),
+            namespace: namespace.map(|n| n.to_string()),
+        }
+    }
+
+    fn matches(&self, namespace: &ResolveResult, local_name: &[u8]) -> bool {
+        if self.element.as_bytes() != local_name {
+            return false;
+        }
+        match &self.namespace {
+            None => true,
+            Some(n) => matches!(namespace, ResolveResult::Bound(b) if b.as_ref() == n.as_bytes()),
+        }
+    }
+}
+
+/// Set of rules to redact messages with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Redactor {
+    rules: Vec<RedactRule>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self {
+            rules: vec![
+                RedactRule::new("pw", None),
+                RedactRule::new("newPW", None),
+                RedactRule::new("authInfo", None),
+            ],
+        }
+    }
+}
+
+impl Redactor {
+    pub fn new(rules: Vec<RedactRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Returns a copy of the message with the contents of matching elements replaced
+    ///
+    /// # Arguments
+    /// * `msg` - Serialised XML message
+    pub fn redact(&self, msg: &str) -> Result<String, quick_xml::Error> {
+        if self.rules.is_empty() {
+            return Ok(msg.to_string());
+        }
+
+        let mut reader = quick_xml::reader::NsReader::from_str(msg);
+        let mut writer = quick_xml::Writer::new(Vec::new());
+        // Depth of nesting inside the element currently being redacted
+        let mut depth = 0usize;
+        loop {
+            let (namespace, event) = reader.read_resolved_event()?;
+            if depth > 0 {
+                match event {
+                    Event::Start(_) => depth += 1,
+                    Event::End(_) => {
+                        depth -= 1;
+                        if depth == 0 {
+                            writer.write_event(Event::Text(BytesText::new(REDACTED)))?;
+                            writer.write_event(event)?;
+                        }
+                    }
+                    Event::Eof => break,
+                    _ => {}
+                }
+                continue;
+            }
+            match &event {
+                Event::Start(e)
+                    if self
+                        .rules
+                        .iter()
+                        .any(|r| r.matches(&namespace, e.local_name().as_ref())) =>
+                {
+                    depth = 1
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            writer.write_event(event)?;
+        }
+        Ok(String::from_utf8_lossy(&writer.into_inner()).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod redact_tests {
+    use super::{RedactRule, Redactor};
+
+    #[test]
+    fn login() {
+        const XML_DATA: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <command>
+    <login>
+      <clID>AS207960</clID>
+      <pw>oldpassword</pw>
+      <newPW>supersecretpassword</newPW>
+      <options>
+        <version>1.0</version>
+        <lang>en</lang>
+      </options>
+      <svcs>
+        <objURI>urn:ietf:params:xml:ns:domain-1.0</objURI>
+        <svcExtension>
+          <extURI>urn:ietf:params:xml:ns:epp:loginSec-1.0</extURI>
+        </svcExtension>
+      </svcs>
+    </login>
+    <extension>
+      <loginSec:loginSec xmlns:loginSec="urn:ietf:params:xml:ns:epp:loginSec-1.0">
+        <loginSec:userAgent>
+          <loginSec:app>AS207960 EPP Proxy</loginSec:app>
+        </loginSec:userAgent>
+        <loginSec:pw>a long login security password</loginSec:pw>
+        <loginSec:newPW>a new long login security password</loginSec:newPW>
+      </loginSec:loginSec>
+    </extension>
+    <clTRID>ABC-12345</clTRID>
+  </command>
+</epp>"#;
+        let res = Redactor::default().redact(XML_DATA).unwrap();
+        assert!(!res.contains("oldpassword"));
+        assert!(!res.contains("supersecretpassword"));
+        assert!(!res.contains("login security password"));
+        assert!(res.contains("<pw>[REDACTED]</pw>"));
+        assert!(res.contains("<newPW>[REDACTED]</newPW>"));
+        assert!(res.contains("<loginSec:pw>[REDACTED]</loginSec:pw>"));
+        assert!(res.contains("<clID>AS207960</clID>"));
+        assert!(res.contains("<loginSec:app>AS207960 EPP Proxy</loginSec:app>"));
+        assert!(res.contains("<clTRID>ABC-12345</clTRID>"));
+    }
+
+    #[test]
+    fn transfer() {
+        const XML_DATA: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <command>
+    <transfer op="request">
+      <domain:transfer xmlns:domain="urn:ietf:params:xml:ns:domain-1.0">
+        <domain:name>example.com</domain:name>
+        <domain:period unit="y">1</domain:period>
+        <domain:authInfo>
+          <domain:pw roid="JD1234-REP">2fooBAR</domain:pw>
+        </domain:authInfo>
+      </domain:transfer>
+    </transfer>
+    <clTRID>ABC-12345</clTRID>
+  </command>
+</epp>"#;
+        let res = Redactor::default().redact(XML_DATA).unwrap();
+        assert!(!res.contains("2fooBAR"));
+        assert!(!res.contains("JD1234-REP"));
+        assert!(res.contains("<domain:authInfo>[REDACTED]</domain:authInfo>"));
+        assert!(res.contains("<domain:name>example.com</domain:name>"));
+        assert!(res.contains("<domain:period unit=\"y\">1</domain:period>"));
+    }
+
+    #[test]
+    fn info_response() {
+        const XML_DATA: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <response>
+    <result code="1000">
+      <msg>Command completed successfully</msg>
+    </result>
+    <resData>
+      <contact:infData xmlns:contact="urn:ietf:params:xml:ns:contact-1.0">
+        <contact:id>sh8013</contact:id>
+        <contact:roid>SH8013-REP</contact:roid>
+        <contact:email>jdoe@example.com</contact:email>
+        <contact:clID>ClientY</contact:clID>
+        <contact:authInfo>
+          <contact:pw>2fooBAR</contact:pw>
+        </contact:authInfo>
+      </contact:infData>
+    </resData>
+    <trID>
+      <clTRID>ABC-12345</clTRID>
+      <svTRID>54322-XYZ</svTRID>
+    </trID>
+  </response>
+</epp>"#;
+        let res = Redactor::default().redact(XML_DATA).unwrap();
+        assert!(!res.contains("2fooBAR"));
+        assert!(res.contains("<contact:authInfo>[REDACTED]</contact:authInfo>"));
+        assert!(res.contains("<contact:email>jdoe@example.com</contact:email>"));
+    }
+
+    #[test]
+    fn namespace_rule() {
+        const XML_DATA: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<epp xmlns="urn:ietf:params:xml:ns:epp-1.0">
+  <command>
+    <login>
+      <clID>AS207960</clID>
+      <pw>oldpassword</pw>
+    </login>
+    <extension>
+      <loginSec:loginSec xmlns:loginSec="urn:ietf:params:xml:ns:epp:loginSec-1.0">
+        <loginSec:pw>a long login security password</loginSec:pw>
+      </loginSec:loginSec>
+    </extension>
+  </command>
+</epp>"#;
+        let redactor = Redactor::new(vec![RedactRule::new(
+            "pw",
+            Some("urn:ietf:params:xml:ns:epp:loginSec-1.0"),
+        )]);
+        let res = redactor.redact(XML_DATA).unwrap();
+        assert!(res.contains("<pw>oldpassword</pw>"));
+        assert!(res.contains("<loginSec:pw>[REDACTED]</loginSec:pw>"));
+    }
+
+    #[test]
+    fn no_rules() {
+        const XML_DATA: &str = r#"<epp xmlns="urn:ietf:params:xml:ns:epp-1.0"><hello/></epp>"#;
+        let res = Redactor::new(vec![]).redact(XML_DATA).unwrap();
+        assert_eq!(res, XML_DATA);
+    }
+}
@@ -0,0 +1,148 @@
+//! Secrets in config files given either literally or as a reference to where they're kept
+//!
+//! A secret can be a plain string, or an object naming its source:
+//! * `{"file": "path"}` - contents of a file, such as a mounted Kubernetes secret
+//! * `{"env": "NAME"}` - value of an environment variable
+//! * `{"command": ["program", "args"...]}` - standard output of a command, for secret managers
+//!
+//! A single trailing newline is removed from file contents and command output. References are
+//! resolved whenever the config is read, so a changed secret is picked up on reload.
+
+/// Where to read a secret from
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum SecretReference {
+    /// Path of a file holding the secret
+    File(String),
+    /// Name of an environment variable holding the secret
+    Env(String),
+    /// Command and arguments to run, that prints the secret
+    Command(Vec<String>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SecretConfig {
+    Literal(String),
+    Reference(SecretReference),
+}
+
+fn trim_newline(mut secret: String) -> String {
+    if secret.ends_with('\n') {
+        secret.pop();
+        if secret.ends_with('\r') {
+            secret.pop();
+        }
+    }
+    secret
+}
+
+impl SecretReference {
+    /// Fetches the secret from wherever it's kept
+    pub fn resolve(&self) -> Result<String, String> {
+        match self {
+            SecretReference::File(path) => std::fs::read_to_string(path)
+                .map(trim_newline)
+                .map_err(|e| format!("can't read secret file {}: {}", path, e)),
+            SecretReference::Env(name) => std::env::var(name)
+                .map_err(|e| format!("can't read secret environment variable {}: {}", name, e)),
+            SecretReference::Command(command) => {
+                let (program, args) = match command.split_first() {
+                    Some(c) => c,
+                    None => return Err("empty secret command".to_string()),
+                };
+                let output = std::process::Command::new(program)
+                    .args(args)
+                    .stderr(std::process::Stdio::inherit())
+                    .output()
+                    .map_err(|e| format!("can't run secret command {}: {}", program, e))?;
+                if !output.status.success() {
+                    return Err(format!(
+                        "secret command {} exited with {}",
+                        program, output.status
+                    ));
+                }
+                String::from_utf8(output.stdout)
+                    .map(trim_newline)
+                    .map_err(|_| format!("secret command {} output isn't UTF-8", program))
+            }
+        }
+    }
+}
+
+/// Deserializes a secret, resolving it if it's a reference, for use with `deserialize_with`
+pub fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    use serde::de::{Deserialize, Error};
+
+    match SecretConfig::deserialize(deserializer)? {
+        SecretConfig::Literal(s) => Ok(s),
+        SecretConfig::Reference(r) => r.resolve().map_err(D::Error::custom),
+    }
+}
+
+/// Deserializes an optional secret, resolving it if it's a reference, for use with
+/// `deserialize_with` alongside `default`
+pub fn deserialize_opt<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    use serde::de::{Deserialize, Error};
+
+    match Option::<SecretConfig>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(SecretConfig::Literal(s)) => Ok(Some(s)),
+        Some(SecretConfig::Reference(r)) => r.resolve().map(Some).map_err(D::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod secret_tests {
+    #[derive(Debug, Deserialize)]
+    struct Config {
+        #[serde(deserialize_with = "super::deserialize")]
+        password: String,
+        #[serde(default, deserialize_with = "super::deserialize_opt")]
+        new_password: Option<String>,
+    }
+
+    #[test]
+    fn literal() {
+        let conf: Config = serde_json::from_str(r#"{"password": "hunter2"}"#).unwrap();
+        assert_eq!(conf.password, "hunter2");
+        assert_eq!(conf.new_password, None);
+    }
+
+    #[test]
+    fn env() {
+        std::env::set_var("EPP_PROXY_TEST_SECRET", "from-env");
+        let conf: Config = serde_json::from_str(
+            r#"{"password": "hunter2", "new_password": {"env": "EPP_PROXY_TEST_SECRET"}}"#,
+        )
+        .unwrap();
+        assert_eq!(conf.new_password.as_deref(), Some("from-env"));
+
+        assert!(serde_json::from_str::<Config>(
+            r#"{"password": {"env": "EPP_PROXY_TEST_SECRET_MISSING"}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn file() {
+        let path = std::env::temp_dir().join(format!("epp-proxy-secret-{}", std::process::id()));
+        std::fs::write(&path, "from-file\n").unwrap();
+        let conf: Config =
+            serde_json::from_value(serde_json::json!({ "password": { "file": path } })).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(conf.password, "from-file");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command() {
+        let conf: Config =
+            serde_json::from_str(r#"{"password": {"command": ["echo", "from-command"]}}"#).unwrap();
+        assert_eq!(conf.password, "from-command");
+
+        assert!(serde_json::from_str::<Config>(r#"{"password": {"command": ["false"]}}"#).is_err());
+    }
+}